@@ -0,0 +1,100 @@
+//! Cold vs warm skill scan timings over synthetic skill folders.
+//!
+//! `cargo bench --bench scan -- [folders]` (default 2000)
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use app_lib::{AgentConfig, AgentType, ScanIndex, SkillService};
+
+const DEFAULT_FOLDERS: usize = 2000;
+
+fn main() {
+    // cargo passes `--bench` along with any user arguments
+    let folders = std::env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_FOLDERS);
+
+    let root = std::env::temp_dir().join(format!("skills-scan-bench-{}", std::process::id()));
+    let skills_dir = root.join("skills");
+    let index_path = root.join("scan-index.json");
+
+    let start = Instant::now();
+    generate(&skills_dir, folders).expect("failed to generate skill folders");
+    println!("generated {} skill folders in {:?}", folders, start.elapsed());
+
+    let config = AgentConfig {
+        agent: AgentType::Custom("bench".to_string()),
+        name: "Bench".to_string(),
+        config_dir: root.to_string_lossy().to_string(),
+        skills_dir: Some(skills_dir.to_string_lossy().to_string()),
+        file_patterns: Vec::new(),
+        project_patterns: Vec::new(),
+        enabled: true,
+    };
+
+    let runtime = tokio::runtime::Runtime::new().expect("failed to start runtime");
+    runtime.block_on(async {
+        let mut index = ScanIndex::default();
+        let (cold, found) = scan(&config, &mut index).await;
+        report("cold (no index)", cold, found);
+        index.save(&index_path).await.expect("failed to save index");
+
+        // A fresh launch: load the saved index, nothing changed on disk
+        let start = Instant::now();
+        let mut index = ScanIndex::load(&index_path).await;
+        let loaded = start.elapsed();
+        let (warm, found) = scan(&config, &mut index).await;
+        report("warm (index load)", loaded, index.len());
+        report("warm (scan)", warm, found);
+
+        // Edit 1% of the skills between launches
+        let changed = (folders / 100).max(1);
+        for i in 0..changed {
+            std::fs::write(
+                skills_dir.join(folder_name(i)).join("skill.md"),
+                format!("# Skill {}\n\nEdited description.\n", i),
+            ).expect("failed to edit skill");
+        }
+        let (incremental, found) = scan(&config, &mut index).await;
+        report(&format!("warm ({} changed)", changed), incremental, found);
+
+        println!("speedup: {:.1}x", cold.as_secs_f64() / (loaded + warm).as_secs_f64());
+    });
+
+    let _ = std::fs::remove_dir_all(&root);
+}
+
+async fn scan(config: &AgentConfig, index: &mut ScanIndex) -> (Duration, usize) {
+    let start = Instant::now();
+    let skills = SkillService::scan_all_skills(std::slice::from_ref(config), index, |_| {}).await;
+    (start.elapsed(), skills.len())
+}
+
+fn report(label: &str, elapsed: Duration, skills: usize) {
+    println!("{:<22} {:>10.2?}  {} skills", label, elapsed, skills);
+}
+
+fn folder_name(i: usize) -> String {
+    format!("skill-{:05}", i)
+}
+
+/// Skill folders shaped like real ones: an entry file with a description,
+/// plus references and scripts subdirectories
+fn generate(skills_dir: &Path, count: usize) -> std::io::Result<()> {
+    for i in 0..count {
+        let folder: PathBuf = skills_dir.join(folder_name(i));
+        std::fs::create_dir_all(folder.join("references"))?;
+        std::fs::create_dir_all(folder.join("scripts"))?;
+
+        std::fs::write(
+            folder.join("skill.md"),
+            format!("# Skill {}\n\nSynthetic skill number {} for scan benchmarks.\n\n{}", i, i, "Body text. ".repeat(200)),
+        )?;
+        std::fs::write(folder.join("references").join("guide.md"), "# Guide\n\nReference material.\n")?;
+        std::fs::write(folder.join("scripts").join("run.sh"), "#!/bin/sh\necho run\n")?;
+    }
+
+    Ok(())
+}
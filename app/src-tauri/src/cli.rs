@@ -0,0 +1,113 @@
+//! Headless subcommands for scripts and CI, e.g.
+//! `ai-skills-aggregator check path/to/repo`, and `ai-skills-aggregator mcp`
+//! to serve the skill library to agents over MCP
+
+use std::path::PathBuf;
+
+use tokio::io::BufReader;
+
+use crate::models::{AgentConfig, SyncReport};
+use crate::services::{McpLibraryService, RegistryService};
+
+const USAGE: &str = "Usage: ai-skills-aggregator <lock|sync|check> [project-dir]
+       ai-skills-aggregator mcp    Serve the skill library as an MCP server on stdin/stdout";
+
+/// Run a CLI subcommand if one was given.
+/// Returns the process exit code, or None to start the GUI.
+pub fn run_from_args() -> Option<i32> {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first()?.as_str();
+
+    if !matches!(command, "lock" | "sync" | "check" | "mcp" | "help" | "--help") {
+        return None;
+    }
+
+    if matches!(command, "help" | "--help") {
+        println!("{}", USAGE);
+        return Some(0);
+    }
+
+    let project_dir = args.get(1)
+        .map(PathBuf::from)
+        .or_else(|| std::env::current_dir().ok())
+        .unwrap_or_else(|| PathBuf::from("."));
+
+    let runtime = match tokio::runtime::Runtime::new() {
+        Ok(rt) => rt,
+        Err(e) => {
+            eprintln!("Failed to start runtime: {}", e);
+            return Some(1);
+        }
+    };
+
+    if command == "mcp" {
+        return Some(runtime.block_on(serve_library()));
+    }
+
+    let service = RegistryService::new();
+
+    let code = runtime.block_on(async {
+        match command {
+            "lock" => match service.lock_project(&project_dir).await {
+                Ok(lockfile) => {
+                    println!("Locked {} skill install(s)", lockfile.skills.len());
+                    0
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    1
+                }
+            },
+            _ => match service.sync_project(&project_dir, command == "check").await {
+                Ok(report) => {
+                    print_report(&report);
+                    // check fails when the machine has drifted from the lockfile
+                    if report.in_sync || report.applied { 0 } else { 1 }
+                }
+                Err(e) => {
+                    eprintln!("error: {}", e);
+                    1
+                }
+            },
+        }
+    });
+
+    Some(code)
+}
+
+/// Serve the skill library to an MCP client until it closes stdin. Stdout
+/// carries the protocol, so problems go to stderr.
+async fn serve_library() -> i32 {
+    let mut server = McpLibraryService::new(AgentConfig::defaults()).await;
+
+    match server.serve(BufReader::new(tokio::io::stdin()), tokio::io::stdout()).await {
+        Ok(()) => 0,
+        Err(e) => {
+            eprintln!("error: {}", e);
+            1
+        }
+    }
+}
+
+fn print_report(report: &SyncReport) {
+    if report.in_sync {
+        println!("Skills are in sync with the lockfile");
+        return;
+    }
+
+    for action in &report.actions {
+        println!(
+            "{:<8} {} ({}) {} -> {}: {}",
+            format!("{:?}", action.kind).to_lowercase(),
+            action.skill_id,
+            action.agent,
+            action.installed_version.as_deref().unwrap_or("-"),
+            action.locked_version.as_deref().unwrap_or("-"),
+            action.reason,
+        );
+    }
+
+    if !report.applied {
+        println!("{} skill(s) out of sync with the lockfile", report.actions.len());
+    }
+}
@@ -0,0 +1,27 @@
+use crate::models::{BundleImportOptions, BundleManifest, BundlePreview, Skill};
+use crate::services::bundle_service::BundleService;
+
+#[tauri::command]
+pub fn export_skill_bundle(skills: Vec<Skill>, dest_path: String) -> Result<BundleManifest, String> {
+    let service = BundleService::new();
+    service.export_bundle(&skills, &dest_path)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub fn preview_skill_bundle(bundle_path: String, agent: Option<String>) -> Result<BundlePreview, String> {
+    let service = BundleService::new();
+    service.preview_bundle(&bundle_path, agent.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn import_skill_bundle(
+    bundle_path: String,
+    options: BundleImportOptions,
+) -> Result<Vec<Skill>, String> {
+    let service = BundleService::new();
+    service.import_bundle(&bundle_path, &options)
+        .await
+        .map_err(|e| e.to_string())
+}
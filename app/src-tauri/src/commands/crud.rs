@@ -9,6 +9,10 @@ fn parse_agent(agent: &str) -> Result<AgentType, String> {
         "continuedev" | "continue" => Ok(AgentType::ContinueDev),
         "aider" => Ok(AgentType::Aider),
         "windsurf" | "codeium" => Ok(AgentType::Windsurf),
+        "copilot" | "github-copilot" => Ok(AgentType::Copilot),
+        "codex" => Ok(AgentType::Codex),
+        "gemini" | "gemini-cli" => Ok(AgentType::Gemini),
+        "cline" => Ok(AgentType::Cline),
         _ => Ok(AgentType::Custom(agent.to_string())),
     }
 }
@@ -20,6 +24,7 @@ fn parse_format(format: &str) -> Result<SkillFormat, String> {
         "yaml" | "yml" => Ok(SkillFormat::Yaml),
         "python" | "py" => Ok(SkillFormat::Python),
         "text" | "txt" | "plaintext" => Ok(SkillFormat::PlainText),
+        "mdc" => Ok(SkillFormat::Mdc),
         _ => Err(format!("Unknown format: {}", format)),
     }
 }
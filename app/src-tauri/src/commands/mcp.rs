@@ -0,0 +1,65 @@
+use std::path::{Path, PathBuf};
+
+use tauri::State;
+
+use crate::models::{AgentType, McpScope, McpServer, McpServerEntry};
+use crate::services::McpService;
+use crate::state::AppState;
+
+/// MCP servers configured for every agent, including those of `project_dir`
+#[tauri::command]
+pub async fn list_mcp_servers(
+    project_dir: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Vec<McpServerEntry>, String> {
+    let configs = state.agent_configs.read().await.clone();
+    Ok(McpService::list_servers(&configs, project_dir.as_deref().map(Path::new)))
+}
+
+/// Problems that would stop `agent` from starting the server
+#[tauri::command]
+pub fn validate_mcp_server(agent: String, server: McpServer) -> Vec<String> {
+    McpService::validate(&AgentType::parse(&agent), &server)
+}
+
+/// Add a server to a config file, or replace `previous_name` with it
+#[tauri::command]
+pub async fn save_mcp_server(
+    agent: String,
+    config_path: String,
+    server: McpServer,
+    previous_name: Option<String>,
+) -> Result<McpServer, String> {
+    McpService::new()
+        .save_server(&AgentType::parse(&agent), Path::new(&config_path), &server, previous_name.as_deref())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_mcp_server(agent: String, config_path: String, name: String) -> Result<(), String> {
+    McpService::new()
+        .remove_server(&AgentType::parse(&agent), Path::new(&config_path), &name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Write a server into other agents' configs in their own formats
+#[tauri::command]
+pub async fn copy_mcp_server(
+    server: McpServer,
+    agents: Vec<String>,
+    scope: McpScope,
+    project_dir: Option<String>,
+    overwrite: bool,
+    state: State<'_, AppState>,
+) -> Result<Vec<McpServerEntry>, String> {
+    let configs = state.agent_configs.read().await.clone();
+    let agents: Vec<AgentType> = agents.iter().map(|a| AgentType::parse(a)).collect();
+    let project_dir = project_dir.map(PathBuf::from);
+
+    McpService::new()
+        .copy_server(&server, &agents, scope, &configs, project_dir.as_deref(), overwrite)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -2,3 +2,5 @@ pub mod skills;
 pub mod registry;
 pub mod updates;
 pub mod auth;
+pub mod bundle;
+pub mod mcp;
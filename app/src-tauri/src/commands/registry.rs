@@ -1,9 +1,17 @@
-use crate::models::{SkillRegistry, RemoteSkill, InstalledSkill, RegistryConfig, SkillUpdate};
+use tauri::State;
+
+use crate::models::{
+    SkillRegistry, RemoteSkill, InstalledSkill, RegistryConfig, SkillUpdate, GitSource, AgentType,
+    DiscoveredSkill, Lockfile, SyncReport, DriftItem, ReconcileFix, ReconcileReport
+};
 use crate::services::registry_service::RegistryService;
+use crate::services::git_service::{GitService, GitDiscovery};
 
 #[tauri::command]
-pub async fn fetch_registry(url: String) -> Result<SkillRegistry, String> {
-    let service = RegistryService::new();
+pub async fn fetch_registry(
+    url: String,
+    service: State<'_, RegistryService>,
+) -> Result<SkillRegistry, String> {
     let config = RegistryConfig {
         url,
         name: "Custom".into(),
@@ -21,35 +29,39 @@ pub async fn install_remote_skill(
     skill: RemoteSkill,
     registry_url: String,
     agent: String,
+    version: Option<String>,
+    service: State<'_, RegistryService>,
 ) -> Result<InstalledSkill, String> {
-    let service = RegistryService::new();
-
-    service.install_skill(&skill, &registry_url, &agent)
+    service.install_skill(&skill, &registry_url, &agent, version.as_deref())
         .await
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn uninstall_remote_skill(skill_id: String, agent: String) -> Result<(), String> {
-    let service = RegistryService::new();
-
+pub async fn uninstall_remote_skill(
+    skill_id: String,
+    agent: String,
+    service: State<'_, RegistryService>,
+) -> Result<(), String> {
     service.uninstall_skill(&skill_id, &agent)
         .await
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn get_installed_skills() -> Result<Vec<InstalledSkill>, String> {
-    let service = RegistryService::new();
-
+pub async fn get_installed_skills(
+    service: State<'_, RegistryService>,
+) -> Result<Vec<InstalledSkill>, String> {
     service.get_installed_skills()
         .await
         .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn check_skill_updates(registry_url: String) -> Result<Vec<SkillUpdate>, String> {
-    let service = RegistryService::new();
+pub async fn check_skill_updates(
+    registry_url: String,
+    service: State<'_, RegistryService>,
+) -> Result<Vec<SkillUpdate>, String> {
     let config = RegistryConfig {
         url: registry_url,
         name: "".into(),
@@ -65,3 +77,98 @@ pub async fn check_skill_updates(registry_url: String) -> Result<Vec<SkillUpdate
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn discover_git_skills(
+    url: String,
+    git_ref: Option<String>,
+    subdirectory: Option<String>,
+    agent: String,
+) -> Result<GitDiscovery, String> {
+    let service = GitService::new();
+    let source = GitSource::new(url, git_ref, subdirectory).map_err(|e| e.to_string())?;
+
+    service.discover(&source, &AgentType::parse(&agent))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn install_git_skills(
+    url: String,
+    git_ref: Option<String>,
+    paths: Vec<String>,
+    agent: String,
+    service: State<'_, RegistryService>,
+) -> Result<Vec<InstalledSkill>, String> {
+    let source = GitSource::new(url, git_ref, None).map_err(|e| e.to_string())?;
+
+    service.install_from_git(&source, &paths, &agent)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn discover_local_skills(
+    path: String,
+    agent: String,
+    service: State<'_, RegistryService>,
+) -> Result<Vec<DiscoveredSkill>, String> {
+    service.discover_local(&path, &agent)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn install_local_skills(
+    path: String,
+    paths: Vec<String>,
+    agent: String,
+    service: State<'_, RegistryService>,
+) -> Result<Vec<InstalledSkill>, String> {
+    service.install_from_local(&path, &paths, &agent)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn lock_project_skills(
+    project_dir: String,
+    service: State<'_, RegistryService>,
+) -> Result<Lockfile, String> {
+    service.lock_project(std::path::Path::new(&project_dir))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn sync_project_skills(
+    project_dir: String,
+    check_only: bool,
+    service: State<'_, RegistryService>,
+) -> Result<SyncReport, String> {
+    service.sync_project(std::path::Path::new(&project_dir), check_only)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reconcile_installed_skills(
+    registry_urls: Vec<String>,
+    service: State<'_, RegistryService>,
+) -> Result<ReconcileReport, String> {
+    service.reconcile(&registry_urls)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn fix_installed_skill(
+    item: DriftItem,
+    fix: ReconcileFix,
+    service: State<'_, RegistryService>,
+) -> Result<Option<InstalledSkill>, String> {
+    service.fix_drift(&item, fix)
+        .await
+        .map_err(|e| e.to_string())
+}
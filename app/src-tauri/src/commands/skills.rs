@@ -1,46 +1,59 @@
-use crate::models::{AgentConfig, AgentType, Skill, SkillFile};
-use crate::services::SkillService;
-use std::sync::Mutex;
-use once_cell::sync::Lazy;
+use std::path::{Path, PathBuf};
 
-// Global state for skills and configs
-static SKILLS_CACHE: Lazy<Mutex<Vec<Skill>>> = Lazy::new(|| Mutex::new(Vec::new()));
-static AGENT_CONFIGS: Lazy<Mutex<Vec<AgentConfig>>> = Lazy::new(|| {
-    Mutex::new(AgentConfig::defaults())
-});
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, State};
+
+use crate::models::{AgentConfig, AgentType, AiderConventions, ClaudeContext, EffectiveRules, RuleMode, Skill, SkillFile, SkillKind, WindsurfTrigger};
+use crate::services::{AiderService, ClaudeMemoryService, ClaudeService, EffectiveRulesService, RuleService, ScanIndex, SkillService, TemplateService, WindsurfService};
+use crate::state::AppState;
+
+/// Event carrying skills found so far while `scan_skills` runs
+pub const SCAN_PROGRESS_EVENT: &str = "skills://scan-progress";
+
+#[derive(Clone, Serialize)]
+struct ScanProgress<'a> {
+    skills: &'a [Skill],
+}
 
 #[tauri::command]
-pub fn scan_skills() -> Result<Vec<Skill>, String> {
-    let configs = AGENT_CONFIGS.lock().map_err(|e| e.to_string())?;
-    let skills = SkillService::scan_all_skills(&configs).map_err(|e| e.to_string())?;
+pub async fn scan_skills(app: AppHandle, state: State<'_, AppState>) -> Result<Vec<Skill>, String> {
+    let configs = state.agent_configs.read().await.clone();
+
+    // Held for the whole scan so concurrent scans don't race on the index
+    let mut index_slot = state.scan_index.lock().await;
+    let path = ScanIndex::default_path();
+    if index_slot.is_none() {
+        *index_slot = Some(ScanIndex::load(&path).await);
+    }
+    let index = index_slot.get_or_insert_with(ScanIndex::default);
+
+    let skills = SkillService::scan_all_skills(&configs, index, |found| {
+        if let Err(e) = app.emit(SCAN_PROGRESS_EVENT, ScanProgress { skills: found }) {
+            log::warn!("Failed to emit scan progress: {}", e);
+        }
+    }).await;
+
+    if let Err(e) = index.save(&path).await {
+        log::warn!("Failed to save scan index: {}", e);
+    }
 
     // Update cache
-    let mut cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
-    *cache = skills.clone();
+    *state.skills.write().await = skills.clone();
 
     Ok(skills)
 }
 
 #[tauri::command]
-pub fn get_all_skills() -> Result<Vec<Skill>, String> {
-    let cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
-    Ok(cache.clone())
+pub async fn get_all_skills(state: State<'_, AppState>) -> Result<Vec<Skill>, String> {
+    Ok(state.skills.read().await.clone())
 }
 
 #[tauri::command]
-pub fn get_skills_by_agent(agent: String) -> Result<Vec<Skill>, String> {
-    let cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
-
-    let agent_type: AgentType = match agent.to_lowercase().as_str() {
-        "claude" => AgentType::Claude,
-        "cursor" => AgentType::Cursor,
-        "continuedev" => AgentType::ContinueDev,
-        "aider" => AgentType::Aider,
-        "windsurf" => AgentType::Windsurf,
-        other => AgentType::Custom(other.to_string()),
-    };
+pub async fn get_skills_by_agent(agent: String, state: State<'_, AppState>) -> Result<Vec<Skill>, String> {
+    let agent_type = AgentType::parse(&agent);
 
-    let filtered: Vec<Skill> = cache.iter()
+    let filtered: Vec<Skill> = state.skills.read().await
+        .iter()
         .filter(|s| s.agent == agent_type)
         .cloned()
         .collect();
@@ -49,9 +62,8 @@ pub fn get_skills_by_agent(agent: String) -> Result<Vec<Skill>, String> {
 }
 
 #[tauri::command]
-pub fn get_skill_by_id(id: String) -> Result<Option<Skill>, String> {
-    let cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
-    Ok(cache.iter().find(|s| s.id == id).cloned())
+pub async fn get_skill_by_id(id: String, state: State<'_, AppState>) -> Result<Option<Skill>, String> {
+    Ok(state.skills.read().await.iter().find(|s| s.id == id).cloned())
 }
 
 #[tauri::command]
@@ -60,23 +72,16 @@ pub fn read_skill_content(file_path: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-pub fn create_skill(
+pub async fn create_skill(
     agent: String,
     name: String,
     content: String,
     description: Option<String>,
     tags: Option<Vec<String>>,
+    state: State<'_, AppState>,
 ) -> Result<Skill, String> {
-    let configs = AGENT_CONFIGS.lock().map_err(|e| e.to_string())?;
-
-    let agent_type: AgentType = match agent.to_lowercase().as_str() {
-        "claude" => AgentType::Claude,
-        "cursor" => AgentType::Cursor,
-        "continuedev" => AgentType::ContinueDev,
-        "aider" => AgentType::Aider,
-        "windsurf" => AgentType::Windsurf,
-        other => AgentType::Custom(other.to_string()),
-    };
+    let configs = state.agent_configs.read().await.clone();
+    let agent_type = AgentType::parse(&agent);
 
     let skill = SkillService::create_skill(
         &agent_type,
@@ -88,18 +93,17 @@ pub fn create_skill(
     ).map_err(|e| e.to_string())?;
 
     // Update cache
-    let mut cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
-    cache.push(skill.clone());
+    state.skills.write().await.push(skill.clone());
 
     Ok(skill)
 }
 
 #[tauri::command]
-pub fn update_skill(file_path: String, content: String) -> Result<Skill, String> {
+pub async fn update_skill(file_path: String, content: String, state: State<'_, AppState>) -> Result<Skill, String> {
     let skill = SkillService::update_skill(&file_path, &content).map_err(|e| e.to_string())?;
 
     // Update cache
-    let mut cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
+    let mut cache = state.skills.write().await;
     if let Some(pos) = cache.iter().position(|s| s.folder_path == file_path) {
         cache[pos] = skill.clone();
     }
@@ -108,31 +112,32 @@ pub fn update_skill(file_path: String, content: String) -> Result<Skill, String>
 }
 
 #[tauri::command]
-pub fn delete_skill(file_path: String) -> Result<(), String> {
+pub async fn delete_skill(file_path: String, state: State<'_, AppState>) -> Result<(), String> {
     SkillService::delete_skill(&file_path).map_err(|e| e.to_string())?;
 
     // Update cache
-    let mut cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
-    cache.retain(|s| s.folder_path != file_path);
+    state.skills.write().await.retain(|s| s.folder_path != file_path);
 
     Ok(())
 }
 
 #[tauri::command]
-pub fn duplicate_skill(file_path: String, new_name: String) -> Result<Skill, String> {
+pub async fn duplicate_skill(
+    file_path: String,
+    new_name: String,
+    state: State<'_, AppState>,
+) -> Result<Skill, String> {
     let skill = SkillService::duplicate_skill(&file_path, &new_name).map_err(|e| e.to_string())?;
 
     // Update cache
-    let mut cache = SKILLS_CACHE.lock().map_err(|e| e.to_string())?;
-    cache.push(skill.clone());
+    state.skills.write().await.push(skill.clone());
 
     Ok(skill)
 }
 
 #[tauri::command]
-pub fn get_agent_configs() -> Result<Vec<AgentConfig>, String> {
-    let configs = AGENT_CONFIGS.lock().map_err(|e| e.to_string())?;
-    Ok(configs.clone())
+pub async fn get_agent_configs(state: State<'_, AppState>) -> Result<Vec<AgentConfig>, String> {
+    Ok(state.agent_configs.read().await.clone())
 }
 
 #[tauri::command]
@@ -154,3 +159,206 @@ pub fn create_skill_file(
 pub fn delete_skill_file(file_path: String) -> Result<(), String> {
     SkillService::delete_file(&file_path).map_err(|e| e.to_string())
 }
+
+/// Skills, instructions and rules every enabled agent keeps in a project
+#[tauri::command]
+pub async fn scan_project_skills(project_dir: String, state: State<'_, AppState>) -> Result<Vec<Skill>, String> {
+    let configs = state.agent_configs.read().await.clone();
+    Ok(SkillService::scan_project_skills(Path::new(&project_dir), &configs))
+}
+
+#[tauri::command]
+pub fn scan_project_rules(project_dir: String) -> Result<Vec<Skill>, String> {
+    Ok(RuleService::scan_rules(Path::new(&project_dir)))
+}
+
+/// Create a Cursor rule in a project, or in `~/.cursor/rules` without one
+#[tauri::command]
+pub async fn create_cursor_rule(
+    project_dir: Option<String>,
+    name: String,
+    mode: RuleMode,
+    description: Option<String>,
+    globs: Vec<String>,
+    content: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Skill, String> {
+    let root = match &project_dir {
+        Some(dir) => PathBuf::from(dir),
+        None => dirs::home_dir().ok_or("Cannot find home directory")?,
+    };
+
+    let rule = RuleService::rule_for_mode(mode, description, globs).map_err(|e| e.to_string())?;
+    let skill = RuleService::create_rule(&RuleService::rules_dir(&root), &name, &rule, content.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    // Only home rules are part of the scanned skills
+    if project_dir.is_none() {
+        state.skills.write().await.push(skill.clone());
+    }
+
+    Ok(skill)
+}
+
+#[tauri::command]
+pub async fn set_cursor_rule_mode(
+    file_path: String,
+    mode: RuleMode,
+    description: Option<String>,
+    globs: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Skill, String> {
+    let skill = RuleService::set_mode(&file_path, mode, description, globs).map_err(|e| e.to_string())?;
+
+    // Update cache
+    let mut cache = state.skills.write().await;
+    if let Some(pos) = cache.iter().position(|s| s.folder_path == file_path) {
+        cache[pos] = skill.clone();
+    }
+
+    Ok(skill)
+}
+
+/// Create a Claude skill, subagent, command, memory file, rule or output
+/// style in `~/.claude`, or in a project's `.claude` folder
+#[tauri::command]
+pub async fn create_claude_item(
+    project_dir: Option<String>,
+    kind: SkillKind,
+    name: String,
+    description: Option<String>,
+    content: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Skill, String> {
+    let claude_dir = match &project_dir {
+        Some(dir) => Path::new(dir).join(".claude"),
+        None => state.agent_configs.read().await
+            .iter()
+            .find(|c| c.agent == AgentType::Claude)
+            .map(|c| PathBuf::from(&c.config_dir))
+            .ok_or("Agent config not found: Claude")?,
+    };
+
+    let skill = ClaudeService::create_item(&claude_dir, kind, &name, description.as_deref(), content.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    // Only home items are part of the scanned skills
+    if project_dir.is_none() {
+        state.skills.write().await.push(skill.clone());
+    }
+
+    Ok(skill)
+}
+
+#[tauri::command]
+pub fn get_claude_template(kind: SkillKind, name: String, description: Option<String>) -> String {
+    TemplateService::claude_template(kind, &name, description.as_deref())
+}
+
+#[tauri::command]
+pub fn validate_claude_item(kind: SkillKind, content: String) -> Vec<String> {
+    ClaudeService::validate(kind, &content)
+}
+
+/// Every CLAUDE.md file, and file they import, that Claude loads when
+/// started in a directory
+#[tauri::command]
+pub async fn get_claude_context(directory: String, state: State<'_, AppState>) -> Result<ClaudeContext, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let claude_dir = state.agent_configs.read().await
+        .iter()
+        .find(|c| c.agent == AgentType::Claude)
+        .map(|c| PathBuf::from(&c.config_dir))
+        .ok_or("Agent config not found: Claude")?;
+
+    ClaudeMemoryService::context(&claude_dir, &home, Path::new(&directory)).map_err(|e| e.to_string())
+}
+
+/// Create a Windsurf workspace rule in a project's `.windsurf/rules`
+#[tauri::command]
+pub fn create_windsurf_rule(
+    project_dir: String,
+    name: String,
+    trigger: WindsurfTrigger,
+    description: Option<String>,
+    globs: Vec<String>,
+    content: Option<String>,
+) -> Result<Skill, String> {
+    let rule = WindsurfService::rule_for_trigger(trigger, description, globs).map_err(|e| e.to_string())?;
+    WindsurfService::create_rule(&WindsurfService::rules_dir(Path::new(&project_dir)), &name, &rule, content.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+/// Create a Windsurf workflow in a project, or in the global workflows
+/// without one
+#[tauri::command]
+pub async fn create_windsurf_workflow(
+    project_dir: Option<String>,
+    name: String,
+    description: Option<String>,
+    content: Option<String>,
+    state: State<'_, AppState>,
+) -> Result<Skill, String> {
+    let workflows_dir = match &project_dir {
+        Some(dir) => WindsurfService::workflows_dir(Path::new(dir)),
+        None => state.agent_configs.read().await
+            .iter()
+            .find(|c| c.agent == AgentType::Windsurf)
+            .map(|c| WindsurfService::global_workflows_dir(Path::new(&c.config_dir)))
+            .ok_or("Agent config not found: Windsurf")?,
+    };
+
+    let skill = WindsurfService::create_workflow(&workflows_dir, &name, description.as_deref(), content.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    // Only global workflows are part of the scanned skills
+    if project_dir.is_none() {
+        state.skills.write().await.push(skill.clone());
+    }
+
+    Ok(skill)
+}
+
+#[tauri::command]
+pub async fn set_windsurf_rule_trigger(
+    file_path: String,
+    trigger: WindsurfTrigger,
+    description: Option<String>,
+    globs: Vec<String>,
+    state: State<'_, AppState>,
+) -> Result<Skill, String> {
+    let skill = WindsurfService::set_trigger(&file_path, trigger, description, globs).map_err(|e| e.to_string())?;
+
+    // Update cache
+    let mut cache = state.skills.write().await;
+    if let Some(pos) = cache.iter().position(|s| s.folder_path == file_path) {
+        cache[pos] = skill.clone();
+    }
+
+    Ok(skill)
+}
+
+#[tauri::command]
+pub fn validate_windsurf_file(file_path: String, content: String) -> Vec<String> {
+    let path = Path::new(&file_path);
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    WindsurfService::validate(&file_name, WindsurfService::kind_for_path(path), &content)
+}
+
+/// Which conventions Aider loads in a project, or from the home directory
+/// without one
+#[tauri::command]
+pub fn get_aider_conventions(project_dir: Option<String>) -> Result<AiderConventions, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    Ok(AiderService::conventions(&home, project_dir.as_deref().map(Path::new)))
+}
+
+/// Rules each agent applies when working on a file, and why
+#[tauri::command]
+pub async fn effective_rules(path: String, state: State<'_, AppState>) -> Result<EffectiveRules, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let skills = state.skills.read().await.clone();
+    let configs = state.agent_configs.read().await.clone();
+
+    EffectiveRulesService::effective_rules(Path::new(&path), &home, &skills, &configs).map_err(|e| e.to_string())
+}
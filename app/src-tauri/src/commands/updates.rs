@@ -1,31 +1,170 @@
-use crate::services::update_service::{UpdateService, SkillUpdate, UpdateCheckResult};
+use std::collections::HashMap;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::models::{InstalledSkill, UpdateChannel, UpdatePolicy};
+use crate::services::diff_service::MergeResolution;
+use crate::services::registry_service::RegistryService;
+use crate::services::scheduler_service::{
+    SchedulerService, SchedulerRun, SchedulerSettings, SchedulerState, SCHEDULER_TICK,
+};
+use crate::services::update_service::{
+    UpdateService, SkillUpdate, UpdateCheckResult, UpdateDetails, UpdateMerge, BatchUpdateOptions, BatchUpdateReport,
+    SkipMode, SkippedVersion,
+};
 
 #[tauri::command]
-pub async fn check_for_updates() -> Result<UpdateCheckResult, String> {
-    let service = UpdateService::new();
+pub async fn check_for_updates(
+    service: State<'_, UpdateService>,
+) -> Result<UpdateCheckResult, String> {
     Ok(service.check_all_updates().await)
 }
 
 #[tauri::command]
-pub async fn apply_skill_update(update: SkillUpdate) -> Result<(), String> {
-    let service = UpdateService::new();
-    service.apply_update(&update).await.map_err(|e| e.to_string())
+pub async fn apply_skill_update(
+    update: SkillUpdate,
+    resolutions: Option<HashMap<String, MergeResolution>>,
+    service: State<'_, UpdateService>,
+) -> Result<(), String> {
+    service.apply_update(&update, &resolutions.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn apply_all_skill_updates(updates: Vec<SkillUpdate>) -> Result<Vec<Result<(), String>>, String> {
-    let service = UpdateService::new();
-    Ok(service.apply_all_updates(&updates).await)
+pub async fn preview_update_merge(
+    update: SkillUpdate,
+    service: State<'_, UpdateService>,
+) -> Result<UpdateMerge, String> {
+    service.preview_update_merge(&update).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn rollback_skill(skill_id: String, agent: String) -> Result<(), String> {
-    let service = UpdateService::new();
+pub async fn apply_all_skill_updates(
+    updates: Vec<SkillUpdate>,
+    options: Option<BatchUpdateOptions>,
+    service: State<'_, UpdateService>,
+) -> Result<BatchUpdateReport, String> {
+    Ok(service.apply_updates(&updates, &options.unwrap_or_default()).await)
+}
+
+#[tauri::command]
+pub async fn rollback_skill(
+    skill_id: String,
+    agent: String,
+    service: State<'_, UpdateService>,
+) -> Result<(), String> {
     service.rollback_skill(&skill_id, &agent).await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-pub async fn skip_skill_version(skill_id: String, version: String) -> Result<(), String> {
-    let service = UpdateService::new();
-    service.skip_version(&skill_id, &version).await.map_err(|e| e.to_string())
+pub async fn skip_skill_version(
+    skill_id: String,
+    version: String,
+    agent: Option<String>,
+    registry_url: Option<String>,
+    mode: Option<SkipMode>,
+    service: State<'_, UpdateService>,
+) -> Result<(), String> {
+    service.skip_version(&skill_id, &version, agent.as_deref(), registry_url.as_deref(), mode.unwrap_or_default())
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_skipped_versions(
+    service: State<'_, UpdateService>,
+) -> Result<Vec<SkippedVersion>, String> {
+    Ok(service.list_skipped_versions().await)
+}
+
+#[tauri::command]
+pub async fn unskip_version(
+    skip: SkippedVersion,
+    service: State<'_, UpdateService>,
+) -> Result<(), String> {
+    service.unskip_version(&skip).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pin_skill_version(
+    skill_id: String,
+    agent: String,
+    pin: Option<String>,
+    service: State<'_, RegistryService>,
+) -> Result<InstalledSkill, String> {
+    service.set_pin(&skill_id, &agent, pin).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_skill_update_channel(
+    skill_id: String,
+    agent: String,
+    channel: UpdateChannel,
+    service: State<'_, RegistryService>,
+) -> Result<InstalledSkill, String> {
+    service.set_channel(&skill_id, &agent, channel).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_update_details(
+    update: SkillUpdate,
+    service: State<'_, UpdateService>,
+) -> Result<UpdateDetails, String> {
+    service.get_update_details(&update).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_skill_update_policy(
+    skill_id: String,
+    agent: String,
+    policy: UpdatePolicy,
+    service: State<'_, RegistryService>,
+) -> Result<InstalledSkill, String> {
+    service.set_update_policy(&skill_id, &agent, policy).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_update_scheduler(
+    service: State<'_, SchedulerService>,
+) -> Result<SchedulerState, String> {
+    Ok(service.get_state().await)
+}
+
+#[tauri::command]
+pub async fn set_update_scheduler(
+    settings: SchedulerSettings,
+    service: State<'_, SchedulerService>,
+) -> Result<SchedulerState, String> {
+    service.set_settings(settings).await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn run_update_scheduler(
+    service: State<'_, SchedulerService>,
+) -> Result<SchedulerRun, String> {
+    service.run_now().await.map_err(|e| e.to_string())
+}
+
+/// Event emitted after a background check that found or applied updates
+pub const SCHEDULER_RUN_EVENT: &str = "updates://scheduled-run";
+
+/// Start the background update loop for the lifetime of the app
+pub fn spawn_update_scheduler(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let service = app.state::<SchedulerService>();
+
+        loop {
+            match service.run_if_due().await {
+                Ok(Some(run)) if run.has_changes() => {
+                    if let Err(e) = app.emit(SCHEDULER_RUN_EVENT, &run) {
+                        log::warn!("Failed to emit update summary: {}", e);
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Scheduled update check failed: {}", e),
+            }
+
+            tokio::time::sleep(SCHEDULER_TICK).await;
+        }
+    });
 }
@@ -1,16 +1,36 @@
 // Module declarations
+pub mod cli;
 mod commands;
 mod models;
 mod services;
+mod state;
 
 use commands::skills;
 use commands::registry;
 use commands::updates;
 use commands::auth;
+use commands::bundle;
+use commands::mcp;
+use services::{RegistryService, SchedulerService, StateService, UpdateService};
+use state::AppState;
+
+// For benches/scan.rs, which times scans against the real services
+#[doc(hidden)]
+pub use models::{AgentConfig, AgentType};
+#[doc(hidden)]
+pub use services::{ScanIndex, SkillService};
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // One store owns the state files; services share it instead of each
+    // command creating its own
+    let state = StateService::new();
+
     tauri::Builder::default()
+        .manage(AppState::new())
+        .manage(RegistryService::with_state(state.clone()))
+        .manage(UpdateService::with_state(state.clone()))
+        .manage(SchedulerService::with_state(state))
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_os::init())
@@ -22,6 +42,7 @@ pub fn run() {
                         .build(),
                 )?;
             }
+            updates::spawn_update_scheduler(app.handle().clone());
             Ok(())
         })
         .invoke_handler(tauri::generate_handler![
@@ -39,18 +60,60 @@ pub fn run() {
             skills::get_skill_files,
             skills::create_skill_file,
             skills::delete_skill_file,
+            skills::scan_project_skills,
+            skills::scan_project_rules,
+            skills::create_cursor_rule,
+            skills::set_cursor_rule_mode,
+            skills::create_claude_item,
+            skills::get_claude_template,
+            skills::validate_claude_item,
+            skills::get_claude_context,
+            skills::create_windsurf_rule,
+            skills::create_windsurf_workflow,
+            skills::set_windsurf_rule_trigger,
+            skills::validate_windsurf_file,
+            skills::get_aider_conventions,
+            skills::effective_rules,
             // Registry commands
             registry::fetch_registry,
             registry::install_remote_skill,
             registry::uninstall_remote_skill,
             registry::get_installed_skills,
             registry::check_skill_updates,
+            registry::discover_git_skills,
+            registry::install_git_skills,
+            registry::discover_local_skills,
+            registry::install_local_skills,
+            registry::lock_project_skills,
+            registry::sync_project_skills,
+            registry::reconcile_installed_skills,
+            registry::fix_installed_skill,
+            // Bundle commands
+            bundle::export_skill_bundle,
+            bundle::preview_skill_bundle,
+            bundle::import_skill_bundle,
+            // MCP commands
+            mcp::list_mcp_servers,
+            mcp::validate_mcp_server,
+            mcp::save_mcp_server,
+            mcp::remove_mcp_server,
+            mcp::copy_mcp_server,
             // Update commands
             updates::check_for_updates,
             updates::apply_skill_update,
+            updates::preview_update_merge,
             updates::apply_all_skill_updates,
             updates::rollback_skill,
             updates::skip_skill_version,
+            updates::list_skipped_versions,
+            updates::unskip_version,
+            updates::pin_skill_version,
+            updates::set_skill_update_channel,
+            updates::get_update_details,
+            updates::set_skill_update_policy,
+            updates::get_update_scheduler,
+            updates::set_update_scheduler,
+            updates::run_update_scheduler,
             // Auth commands
             auth::login,
             auth::logout,
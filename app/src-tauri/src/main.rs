@@ -2,5 +2,11 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+  // Headless subcommands (lock/sync/check) for scripts and CI, and the
+  // MCP server mode (mcp)
+  if let Some(code) = app_lib::cli::run_from_args() {
+    std::process::exit(code);
+  }
+
   app_lib::run();
 }
@@ -9,6 +9,9 @@ pub struct AgentConfig {
     pub config_dir: String,
     pub skills_dir: Option<String>,
     pub file_patterns: Vec<String>,
+    /// Patterns relative to a project root, for `scan_project_skills`
+    #[serde(default)]
+    pub project_patterns: Vec<String>,
     pub enabled: bool,
 }
 
@@ -25,9 +28,19 @@ impl AgentConfig {
                 config_dir: format!("{}/.claude", home_str),
                 skills_dir: Some(format!("{}/.claude/skills", home_str)),
                 file_patterns: vec![
-                    "*.md".to_string(),
                     "CLAUDE.md".to_string(),
-                    "rules/*.md".to_string(),
+                    "agents/*.md".to_string(),
+                    "commands/**/*.md".to_string(),
+                    "rules/**/*.md".to_string(),
+                    "output-styles/*.md".to_string(),
+                ],
+                project_patterns: vec![
+                    "CLAUDE.md".to_string(),
+                    "CLAUDE.local.md".to_string(),
+                    ".claude/agents/*.md".to_string(),
+                    ".claude/commands/**/*.md".to_string(),
+                    ".claude/rules/**/*.md".to_string(),
+                    ".claude/skills/*/SKILL.md".to_string(),
                 ],
                 enabled: true,
             },
@@ -39,6 +52,11 @@ impl AgentConfig {
                 file_patterns: vec![
                     ".cursorrules".to_string(),
                     "*.cursorrules".to_string(),
+                    "rules/**/*.mdc".to_string(),
+                ],
+                project_patterns: vec![
+                    ".cursorrules".to_string(),
+                    ".cursor/rules/**/*.mdc".to_string(),
                 ],
                 enabled: true,
             },
@@ -48,32 +66,111 @@ impl AgentConfig {
                 config_dir: format!("{}/.continue", home_str),
                 skills_dir: None,
                 file_patterns: vec![
+                    "config.yaml".to_string(),
                     "config.json".to_string(),
-                    "profiles/*.json".to_string(),
+                    "prompts/**/*.prompt".to_string(),
+                    "prompts/**/*.md".to_string(),
+                    "rules/**/*.md".to_string(),
+                ],
+                project_patterns: vec![
+                    ".continue/prompts/**/*.prompt".to_string(),
+                    ".continue/prompts/**/*.md".to_string(),
+                    ".continue/rules/**/*.md".to_string(),
                 ],
-                enabled: false,
+                enabled: true,
             },
             AgentConfig {
                 agent: AgentType::Aider,
                 name: "Aider".to_string(),
-                config_dir: format!("{}/.aider", home_str),
-                skills_dir: None,
+                // Aider reads ~/.aider.conf.yml; the files in its `read:` list are the skills
+                config_dir: home_str.to_string(),
+                skills_dir: Some(format!("{}/.aider/conventions", home_str)),
                 file_patterns: vec![
                     ".aider.conf.yml".to_string(),
-                    "*.txt".to_string(),
                 ],
-                enabled: false,
+                project_patterns: vec![
+                    ".aider.conf.yml".to_string(),
+                    "CONVENTIONS.md".to_string(),
+                ],
+                enabled: true,
             },
             AgentConfig {
                 agent: AgentType::Windsurf,
                 name: "Windsurf/Codeium".to_string(),
-                config_dir: format!("{}/.codeium", home_str),
+                config_dir: format!("{}/.codeium/windsurf", home_str),
                 skills_dir: None,
                 file_patterns: vec![
-                    "*.yaml".to_string(),
-                    "*.json".to_string(),
+                    "memories/global_rules.md".to_string(),
+                    "global_workflows/*.md".to_string(),
                 ],
-                enabled: false,
+                project_patterns: vec![
+                    ".windsurfrules".to_string(),
+                    ".windsurf/rules/**/*.md".to_string(),
+                    ".windsurf/workflows/*.md".to_string(),
+                ],
+                enabled: true,
+            },
+            AgentConfig {
+                agent: AgentType::Copilot,
+                name: "GitHub Copilot".to_string(),
+                config_dir: format!("{}/.copilot", home_str),
+                skills_dir: Some(format!("{}/.copilot/skills", home_str)),
+                file_patterns: vec![
+                    "copilot-instructions.md".to_string(),
+                    "instructions/**/*.instructions.md".to_string(),
+                    "prompts/*.prompt.md".to_string(),
+                ],
+                project_patterns: vec![
+                    ".github/copilot-instructions.md".to_string(),
+                    ".github/instructions/**/*.instructions.md".to_string(),
+                    ".github/prompts/*.prompt.md".to_string(),
+                    ".github/skills/*/SKILL.md".to_string(),
+                ],
+                enabled: true,
+            },
+            AgentConfig {
+                agent: AgentType::Codex,
+                name: "OpenAI Codex".to_string(),
+                config_dir: format!("{}/.codex", home_str),
+                skills_dir: Some(format!("{}/.codex/skills", home_str)),
+                file_patterns: vec![
+                    "AGENTS.md".to_string(),
+                    "AGENTS.override.md".to_string(),
+                    "prompts/*.md".to_string(),
+                ],
+                project_patterns: vec![
+                    "AGENTS.md".to_string(),
+                    "AGENTS.override.md".to_string(),
+                ],
+                enabled: true,
+            },
+            AgentConfig {
+                agent: AgentType::Gemini,
+                name: "Gemini CLI".to_string(),
+                config_dir: format!("{}/.gemini", home_str),
+                skills_dir: Some(format!("{}/.gemini/skills", home_str)),
+                file_patterns: vec![
+                    "GEMINI.md".to_string(),
+                ],
+                project_patterns: vec![
+                    "GEMINI.md".to_string(),
+                    ".gemini/GEMINI.md".to_string(),
+                ],
+                enabled: true,
+            },
+            AgentConfig {
+                agent: AgentType::Cline,
+                name: "Cline".to_string(),
+                config_dir: format!("{}/Documents/Cline", home_str),
+                skills_dir: None,
+                file_patterns: vec![
+                    "Rules/**/*.md".to_string(),
+                ],
+                project_patterns: vec![
+                    ".clinerules".to_string(),
+                    ".clinerules/**/*.md".to_string(),
+                ],
+                enabled: true,
             },
         ]
     }
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+/// A file named in the `read:` list of an `.aider.conf.yml`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct AiderReadFile {
+    /// The entry as written in the config
+    pub entry: String,
+    /// The entry resolved against the home or project directory
+    pub path: String,
+    pub config_path: String,
+    pub exists: bool,
+    /// Whether Aider loads it: the file exists and its config is the one in effect
+    pub loaded: bool,
+}
+
+/// Which conventions files Aider loads for the home directory or a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AiderConventions {
+    /// Config files found, in the order Aider reads them; later ones override
+    /// earlier ones
+    pub configs: Vec<String>,
+    /// The config whose `read:` list is in effect
+    pub effective_config: Option<String>,
+    pub files: Vec<AiderReadFile>,
+    /// `CONVENTIONS.md` files that exist but no `read:` list names
+    pub unreferenced: Vec<String>,
+}
@@ -0,0 +1,83 @@
+use serde::{Deserialize, Serialize};
+use super::AgentType;
+
+/// Current bundle manifest format
+pub const BUNDLE_FORMAT_VERSION: u32 = 1;
+
+/// `manifest.json` at the root of an exported skill bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleManifest {
+    pub format_version: u32,
+    pub created_at: i64,
+    pub skills: Vec<BundleSkill>,
+}
+
+/// A skill stored in a bundle under `skills/<dir>/`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleSkill {
+    pub name: String,
+    /// Directory name inside the bundle's `skills/` folder
+    pub dir: String,
+    pub agent: AgentType,
+    pub description: Option<String>,
+    pub version: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    pub author: Option<String>,
+    /// Whether the original was a skill folder or a single file
+    pub is_folder: bool,
+    /// Where the skill lived on the exporting machine
+    pub original_path: String,
+    pub files: Vec<BundleFile>,
+}
+
+/// A file inside a bundled skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BundleFile {
+    /// Path relative to the skill root, using `/` separators
+    pub path: String,
+    pub sha256: String,
+    pub size: u64,
+    #[serde(default)]
+    pub is_entry: bool,
+}
+
+/// What to do when an imported skill already exists
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ConflictStrategy {
+    #[default]
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+/// Options for importing a bundle
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct BundleImportOptions {
+    /// Target agent; defaults to each skill's original agent
+    pub agent: Option<String>,
+    /// Names of skills to import; defaults to all
+    pub skills: Option<Vec<String>>,
+    #[serde(default)]
+    pub on_conflict: ConflictStrategy,
+    /// Convert entry files to the target agent's native format
+    #[serde(default)]
+    pub convert: bool,
+}
+
+/// Result of inspecting a bundle before import
+#[derive(Debug, Clone, Serialize)]
+pub struct BundlePreview {
+    pub manifest: BundleManifest,
+    pub conflicts: Vec<BundleConflict>,
+    /// Files whose hash does not match the manifest
+    pub corrupted_files: Vec<String>,
+}
+
+/// A bundled skill that would replace an existing one
+#[derive(Debug, Clone, Serialize)]
+pub struct BundleConflict {
+    pub name: String,
+    pub existing_path: String,
+}
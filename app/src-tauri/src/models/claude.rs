@@ -0,0 +1,96 @@
+use serde::{Deserialize, Serialize};
+use super::Skill;
+
+/// What a file is to the agent that reads it
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum SkillKind {
+    /// A skill folder, or a single-file skill
+    #[default]
+    Skill,
+    /// A Claude subagent (`agents/*.md`)
+    Subagent,
+    /// A Claude custom slash command (`commands/**/*.md`)
+    SlashCommand,
+    /// Always-loaded memory such as `CLAUDE.md`
+    Memory,
+    /// A rule file (Claude `rules/`, Cursor `.mdc` and `.cursorrules`)
+    Rule,
+    /// A Claude output style (`output-styles/*.md`)
+    OutputStyle,
+}
+
+/// Frontmatter of Claude skills, subagents, slash commands and output styles
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ClaudeFrontmatter {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    /// Tools a subagent may use; empty inherits all tools
+    pub tools: Vec<String>,
+    pub model: Option<String>,
+    /// Slash command argument hint, e.g. `[pr-number] [priority]`
+    pub argument_hint: Option<String>,
+    /// Tools a slash command may use without asking, e.g. `Bash(git add:*)`
+    pub allowed_tools: Vec<String>,
+}
+
+/// Why a CLAUDE.md file is part of Claude's context
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum MemoryLevel {
+    /// `~/.claude/CLAUDE.md`, loaded in every project
+    User,
+    /// `CLAUDE.md` or `.claude/CLAUDE.md` in the directory or one above it
+    Project,
+    /// `CLAUDE.local.md` in the directory or one above it
+    Local,
+    /// A memory file below the directory, loaded once Claude reads files there
+    Nested,
+    /// A file pulled in by an `@path` import
+    Import,
+}
+
+/// A file in Claude's effective context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemorySource {
+    pub skill: Skill,
+    pub level: MemoryLevel,
+    /// The file whose `@` import pulled this one in
+    pub imported_from: Option<String>,
+    /// Import hops from the memory file that started the chain
+    pub depth: usize,
+}
+
+/// Why an `@` import was not loaded
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum ImportProblem {
+    Missing,
+    /// The target is already being imported further up the chain
+    Cycle,
+    /// The import is past Claude's maximum import depth
+    TooDeep,
+}
+
+/// An `@` import that was not loaded
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ImportIssue {
+    pub file: String,
+    /// The import as written, without the `@`
+    pub import: String,
+    /// The import resolved against the importing file
+    pub path: String,
+    pub problem: ImportProblem,
+}
+
+/// Everything Claude loads as memory when started in a directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ClaudeContext {
+    pub directory: String,
+    /// Sources in the order Claude reads them; imports follow the file that
+    /// imports them
+    pub sources: Vec<MemorySource>,
+    /// The sources' contents joined, each under a comment naming its file
+    pub text: String,
+    pub issues: Vec<ImportIssue>,
+}
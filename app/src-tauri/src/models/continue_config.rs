@@ -0,0 +1,89 @@
+use serde::{Deserialize, Serialize};
+
+/// Part of a Continue `config.json`/`config.yaml` an entry comes from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum ContinueSection {
+    /// `systemMessage` (config.json)
+    SystemMessage,
+    /// `rules`, as plain strings or `{ name, rule, globs }`
+    Rules,
+    /// `prompts` with `{ name, description, prompt }` (config.yaml)
+    Prompts,
+    /// `customCommands` with `{ name, description, prompt }` (config.json)
+    CustomCommands,
+    /// `slashCommands`, the built-in commands enabled in config.json
+    SlashCommands,
+}
+
+impl ContinueSection {
+    /// Key of the section in the config file
+    pub fn key(&self) -> &'static str {
+        match self {
+            ContinueSection::SystemMessage => "systemMessage",
+            ContinueSection::Rules => "rules",
+            ContinueSection::Prompts => "prompts",
+            ContinueSection::CustomCommands => "customCommands",
+            ContinueSection::SlashCommands => "slashCommands",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "systemMessage" => Some(ContinueSection::SystemMessage),
+            "rules" => Some(ContinueSection::Rules),
+            "prompts" => Some(ContinueSection::Prompts),
+            "customCommands" => Some(ContinueSection::CustomCommands),
+            "slashCommands" => Some(ContinueSection::SlashCommands),
+            _ => None,
+        }
+    }
+
+    /// Field of a list entry holding the text shown and edited as the skill
+    /// content. `None` for plain string entries and `systemMessage`.
+    pub fn content_field(&self) -> Option<&'static str> {
+        match self {
+            ContinueSection::SystemMessage => None,
+            ContinueSection::Rules => Some("rule"),
+            ContinueSection::Prompts | ContinueSection::CustomCommands => Some("prompt"),
+            ContinueSection::SlashCommands => Some("description"),
+        }
+    }
+}
+
+/// Where a prompt, rule or command lives inside a Continue config file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ContinueEntry {
+    pub config_path: String,
+    pub section: ContinueSection,
+    /// Position in the section's list; 0 for `systemMessage`
+    pub index: usize,
+}
+
+impl ContinueEntry {
+    /// Path standing in for the entry's file, `<config>#<section>/<index>`
+    pub fn item_path(&self) -> String {
+        match self.section {
+            ContinueSection::SystemMessage => format!("{}#{}", self.config_path, self.section.key()),
+            _ => format!("{}#{}/{}", self.config_path, self.section.key(), self.index),
+        }
+    }
+
+    /// The entry an item path points to, if it is one
+    pub fn from_item_path(path: &str) -> Option<Self> {
+        let (config_path, fragment) = path.rsplit_once('#')?;
+        let (key, index) = match fragment.split_once('/') {
+            Some((key, index)) => (key, Some(index)),
+            None => (fragment, None),
+        };
+
+        let section = ContinueSection::from_key(key)?;
+        let index = match (section, index) {
+            (ContinueSection::SystemMessage, None) => 0,
+            (ContinueSection::SystemMessage, Some(_)) | (_, None) => return None,
+            (_, Some(index)) => index.parse().ok()?,
+        };
+
+        Some(Self { config_path: config_path.to_string(), section, index })
+    }
+}
@@ -20,6 +20,12 @@ pub enum AppError {
 
     #[error("Already exists: {0}")]
     AlreadyExists(String),
+
+    #[error("Checksum mismatch: {0}")]
+    ChecksumMismatch(String),
+
+    #[error("Merge conflict: {0}")]
+    MergeConflict(String),
 }
 
 impl From<std::io::Error> for AppError {
@@ -0,0 +1,59 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use super::AgentType;
+
+/// How an agent talks to an MCP server
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum McpTransport {
+    /// A local process speaking over stdin/stdout
+    #[default]
+    Stdio,
+    /// A remote server over streamable HTTP
+    Http,
+    /// A remote server over server-sent events
+    Sse,
+}
+
+/// Whether a server is configured for every project or for one project
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum McpScope {
+    #[default]
+    User,
+    Project,
+}
+
+/// An MCP server definition, independent of any agent's config format.
+/// Registry skills ship these in `mcp_servers`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct McpServer {
+    pub name: String,
+    #[serde(default, alias = "type")]
+    pub transport: McpTransport,
+    #[serde(default)]
+    pub command: Option<String>,
+    #[serde(default)]
+    pub args: Vec<String>,
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// An MCP server found in an agent's config file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpServerEntry {
+    pub server: McpServer,
+    pub agent: AgentType,
+    pub scope: McpScope,
+    pub config_path: String,
+    /// Whether changes can be written back to the config file; servers in
+    /// Continue's main config are edited there
+    pub editable: bool,
+}
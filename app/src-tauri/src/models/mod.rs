@@ -2,8 +2,22 @@ mod skill;
 mod agent;
 mod error;
 mod registry;
+mod bundle;
+mod project;
+mod rule;
+mod claude;
+mod continue_config;
+mod aider;
+mod mcp;
 
 pub use skill::*;
 pub use agent::*;
 pub use error::*;
 pub use registry::*;
+pub use bundle::*;
+pub use project::*;
+pub use rule::*;
+pub use claude::*;
+pub use continue_config::*;
+pub use aider::*;
+pub use mcp::*;
@@ -0,0 +1,93 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+
+/// Team manifest file checked into a repository
+pub const PROJECT_MANIFEST_FILE: &str = "skills.toml";
+/// Generated lockfile next to the manifest
+pub const PROJECT_LOCK_FILE: &str = "skills.lock";
+/// Current lockfile format
+pub const LOCKFILE_VERSION: u32 = 1;
+
+/// `skills.toml` - which registry skills a project needs
+///
+/// ```toml
+/// [registries]
+/// team = "https://github.com/acme/skills"
+///
+/// [skills.code-review]
+/// registry = "team"
+/// version = "1.2.0"
+/// agents = ["claude", "cursor"]
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ProjectManifest {
+    /// Registry name -> URL
+    #[serde(default)]
+    pub registries: BTreeMap<String, String>,
+    /// Skill id -> requirement
+    #[serde(default)]
+    pub skills: BTreeMap<String, ManifestSkill>,
+}
+
+/// A skill requirement in `skills.toml`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestSkill {
+    /// Registry name from `[registries]`, or a registry URL
+    pub registry: String,
+    /// Version requirement; omitted or `*` means latest
+    #[serde(default)]
+    pub version: Option<String>,
+    /// Agents to install for; defaults to every agent the skill supports
+    #[serde(default)]
+    pub agents: Vec<String>,
+}
+
+/// `skills.lock` - exact resolved versions for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Lockfile {
+    pub version: u32,
+    #[serde(default)]
+    pub skills: Vec<LockedSkill>,
+}
+
+/// A pinned skill install
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LockedSkill {
+    pub id: String,
+    pub version: String,
+    pub agent: String,
+    pub registry: String,
+    /// Resolved download URL of the skill file
+    pub source: String,
+    /// `sha256:<hex>` of the downloaded file
+    pub checksum: String,
+}
+
+/// What `sync` needs to do (or did) for one skill
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncActionKind {
+    Install,
+    Update,
+    Remove,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncAction {
+    pub kind: SyncActionKind,
+    pub skill_id: String,
+    pub agent: String,
+    pub installed_version: Option<String>,
+    pub locked_version: Option<String>,
+    pub reason: String,
+}
+
+/// Result of syncing (or checking) a project against its lockfile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyncReport {
+    pub actions: Vec<SyncAction>,
+    /// True when the machine already matched the lockfile
+    pub in_sync: bool,
+    /// False for check-only runs
+    pub applied: bool,
+}
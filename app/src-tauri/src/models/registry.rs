@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use super::{AppError, McpServer};
 
 /// Remote skill registry manifest
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,6 +29,70 @@ pub struct RemoteSkill {
     pub files: SkillFiles,
     pub url: Option<String>,
     pub checksum: Option<String>,
+    /// Published versions in any order; empty for single-version registries
+    #[serde(default)]
+    pub versions: Vec<SkillVersion>,
+    /// MCP servers the skill needs, added to the agent's config on install
+    #[serde(default)]
+    pub mcp_servers: Vec<McpServer>,
+}
+
+/// A published version of a registry skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillVersion {
+    pub version: String,
+    /// Files for this version; defaults to the skill's top-level files
+    #[serde(default)]
+    pub files: Option<SkillFiles>,
+    pub checksum: Option<String>,
+    /// Release date (ISO 8601)
+    #[serde(default)]
+    pub released_at: Option<String>,
+    #[serde(default)]
+    pub changelog: Option<String>,
+    /// Link to release notes, absolute or relative to the registry
+    #[serde(default)]
+    pub changelog_url: Option<String>,
+    /// Yanked versions are never picked by version resolution
+    #[serde(default)]
+    pub yanked: bool,
+}
+
+impl RemoteSkill {
+    /// All published versions. Registries without a version history expose
+    /// the top-level version as the only release.
+    pub fn releases(&self) -> Vec<SkillVersion> {
+        let mut releases = self.versions.clone();
+
+        if !releases.iter().any(|r| r.version == self.version) {
+            releases.push(SkillVersion {
+                version: self.version.clone(),
+                files: None,
+                checksum: self.checksum.clone(),
+                released_at: None,
+                changelog: None,
+                changelog_url: None,
+                yanked: false,
+            });
+        }
+
+        releases
+    }
+
+    /// This skill as published at a specific version
+    pub fn at_version(&self, version: &str) -> Option<RemoteSkill> {
+        let release = self.releases().into_iter().find(|r| r.version == version)?;
+
+        Some(RemoteSkill {
+            version: release.version,
+            files: release.files.unwrap_or_else(|| self.files.clone()),
+            checksum: release.checksum.or_else(|| {
+                // The top-level checksum belongs to the top-level version only
+                (version == self.version).then(|| self.checksum.clone()).flatten()
+            }),
+            ..self.clone()
+        })
+    }
 }
 
 /// Agent-specific file paths within the registry
@@ -39,6 +104,10 @@ pub struct SkillFiles {
     pub continue_dev: Option<String>,
     pub aider: Option<String>,
     pub windsurf: Option<String>,
+    pub copilot: Option<String>,
+    pub codex: Option<String>,
+    pub gemini: Option<String>,
+    pub cline: Option<String>,
 }
 
 /// Record of an installed remote skill
@@ -50,6 +119,147 @@ pub struct InstalledSkill {
     pub installed_path: String,
     pub agent: String,
     pub installed_at: i64,
+    #[serde(default)]
+    pub source: InstallSource,
+    /// Commit the skill was installed from (git sources only)
+    #[serde(default)]
+    pub commit_sha: Option<String>,
+    /// Version constraint updates must stay within (e.g. `~1.4`)
+    #[serde(default)]
+    pub pinned_version: Option<String>,
+    #[serde(default)]
+    pub channel: UpdateChannel,
+    /// `sha256:<hex>` of the content as installed, to detect local edits
+    #[serde(default)]
+    pub content_hash: Option<String>,
+    #[serde(default)]
+    pub update_policy: UpdatePolicy,
+    /// Names of the MCP servers this install added to the agent's config
+    #[serde(default)]
+    pub mcp_servers: Vec<String>,
+    /// Project lockfiles whose sync installed this skill; sync only removes
+    /// skills it installed
+    #[serde(default)]
+    pub lockfiles: Vec<String>,
+}
+
+/// How an install record and the disk have drifted apart
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DriftKind {
+    /// Recorded, but the skill's folder is gone (deleted or moved)
+    Orphan,
+    /// The folder exists but the installed file or entry file is gone
+    Missing,
+    /// Content differs from what was installed
+    Modified,
+    /// A folder in an agent's skills directory that has no install record
+    /// but matches a registry skill
+    Untracked,
+}
+
+/// Ways to resolve a drift item
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconcileFix {
+    /// Drop the install record, leaving the disk as it is
+    Forget,
+    /// Install the recorded skill again, replacing what is on disk
+    Reinstall,
+    /// Record an untracked folder as installed from the matching registry
+    Adopt,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DriftItem {
+    pub kind: DriftKind,
+    pub skill_id: String,
+    pub agent: String,
+    /// Recorded install path, or the untracked folder
+    pub path: String,
+    /// Registry (or source) the skill was installed from or matches
+    pub registry_url: Option<String>,
+    /// Fixes that apply to this item
+    pub fixes: Vec<ReconcileFix>,
+}
+
+/// Result of comparing the installed skills database with the disk
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReconcileReport {
+    pub items: Vec<DriftItem>,
+    /// Number of install records checked
+    pub checked: usize,
+}
+
+/// What the background scheduler does with a skill's updates
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdatePolicy {
+    /// Report updates, never apply them automatically
+    #[default]
+    Notify,
+    /// Apply patch updates (same major.minor) automatically
+    AutoPatch,
+    /// Apply minor and patch updates (same major) automatically
+    AutoMinor,
+    /// Hold at the installed version; no updates are offered
+    Pinned,
+}
+
+/// Which releases a skill follows
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum UpdateChannel {
+    /// Only stable releases
+    #[default]
+    Stable,
+    /// Stable and pre-release versions
+    Beta,
+}
+
+/// Where an installed skill came from
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum InstallSource {
+    #[default]
+    Registry,
+    Git(GitSource),
+    /// Copied from a folder on disk
+    Local { path: String },
+    /// Extracted from a `.zip` or `.tar.gz` bundle
+    Archive { path: String },
+}
+
+/// A git repository containing skill folders
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct GitSource {
+    /// Remote URL or local repository path
+    pub url: String,
+    /// Branch, tag or commit to check out (defaults to the remote HEAD)
+    #[serde(default)]
+    pub git_ref: Option<String>,
+    /// Path inside the repository to look for skills
+    #[serde(default)]
+    pub subdirectory: Option<String>,
+}
+
+impl GitSource {
+    pub fn new(url: String, git_ref: Option<String>, subdirectory: Option<String>) -> Result<Self, AppError> {
+        let source = Self { url, git_ref, subdirectory };
+        source.validate()?;
+        Ok(source)
+    }
+
+    /// Reject URLs and refs git would read as command-line options
+    pub fn validate(&self) -> Result<(), AppError> {
+        if self.url.trim().is_empty() || self.url.trim_start().starts_with('-') {
+            return Err(AppError::InvalidPath(format!("Invalid git URL: {}", self.url)));
+        }
+        if let Some(git_ref) = self.git_ref.as_deref().filter(|r| r.trim_start().starts_with('-')) {
+            return Err(AppError::InvalidPath(format!("Invalid git ref: {}", git_ref)));
+        }
+        Ok(())
+    }
 }
 
 /// Registry configuration
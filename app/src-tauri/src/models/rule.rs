@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use super::Skill;
+
+/// How Cursor attaches a project rule to the conversation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum RuleMode {
+    /// Included in every request
+    Always,
+    /// Included when a referenced file matches one of the globs
+    AutoAttached,
+    /// Offered to the agent by description, which decides whether to read it
+    AgentRequested,
+    /// Only included when mentioned with `@rule-name`
+    Manual,
+}
+
+/// Frontmatter of a Cursor `.mdc` rule file
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CursorRule {
+    pub description: Option<String>,
+    pub globs: Vec<String>,
+    pub always_apply: bool,
+}
+
+impl CursorRule {
+    /// Attachment mode implied by the frontmatter, following Cursor's precedence
+    pub fn mode(&self) -> RuleMode {
+        if self.always_apply {
+            RuleMode::Always
+        } else if !self.globs.is_empty() {
+            RuleMode::AutoAttached
+        } else if self.description.as_deref().is_some_and(|d| !d.trim().is_empty()) {
+            RuleMode::AgentRequested
+        } else {
+            RuleMode::Manual
+        }
+    }
+}
+
+/// When Windsurf applies a workspace rule
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum WindsurfTrigger {
+    /// Included in every request
+    AlwaysOn,
+    /// Only included when mentioned with `@rule-name`
+    #[default]
+    Manual,
+    /// Offered to the model by description, which decides whether to read it
+    ModelDecision,
+    /// Included when a file being worked on matches one of the globs
+    Glob,
+}
+
+impl WindsurfTrigger {
+    /// Value of the `trigger` frontmatter key
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WindsurfTrigger::AlwaysOn => "always_on",
+            WindsurfTrigger::Manual => "manual",
+            WindsurfTrigger::ModelDecision => "model_decision",
+            WindsurfTrigger::Glob => "glob",
+        }
+    }
+
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim() {
+            "always_on" => Some(WindsurfTrigger::AlwaysOn),
+            "manual" => Some(WindsurfTrigger::Manual),
+            "model_decision" => Some(WindsurfTrigger::ModelDecision),
+            "glob" => Some(WindsurfTrigger::Glob),
+            _ => None,
+        }
+    }
+}
+
+/// Frontmatter of a Windsurf `.windsurf/rules/*.md` rule file
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct WindsurfRule {
+    pub trigger: WindsurfTrigger,
+    pub description: Option<String>,
+    pub globs: Vec<String>,
+}
+
+/// Why an agent applies a rule to a file
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+pub enum MatchReason {
+    /// Included in every request
+    Always,
+    /// One of the rule's globs matches the file
+    Glob,
+    /// A memory or instructions file in the file's folder or one above it
+    Directory,
+    /// Imported by a memory file that applies
+    Import,
+    /// Offered to the model by description, which decides whether to read it
+    Description,
+}
+
+/// A rule an agent applies to a file, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleMatch {
+    pub skill: Skill,
+    pub reason: MatchReason,
+    /// The glob that matched, for `Glob` matches
+    pub pattern: Option<String>,
+    pub explanation: String,
+}
+
+/// Rules each agent applies when working on a file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EffectiveRules {
+    pub path: String,
+    /// The nearest folder above the file with a `.git`, or the file's folder
+    pub project_dir: String,
+    /// Grouped by agent, in the order of the agent configs
+    pub rules: Vec<RuleMatch>,
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use super::{ClaudeFrontmatter, ContinueEntry, CursorRule, SkillKind, WindsurfRule};
 
 /// Supported AI agent types
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -9,9 +10,31 @@ pub enum AgentType {
     ContinueDev,
     Aider,
     Windsurf,
+    Copilot,
+    Codex,
+    Gemini,
+    Cline,
     Custom(String),
 }
 
+impl AgentType {
+    /// Parse an agent name as sent by the frontend (case-insensitive)
+    pub fn parse(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "claude" => AgentType::Claude,
+            "cursor" => AgentType::Cursor,
+            "continuedev" | "continue" => AgentType::ContinueDev,
+            "aider" => AgentType::Aider,
+            "windsurf" | "codeium" => AgentType::Windsurf,
+            "copilot" | "github-copilot" => AgentType::Copilot,
+            "codex" => AgentType::Codex,
+            "gemini" | "gemini-cli" => AgentType::Gemini,
+            "cline" => AgentType::Cline,
+            _ => AgentType::Custom(name.to_string()),
+        }
+    }
+}
+
 impl std::fmt::Display for AgentType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -20,6 +43,10 @@ impl std::fmt::Display for AgentType {
             AgentType::ContinueDev => write!(f, "ContinueDev"),
             AgentType::Aider => write!(f, "Aider"),
             AgentType::Windsurf => write!(f, "Windsurf"),
+            AgentType::Copilot => write!(f, "Copilot"),
+            AgentType::Codex => write!(f, "Codex"),
+            AgentType::Gemini => write!(f, "Gemini"),
+            AgentType::Cline => write!(f, "Cline"),
             AgentType::Custom(name) => write!(f, "{}", name),
         }
     }
@@ -34,6 +61,8 @@ pub enum SkillFormat {
     Yaml,
     Python,
     PlainText,
+    /// Cursor rule: markdown with `description`/`globs`/`alwaysApply` frontmatter
+    Mdc,
 }
 
 impl SkillFormat {
@@ -43,6 +72,7 @@ impl SkillFormat {
             "json" => SkillFormat::Json,
             "yaml" | "yml" => SkillFormat::Yaml,
             "py" => SkillFormat::Python,
+            "mdc" => SkillFormat::Mdc,
             _ => SkillFormat::PlainText,
         }
     }
@@ -54,6 +84,7 @@ impl SkillFormat {
             SkillFormat::Yaml => "yaml",
             SkillFormat::Python => "py",
             SkillFormat::PlainText => "txt",
+            SkillFormat::Mdc => "mdc",
         }
     }
 }
@@ -68,6 +99,14 @@ pub struct SkillFile {
     pub size: u64,
 }
 
+/// A skill folder found inside a repository, directory or archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiscoveredSkill {
+    /// Folder path relative to the source root
+    pub path: String,
+    pub skill: Skill,
+}
+
 /// Core skill model - represents a skill folder
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Skill {
@@ -86,6 +125,20 @@ pub struct Skill {
     pub file_count: usize,
     pub created_at: i64,
     pub updated_at: i64,
+    #[serde(default)]
+    pub kind: SkillKind,
+    /// Attachment settings of a Cursor `.mdc` rule
+    #[serde(default)]
+    pub rule: Option<CursorRule>,
+    /// Activation settings of a Windsurf workspace rule
+    #[serde(default)]
+    pub windsurf: Option<WindsurfRule>,
+    /// Frontmatter of a Claude skill, subagent, command or output style
+    #[serde(default)]
+    pub claude: Option<ClaudeFrontmatter>,
+    /// Location of a prompt, rule or command inside a Continue config file
+    #[serde(default)]
+    pub continue_entry: Option<ContinueEntry>,
 }
 
 impl Skill {
@@ -117,6 +170,11 @@ impl Skill {
             file_count,
             created_at: now,
             updated_at: now,
+            kind: SkillKind::default(),
+            rule: None,
+            windsurf: None,
+            claude: None,
+            continue_entry: None,
         }
     }
 
@@ -151,6 +209,11 @@ impl Skill {
             file_count: 1,
             created_at: now,
             updated_at: now,
+            kind: SkillKind::default(),
+            rule: None,
+            windsurf: None,
+            claude: None,
+            continue_entry: None,
         }
     }
 }
@@ -0,0 +1,296 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use glob::glob;
+
+use crate::models::{AiderConventions, AiderReadFile, AppError};
+use crate::services::template_service::yaml_string;
+
+/// Aider's config file, read from the home directory and the project root
+pub const CONFIG_FILE: &str = ".aider.conf.yml";
+
+/// Conventions file Aider's docs suggest adding with `read:`
+pub const CONVENTIONS_FILE: &str = "CONVENTIONS.md";
+
+/// Aider config files and the conventions they load
+pub struct AiderService;
+
+impl AiderService {
+    pub fn is_config_file(path: &Path) -> bool {
+        path.file_name().is_some_and(|n| n == CONFIG_FILE)
+    }
+
+    /// Existing files named in a config's `read:` list
+    pub fn read_files(config_path: &Path) -> Vec<PathBuf> {
+        let base = config_path.parent().unwrap_or(Path::new(""));
+        Self::read_entries(config_path)
+            .unwrap_or_default()
+            .iter()
+            .map(|entry| Self::resolve(entry, base))
+            .filter(|path| path.is_file())
+            .collect()
+    }
+
+    /// Where installed conventions live, one folder per skill
+    pub fn conventions_dir(home: &Path) -> PathBuf {
+        home.join(".aider").join("conventions")
+    }
+
+    /// Entries of a config's `read:` list, which may also be a single path
+    pub fn read_entries(config_path: &Path) -> Result<Vec<String>, AppError> {
+        let text = fs::read_to_string(config_path)?;
+        Ok(parse_read(&text)?.unwrap_or_default())
+    }
+
+    /// A `read:` entry as a path. Relative entries are relative to `base`,
+    /// the directory Aider runs in.
+    pub fn resolve(entry: &str, base: &Path) -> PathBuf {
+        let path = match (entry.strip_prefix("~/"), dirs::home_dir()) {
+            (Some(rest), Some(home)) => home.join(rest),
+            _ => PathBuf::from(entry),
+        };
+
+        if path.is_absolute() {
+            path
+        } else {
+            base.join(path)
+        }
+    }
+
+    /// Conventions Aider loads in a project, or from the home directory
+    /// without one. Aider reads the home config, then the project's, and the
+    /// last `read:` list found replaces the earlier ones.
+    pub fn conventions(home: &Path, project_dir: Option<&Path>) -> AiderConventions {
+        let base = project_dir.unwrap_or(home);
+
+        let mut configs = vec![home.join(CONFIG_FILE)];
+        if let Some(project_dir) = project_dir.filter(|p| *p != home) {
+            configs.push(project_dir.join(CONFIG_FILE));
+        }
+        configs.retain(|c| c.is_file());
+
+        let lists: Vec<(PathBuf, Option<Vec<String>>)> = configs.iter()
+            .map(|config| {
+                let list = fs::read_to_string(config).ok().and_then(|text| parse_read(&text).ok().flatten());
+                (config.clone(), list)
+            })
+            .collect();
+        let effective = lists.iter().rev().find(|(_, list)| list.is_some()).map(|(config, _)| config.clone());
+
+        let mut files = Vec::new();
+        for (config, list) in &lists {
+            for entry in list.iter().flatten() {
+                let path = Self::resolve(entry, base);
+                files.push(AiderReadFile {
+                    entry: entry.clone(),
+                    path: path.to_string_lossy().to_string(),
+                    config_path: config.to_string_lossy().to_string(),
+                    exists: path.is_file(),
+                    loaded: path.is_file() && effective.as_ref() == Some(config),
+                });
+            }
+        }
+
+        // Conventions files that exist but aren't loaded
+        let mut candidates = vec![base.join(CONVENTIONS_FILE)];
+        let pattern = Self::conventions_dir(home).join("*").join("*");
+        if let Ok(entries) = glob(&pattern.to_string_lossy()) {
+            candidates.extend(entries.flatten());
+        }
+        let unreferenced = candidates.into_iter()
+            .filter(|path| path.is_file())
+            .filter(|path| !files.iter().any(|f| f.loaded && Path::new(&f.path) == path))
+            .map(|path| path.to_string_lossy().to_string())
+            .collect();
+
+        AiderConventions {
+            configs: configs.iter().map(|c| c.to_string_lossy().to_string()).collect(),
+            effective_config: effective.map(|c| c.to_string_lossy().to_string()),
+            files,
+            unreferenced,
+        }
+    }
+
+    /// Whether a config's `read:` list names the file
+    pub fn is_listed(config_path: &Path, file: &Path) -> bool {
+        let base = config_path.parent().unwrap_or(Path::new(""));
+        Self::read_entries(config_path)
+            .is_ok_and(|entries| entries.iter().any(|entry| Self::resolve(entry, base) == file))
+    }
+
+    /// Add a file to a config's `read:` list, creating the config when
+    /// needed. Returns false when the file is already listed.
+    pub fn add_read(config_path: &Path, file: &Path) -> Result<bool, AppError> {
+        if Self::is_listed(config_path, file) {
+            return Ok(false);
+        }
+
+        let text = fs::read_to_string(config_path).unwrap_or_default();
+        let mut entries = parse_read(&text)?.unwrap_or_default();
+
+        entries.push(file.to_string_lossy().to_string());
+        write_read(config_path, &text, &entries)?;
+        Ok(true)
+    }
+
+    /// Remove a file, or every file inside a folder, from a config's `read:`
+    /// list. Returns false when nothing was listed.
+    pub fn remove_read(config_path: &Path, path: &Path) -> Result<bool, AppError> {
+        let Ok(text) = fs::read_to_string(config_path) else {
+            return Ok(false);
+        };
+        let entries = parse_read(&text)?.unwrap_or_default();
+
+        let base = config_path.parent().unwrap_or(Path::new(""));
+        let kept: Vec<String> = entries.iter()
+            .filter(|entry| !Self::resolve(entry, base).starts_with(path))
+            .cloned()
+            .collect();
+        if kept.len() == entries.len() {
+            return Ok(false);
+        }
+
+        write_read(config_path, &text, &kept)?;
+        Ok(true)
+    }
+}
+
+/// The `read:` value of a config, `None` when the key is missing
+fn parse_read(text: &str) -> Result<Option<Vec<String>>, AppError> {
+    let config: serde_yaml::Value = serde_yaml::from_str(text)?;
+
+    Ok(config.get("read").map(|value| match value {
+        serde_yaml::Value::String(entry) => vec![entry.clone()],
+        serde_yaml::Value::Sequence(items) => items.iter()
+            .filter_map(|item| item.as_str().map(str::to_string))
+            .collect(),
+        _ => Vec::new(),
+    }))
+}
+
+/// Rewrite only the `read:` block of a config, leaving other lines as they
+/// were, and check the result before writing it
+fn write_read(config_path: &Path, text: &str, entries: &[String]) -> Result<(), AppError> {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let is_blank = |line: &str| {
+        let trimmed = line.trim();
+        trimmed.is_empty() || trimmed.starts_with('#')
+    };
+
+    let mut block = String::new();
+    if !entries.is_empty() {
+        block.push_str("read:\n");
+        for entry in entries {
+            block.push_str(&format!("  - {}\n", yaml_string(entry)));
+        }
+    }
+
+    let header = lines.iter().position(|line| line.starts_with("read:"));
+    let updated = match header {
+        Some(start) => {
+            // The value runs until the next top-level key
+            let mut end = (start + 1..lines.len())
+                .find(|&i| !is_blank(lines[i]) && !lines[i].starts_with([' ', '\t', '-']))
+                .unwrap_or(lines.len());
+            while end > start + 1 && lines[end - 1].trim().is_empty() {
+                end -= 1;
+            }
+            format!("{}{}{}", lines[..start].concat(), block, lines[end..].concat())
+        }
+        None if text.is_empty() || text.ends_with('\n') => format!("{}{}", text, block),
+        None => format!("{}\n{}", text, block),
+    };
+
+    let written = parse_read(&updated)?.unwrap_or_default();
+    if written != entries {
+        return Err(AppError::ParseError(format!(
+            "Cannot update the read: list of {}; edit it directly",
+            config_path.display()
+        )));
+    }
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(config_path, updated)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_config(text: Option<&str>) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aider-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(CONFIG_FILE);
+        if let Some(text) = text {
+            fs::write(&path, text).unwrap();
+        }
+        path
+    }
+
+    fn rewrite(text: &str, entries: &[&str]) -> String {
+        let path = temp_config(Some(text));
+        let entries: Vec<String> = entries.iter().map(|e| e.to_string()).collect();
+        write_read(&path, text, &entries).unwrap();
+        let written = fs::read_to_string(&path).unwrap();
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        written
+    }
+
+    #[test]
+    fn adds_a_read_block_to_empty_or_missing_configs() {
+        assert_eq!(rewrite("", &["CONVENTIONS.md"]), "read:\n  - CONVENTIONS.md\n");
+        assert_eq!(rewrite("model: gpt-4o", &["a.md"]), "model: gpt-4o\nread:\n  - a.md\n");
+
+        let path = temp_config(None);
+        let file = path.parent().unwrap().join("CONVENTIONS.md");
+        assert!(AiderService::add_read(&path, &file).unwrap());
+        assert!(!AiderService::add_read(&path, &file).unwrap());
+        assert_eq!(AiderService::read_entries(&path).unwrap(), vec![file.to_string_lossy().to_string()]);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn replaces_only_the_read_block() {
+        let text = "# settings\nmodel: gpt-4o\nread:\n  # team rules\n  - CONVENTIONS.md\n\n  - docs/style.md\nauto-commits: false\n";
+        assert_eq!(
+            rewrite(text, &["CONVENTIONS.md", "docs/style.md", "extra.md"]),
+            "# settings\nmodel: gpt-4o\nread:\n  - CONVENTIONS.md\n  - docs/style.md\n  - extra.md\nauto-commits: false\n"
+        );
+    }
+
+    #[test]
+    fn rewrites_single_and_flow_style_values() {
+        assert_eq!(
+            rewrite("read: CONVENTIONS.md\nmodel: x\n", &["CONVENTIONS.md", "b.md"]),
+            "read:\n  - CONVENTIONS.md\n  - b.md\nmodel: x\n"
+        );
+        assert_eq!(rewrite("read: [a.md, b.md]\n", &["a.md"]), "read:\n  - a.md\n");
+    }
+
+    #[test]
+    fn removing_the_last_entry_drops_the_key() {
+        assert_eq!(rewrite("model: x\nread:\n  - a.md\n\nauto-commits: false\n", &[]), "model: x\n\nauto-commits: false\n");
+        assert_eq!(rewrite("read:\n  - a.md\n", &[]), "");
+    }
+
+    #[test]
+    fn remove_read_drops_files_inside_a_folder() {
+        let path = temp_config(Some("read:\n  - skills/x/CONVENTIONS.md\n  - skills/y/CONVENTIONS.md\n"));
+        let base = path.parent().unwrap().to_path_buf();
+        assert!(AiderService::remove_read(&path, &base.join("skills/x")).unwrap());
+        assert!(!AiderService::remove_read(&path, &base.join("skills/x")).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "read:\n  - skills/y/CONVENTIONS.md\n");
+        let _ = fs::remove_dir_all(base);
+    }
+
+    #[test]
+    fn unparsable_configs_are_not_written() {
+        let text = "read: [a.md\n";
+        let path = temp_config(Some(text));
+        assert!(AiderService::add_read(&path, Path::new("/b.md")).is_err());
+        assert_eq!(fs::read_to_string(&path).unwrap(), text);
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
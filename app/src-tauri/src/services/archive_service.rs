@@ -0,0 +1,131 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use crate::models::AppError;
+
+/// File type bits of a Unix mode, and the value marking a symlink
+const S_IFMT: u32 = 0o170000;
+const S_IFLNK: u32 = 0o120000;
+
+/// Supported skill bundle archive formats
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArchiveKind {
+    Zip,
+    TarGz,
+}
+
+pub struct ArchiveService;
+
+impl ArchiveService {
+    /// Detect the archive kind from a file name
+    pub fn detect(path: &Path) -> Option<ArchiveKind> {
+        let name = path.file_name()?.to_str()?.to_lowercase();
+
+        if name.ends_with(".zip") {
+            Some(ArchiveKind::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(ArchiveKind::TarGz)
+        } else {
+            None
+        }
+    }
+
+    /// File name without the archive extension (`my-skill.tar.gz` -> `my-skill`)
+    pub fn stem(path: &Path) -> String {
+        let name = path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("skill");
+
+        [".tar.gz", ".tgz", ".zip"]
+            .iter()
+            .find_map(|ext| name.strip_suffix(ext))
+            .unwrap_or(name)
+            .to_string()
+    }
+
+    /// Extract an archive into a fresh temporary directory.
+    /// The caller is responsible for removing it when done.
+    pub fn extract_to_temp(archive: &Path) -> Result<PathBuf, AppError> {
+        let dest = std::env::temp_dir()
+            .join("ai-skills-aggregator")
+            .join(uuid::Uuid::new_v4().to_string());
+
+        fs::create_dir_all(&dest)?;
+
+        if let Err(e) = Self::extract(archive, &dest) {
+            let _ = fs::remove_dir_all(&dest);
+            return Err(e);
+        }
+
+        Ok(dest)
+    }
+
+    /// Extract an archive into `dest`, rejecting entries that escape it and
+    /// links, whose targets could point anywhere
+    pub fn extract(archive: &Path, dest: &Path) -> Result<(), AppError> {
+        let kind = Self::detect(archive).ok_or_else(|| AppError::InvalidPath(
+            format!("Unsupported archive: {}", archive.display())
+        ))?;
+
+        let file = File::open(archive)
+            .map_err(|_| AppError::FileNotFound(archive.to_string_lossy().to_string()))?;
+
+        match kind {
+            ArchiveKind::Zip => {
+                let mut zip = zip::ZipArchive::new(file)
+                    .map_err(|e| AppError::ParseError(format!("Invalid zip archive: {}", e)))?;
+
+                for i in 0..zip.len() {
+                    let mut entry = zip.by_index(i)
+                        .map_err(|e| AppError::ParseError(e.to_string()))?;
+
+                    let relative = entry.enclosed_name().ok_or_else(|| AppError::InvalidPath(
+                        format!("Unsafe path in archive: {}", entry.name())
+                    ))?;
+                    let out_path = dest.join(relative);
+
+                    if entry.unix_mode().is_some_and(|mode| mode & S_IFMT == S_IFLNK) {
+                        return Err(AppError::InvalidPath(
+                            format!("Link in archive: {}", entry.name())
+                        ));
+                    }
+
+                    if entry.is_dir() {
+                        fs::create_dir_all(&out_path)?;
+                    } else {
+                        if let Some(parent) = out_path.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+                        let mut out = File::create(&out_path)?;
+                        std::io::copy(&mut entry, &mut out)?;
+                    }
+                }
+            }
+            ArchiveKind::TarGz => {
+                let decoder = flate2::read::GzDecoder::new(file);
+                let mut tar = tar::Archive::new(decoder);
+
+                for entry in tar.entries()? {
+                    let mut entry = entry?;
+                    let entry_type = entry.header().entry_type();
+                    if entry_type.is_symlink() || entry_type.is_hard_link() {
+                        return Err(AppError::InvalidPath(format!(
+                            "Link in archive: {}",
+                            entry.path()?.display()
+                        )));
+                    }
+
+                    // unpack_in refuses paths outside of dest
+                    if !entry.unpack_in(dest)? {
+                        return Err(AppError::InvalidPath(format!(
+                            "Unsafe path in archive: {}",
+                            entry.path()?.display()
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
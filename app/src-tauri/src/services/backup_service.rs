@@ -1,9 +1,12 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH, Duration};
 use tokio::fs;
 use crate::models::AppError;
 use serde::Serialize;
 
+/// Folder of the backups taken when installs and updates replace a skill
+const SKILL_BACKUPS: &str = "skills";
+
 pub struct BackupService {
     backup_dir: PathBuf,
     retention_days: u64,
@@ -23,6 +26,10 @@ impl BackupService {
     }
 
     pub async fn backup_file(&self, file_path: &str) -> Result<PathBuf, AppError> {
+        self.backup_file_to(&self.backup_dir, file_path).await
+    }
+
+    async fn backup_file_to(&self, backup_dir: &Path, file_path: &str) -> Result<PathBuf, AppError> {
         let source = PathBuf::from(file_path);
         if !source.exists() {
             return Err(AppError::FileNotFound(file_path.to_string()));
@@ -40,11 +47,11 @@ impl BackupService {
         let backup_name = format!("{}_{}", timestamp, file_name);
 
         // Ensure backup dir exists
-        fs::create_dir_all(&self.backup_dir)
+        fs::create_dir_all(backup_dir)
             .await
             .map_err(|e| AppError::IoError(e.to_string()))?;
 
-        let backup_path = self.backup_dir.join(backup_name);
+        let backup_path = backup_dir.join(backup_name);
 
         // Copy file to backup
         fs::copy(&source, &backup_path)
@@ -55,6 +62,10 @@ impl BackupService {
     }
 
     pub async fn backup_folder(&self, folder_path: &str) -> Result<PathBuf, AppError> {
+        self.backup_folder_to(&self.backup_dir, folder_path).await
+    }
+
+    async fn backup_folder_to(&self, backup_dir: &Path, folder_path: &str) -> Result<PathBuf, AppError> {
         let source = PathBuf::from(folder_path);
         if !source.exists() {
             return Err(AppError::FileNotFound(folder_path.to_string()));
@@ -71,11 +82,11 @@ impl BackupService {
         let backup_name = format!("{}_{}", timestamp, folder_name);
 
         // Ensure backup dir exists
-        fs::create_dir_all(&self.backup_dir)
+        fs::create_dir_all(backup_dir)
             .await
             .map_err(|e| AppError::IoError(e.to_string()))?;
 
-        let backup_path = self.backup_dir.join(backup_name);
+        let backup_path = backup_dir.join(backup_name);
 
         // Copy folder recursively
         self.copy_dir_recursive(&source, &backup_path).await?;
@@ -83,7 +94,51 @@ impl BackupService {
         Ok(backup_path)
     }
 
-    async fn copy_dir_recursive(&self, src: &PathBuf, dst: &PathBuf) -> Result<(), AppError> {
+    /// Back up an installed skill's file or folder before it is replaced.
+    /// Each skill's backups are kept apart, as installs of different skills
+    /// share file names.
+    pub async fn backup_skill(&self, skill_id: &str, agent: &str, path: &str) -> Result<PathBuf, AppError> {
+        let backup_dir = self.skill_backup_dir(skill_id, agent);
+        if Path::new(path).is_dir() {
+            self.backup_folder_to(&backup_dir, path).await
+        } else {
+            self.backup_file_to(&backup_dir, path).await
+        }
+    }
+
+    /// The most recent backup `backup_skill` took of a skill
+    pub async fn latest_skill_backup(&self, skill_id: &str, agent: &str) -> Result<Option<PathBuf>, AppError> {
+        let backup_dir = self.skill_backup_dir(skill_id, agent);
+        if !backup_dir.exists() {
+            return Ok(None);
+        }
+
+        let mut latest: Option<(u64, PathBuf)> = None;
+        let mut entries = fs::read_dir(&backup_dir)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        while let Some(entry) = entries.next_entry().await
+            .map_err(|e| AppError::IoError(e.to_string()))? {
+
+            // Backups are named `<timestamp>_<name>`
+            let name = entry.file_name().to_string_lossy().to_string();
+            let Some(timestamp) = name.split_once('_').and_then(|(t, _)| t.parse::<u64>().ok()) else {
+                continue;
+            };
+            if latest.as_ref().map_or(true, |(newest, _)| timestamp >= *newest) {
+                latest = Some((timestamp, entry.path()));
+            }
+        }
+
+        Ok(latest.map(|(_, path)| path))
+    }
+
+    fn skill_backup_dir(&self, skill_id: &str, agent: &str) -> PathBuf {
+        self.backup_dir.join(SKILL_BACKUPS).join(agent.to_lowercase()).join(skill_id)
+    }
+
+    async fn copy_dir_recursive(&self, src: &Path, dst: &Path) -> Result<(), AppError> {
         fs::create_dir_all(dst)
             .await
             .map_err(|e| AppError::IoError(e.to_string()))?;
@@ -133,6 +188,24 @@ impl BackupService {
         Ok(())
     }
 
+    /// Replace a folder with a backup taken by `backup_folder`
+    pub async fn restore_folder(&self, backup_path: &str, dest_path: &str) -> Result<(), AppError> {
+        let backup = PathBuf::from(backup_path);
+        let dest = PathBuf::from(dest_path);
+
+        if !backup.is_dir() {
+            return Err(AppError::FileNotFound(backup_path.to_string()));
+        }
+
+        if dest.exists() {
+            fs::remove_dir_all(&dest)
+                .await
+                .map_err(|e| AppError::IoError(e.to_string()))?;
+        }
+
+        self.copy_dir_recursive(&backup, &dest).await
+    }
+
     pub async fn cleanup_old_backups(&self) -> Result<usize, AppError> {
         if !self.backup_dir.exists() {
             return Ok(0);
@@ -142,15 +215,35 @@ impl BackupService {
             .checked_sub(Duration::from_secs(self.retention_days * 24 * 60 * 60))
             .unwrap_or(SystemTime::UNIX_EPOCH);
 
+        // Skill backups sit two folders further down, one folder per skill
+        let mut dirs = vec![self.backup_dir.clone()];
+        let skills_dir = self.backup_dir.join(SKILL_BACKUPS);
+        for agent_dir in list_dirs(&skills_dir).await {
+            dirs.extend(list_dirs(&agent_dir).await);
+        }
+
         let mut deleted = 0;
+        for dir in dirs {
+            deleted += self.cleanup_dir(&dir, cutoff).await?;
+        }
 
-        let mut entries = fs::read_dir(&self.backup_dir)
+        Ok(deleted)
+    }
+
+    async fn cleanup_dir(&self, dir: &Path, cutoff: SystemTime) -> Result<usize, AppError> {
+        let mut deleted = 0;
+
+        let mut entries = fs::read_dir(dir)
             .await
             .map_err(|e| AppError::IoError(e.to_string()))?;
 
         while let Some(entry) = entries.next_entry().await
             .map_err(|e| AppError::IoError(e.to_string()))? {
 
+            if dir == self.backup_dir && entry.file_name() == SKILL_BACKUPS {
+                continue;
+            }
+
             let metadata = entry.metadata().await
                 .map_err(|e| AppError::IoError(e.to_string()))?;
 
@@ -208,6 +301,22 @@ impl BackupService {
     }
 }
 
+/// Folders directly inside `dir`
+async fn list_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+    let Ok(mut entries) = fs::read_dir(dir).await else {
+        return dirs;
+    };
+
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+            dirs.push(entry.path());
+        }
+    }
+
+    dirs
+}
+
 impl Default for BackupService {
     fn default() -> Self {
         Self::new()
@@ -0,0 +1,323 @@
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use chrono::Utc;
+use zip::write::SimpleFileOptions;
+
+use crate::models::{
+    AgentType, BundleConflict, BundleFile, BundleImportOptions, BundleManifest, BundlePreview,
+    BundleSkill, ConflictStrategy, Skill, SkillFormat, AppError, BUNDLE_FORMAT_VERSION,
+};
+use crate::services::archive_service::ArchiveService;
+use crate::services::backup_service::BackupService;
+use crate::services::convert_service::ConvertService;
+use crate::services::crud_service::CrudService;
+use crate::services::download_service::DownloadService;
+use crate::services::SkillService;
+
+const MANIFEST_FILE: &str = "manifest.json";
+
+pub struct BundleService {
+    crud: CrudService,
+    backup: BackupService,
+}
+
+impl BundleService {
+    pub fn new() -> Self {
+        Self {
+            crud: CrudService::new(),
+            backup: BackupService::new(),
+        }
+    }
+
+    /// Export skills (including references/ and scripts/) into a zip bundle
+    pub fn export_bundle(&self, skills: &[Skill], dest_path: &str) -> Result<BundleManifest, AppError> {
+        let file = File::create(dest_path)?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+
+        let mut bundled = Vec::new();
+
+        for skill in skills {
+            let dir = self.unique_dir_name(&skill.name, &bundled);
+            let root = PathBuf::from(&skill.folder_path);
+            let mut files = Vec::new();
+
+            for file in SkillService::get_skill_files(&skill.folder_path)? {
+                let file_path = PathBuf::from(&file.file_path);
+                let relative = if skill.is_folder {
+                    to_bundle_path(file_path.strip_prefix(&root).unwrap_or(&file_path))
+                } else {
+                    file.name.clone()
+                };
+
+                let bytes = fs::read(&file_path)?;
+                zip.start_file(format!("skills/{}/{}", dir, relative), options)
+                    .map_err(|e| AppError::IoError(e.to_string()))?;
+                zip.write_all(&bytes)?;
+
+                files.push(BundleFile {
+                    is_entry: !skill.is_folder || skill.entry_file.as_deref() == Some(file.file_path.as_str()),
+                    path: relative,
+                    sha256: sha256_hex(&bytes),
+                    size: bytes.len() as u64,
+                });
+            }
+
+            bundled.push(BundleSkill {
+                name: skill.name.clone(),
+                dir,
+                agent: skill.agent.clone(),
+                description: skill.description.clone(),
+                version: skill.version.clone(),
+                tags: skill.tags.clone(),
+                author: skill.author.clone(),
+                is_folder: skill.is_folder,
+                original_path: skill.folder_path.clone(),
+                files,
+            });
+        }
+
+        let manifest = BundleManifest {
+            format_version: BUNDLE_FORMAT_VERSION,
+            created_at: Utc::now().timestamp(),
+            skills: bundled,
+        };
+
+        zip.start_file(MANIFEST_FILE, options)
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+        zip.write_all(serde_json::to_string_pretty(&manifest)?.as_bytes())?;
+        zip.finish().map_err(|e| AppError::IoError(e.to_string()))?;
+
+        Ok(manifest)
+    }
+
+    /// Inspect a bundle: manifest, integrity and conflicts with existing skills
+    pub fn preview_bundle(&self, bundle_path: &str, agent: Option<&str>) -> Result<BundlePreview, AppError> {
+        let root = ArchiveService::extract_to_temp(Path::new(bundle_path))?;
+        let result = self.preview_extracted(&root, agent);
+        let _ = fs::remove_dir_all(&root);
+        result
+    }
+
+    /// Import skills from a bundle into the chosen agent
+    pub async fn import_bundle(
+        &self,
+        bundle_path: &str,
+        options: &BundleImportOptions,
+    ) -> Result<Vec<Skill>, AppError> {
+        let root = ArchiveService::extract_to_temp(Path::new(bundle_path))?;
+        let result = self.import_extracted(&root, options).await;
+        let _ = fs::remove_dir_all(&root);
+        result
+    }
+
+    // Internal helpers
+
+    fn preview_extracted(&self, root: &Path, agent: Option<&str>) -> Result<BundlePreview, AppError> {
+        let manifest = Self::read_manifest(root)?;
+        let mut conflicts = Vec::new();
+        let mut corrupted_files = Vec::new();
+
+        for skill in &manifest.skills {
+            corrupted_files.extend(Self::verify_skill(root, skill));
+
+            let target = agent.map(AgentType::parse).unwrap_or_else(|| skill.agent.clone());
+            let dest = self.target_dir(skill, &target)?;
+            if dest.exists() {
+                conflicts.push(BundleConflict {
+                    name: skill.name.clone(),
+                    existing_path: dest.to_string_lossy().to_string(),
+                });
+            }
+        }
+
+        Ok(BundlePreview { manifest, conflicts, corrupted_files })
+    }
+
+    async fn import_extracted(&self, root: &Path, options: &BundleImportOptions) -> Result<Vec<Skill>, AppError> {
+        let manifest = Self::read_manifest(root)?;
+        let mut imported = Vec::new();
+
+        let selected = manifest.skills.iter().filter(|s| {
+            options.skills.as_ref().map_or(true, |names| names.contains(&s.name))
+        });
+
+        for bundled in selected {
+            let corrupted = Self::verify_skill(root, bundled);
+            if !corrupted.is_empty() {
+                return Err(AppError::ChecksumMismatch(corrupted.join(", ")));
+            }
+
+            let target = options.agent.as_deref()
+                .map(AgentType::parse)
+                .unwrap_or_else(|| bundled.agent.clone());
+
+            let mut dest = self.target_dir(bundled, &target)?;
+            if dest.exists() {
+                match options.on_conflict {
+                    ConflictStrategy::Skip => continue,
+                    ConflictStrategy::Overwrite => {
+                        self.backup.backup_folder(&dest.to_string_lossy()).await?;
+                        fs::remove_dir_all(&dest)?;
+                    }
+                    ConflictStrategy::Rename => dest = unique_path(&dest),
+                }
+            }
+
+            let convert = options.convert && target != bundled.agent;
+            self.write_skill(root, bundled, &dest, &target, convert)?;
+
+            let mut skill = SkillService::parse_skill_folder(&dest, &target)?;
+            skill.description = bundled.description.clone().or(skill.description);
+            skill.version = bundled.version.clone();
+            skill.tags = bundled.tags.clone();
+            skill.author = bundled.author.clone();
+            imported.push(skill);
+        }
+
+        Ok(imported)
+    }
+
+    fn write_skill(
+        &self,
+        root: &Path,
+        bundled: &BundleSkill,
+        dest: &Path,
+        target: &AgentType,
+        convert: bool,
+    ) -> Result<(), AppError> {
+        let source_dir = root.join("skills").join(&bundled.dir);
+        fs::create_dir_all(dest)?;
+
+        for file in &bundled.files {
+            let source = source_dir.join(&file.path);
+            let is_entry = file.is_entry;
+
+            // Single-file skills become folders with a standard entry file
+            let mut dest_name = if is_entry && !bundled.is_folder {
+                let ext = Path::new(&file.path).extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("md");
+                ConvertService::entry_file_name(target, &SkillFormat::from_extension(ext))
+            } else {
+                file.path.clone()
+            };
+
+            let mut content = fs::read(&source)?;
+
+            if is_entry && convert {
+                let ext = Path::new(&file.path).extension()
+                    .and_then(|e| e.to_str())
+                    .unwrap_or("");
+                let text = String::from_utf8_lossy(&content).to_string();
+                let (format, converted) = ConvertService::convert(
+                    &bundled.name,
+                    &text,
+                    &SkillFormat::from_extension(ext),
+                    target,
+                )?;
+                dest_name = ConvertService::entry_file_name(target, &format);
+                content = converted.into_bytes();
+            }
+
+            let out_path = dest.join(&dest_name);
+            if let Some(parent) = out_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&out_path, content)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_manifest(root: &Path) -> Result<BundleManifest, AppError> {
+        let content = fs::read_to_string(root.join(MANIFEST_FILE))
+            .map_err(|_| AppError::ParseError("Bundle has no manifest.json".into()))?;
+
+        let manifest: BundleManifest = serde_json::from_str(&content)?;
+        if manifest.format_version > BUNDLE_FORMAT_VERSION {
+            return Err(AppError::ParseError(format!(
+                "Unsupported bundle format version {}",
+                manifest.format_version
+            )));
+        }
+
+        for skill in &manifest.skills {
+            let paths = std::iter::once(skill.dir.as_str())
+                .chain(skill.files.iter().map(|f| f.path.as_str()));
+            for path in paths {
+                if path.split('/').any(|part| part == ".." || part.is_empty()) {
+                    return Err(AppError::InvalidPath(format!("Unsafe path in manifest: {}", path)));
+                }
+            }
+        }
+
+        Ok(manifest)
+    }
+
+    /// Returns the bundle paths of files whose hash does not match the manifest
+    fn verify_skill(root: &Path, skill: &BundleSkill) -> Vec<String> {
+        let source_dir = root.join("skills").join(&skill.dir);
+
+        skill.files.iter()
+            .filter(|file| {
+                fs::read(source_dir.join(&file.path))
+                    .map(|bytes| sha256_hex(&bytes) != file.sha256)
+                    .unwrap_or(true)
+            })
+            .map(|file| format!("{}/{}", skill.dir, file.path))
+            .collect()
+    }
+
+    fn target_dir(&self, skill: &BundleSkill, agent: &AgentType) -> Result<PathBuf, AppError> {
+        let skills_dir = self.crud.get_agent_skills_dir(agent)?;
+        Ok(skills_dir.join(self.crud.sanitize_filename(&skill.name)))
+    }
+
+    fn unique_dir_name(&self, name: &str, existing: &[BundleSkill]) -> String {
+        let base = self.crud.sanitize_filename(name);
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while existing.iter().any(|s| s.dir == candidate) {
+            candidate = format!("{}-{}", base, n);
+            n += 1;
+        }
+        candidate
+    }
+}
+
+impl Default for BundleService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn sha256_hex(bytes: &[u8]) -> String {
+    DownloadService::checksum(bytes).trim_start_matches("sha256:").to_string()
+}
+
+fn to_bundle_path(path: &Path) -> String {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// `foo` -> `foo-2`, `foo-3`, ... until the path is free
+fn unique_path(path: &Path) -> PathBuf {
+    let name = path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("skill")
+        .to_string();
+
+    let mut n = 2;
+    loop {
+        let candidate = path.with_file_name(format!("{}-{}", name, n));
+        if !candidate.exists() {
+            return candidate;
+        }
+        n += 1;
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{
+    AgentType, AppError, ClaudeContext, ImportIssue, ImportProblem, MemoryLevel, MemorySource, SkillKind,
+};
+use crate::services::SkillService;
+
+/// Import hops Claude follows from a memory file
+const MAX_IMPORT_DEPTH: usize = 5;
+
+/// Folders skipped when looking for nested memory files
+const SKIPPED_DIRS: [&str; 4] = ["node_modules", "target", "dist", "build"];
+
+/// Folder levels searched below the directory for nested memory files
+const MAX_NESTED_DEPTH: usize = 8;
+
+/// Characters that end a sentence rather than an import path
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '"', '\''];
+
+/// Claude's layered CLAUDE.md memory and the files it imports
+pub struct ClaudeMemoryService;
+
+struct Resolver<'a> {
+    home: &'a Path,
+    included: HashSet<PathBuf>,
+    context: ClaudeContext,
+}
+
+impl ClaudeMemoryService {
+    /// What Claude loads as memory when started in `directory`: the user
+    /// memory in `claude_dir`, memory files in the directory and each one
+    /// above it (outermost first), memory files below it, and the files each
+    /// of these imports with `@path`
+    pub fn context(claude_dir: &Path, home: &Path, directory: &Path) -> Result<ClaudeContext, AppError> {
+        Self::build_context(claude_dir, home, directory, true)
+    }
+
+    /// Like `context`, but without the memory files below `directory`, which
+    /// Claude only loads for files inside their folders
+    pub fn directory_context(claude_dir: &Path, home: &Path, directory: &Path) -> Result<ClaudeContext, AppError> {
+        Self::build_context(claude_dir, home, directory, false)
+    }
+
+    fn build_context(
+        claude_dir: &Path,
+        home: &Path,
+        directory: &Path,
+        include_nested: bool,
+    ) -> Result<ClaudeContext, AppError> {
+        if !directory.is_dir() {
+            return Err(AppError::FileNotFound(directory.to_string_lossy().to_string()));
+        }
+
+        let mut resolver = Resolver {
+            home,
+            included: HashSet::new(),
+            context: ClaudeContext {
+                directory: directory.to_string_lossy().to_string(),
+                ..Default::default()
+            },
+        };
+
+        resolver.add(&claude_dir.join("CLAUDE.md"), MemoryLevel::User);
+
+        // Claude stops below the filesystem root
+        let mut ancestors: Vec<&Path> = directory.ancestors()
+            .filter(|dir| dir.parent().is_some())
+            .collect();
+        ancestors.reverse();
+        for dir in ancestors {
+            resolver.add(&dir.join("CLAUDE.md"), MemoryLevel::Project);
+            resolver.add(&dir.join(".claude").join("CLAUDE.md"), MemoryLevel::Project);
+            resolver.add(&dir.join("CLAUDE.local.md"), MemoryLevel::Local);
+        }
+
+        let mut nested = Vec::new();
+        if include_nested {
+            find_nested(directory, 0, &mut nested);
+        }
+        nested.sort();
+        for path in nested {
+            let level = if path.file_name().is_some_and(|n| n == "CLAUDE.local.md") {
+                MemoryLevel::Local
+            } else {
+                MemoryLevel::Nested
+            };
+            resolver.add(&path, level);
+        }
+
+        Ok(resolver.context)
+    }
+
+    /// `@` imports in a memory file, as written. Code spans and fenced code
+    /// blocks are skipped, as Claude does.
+    pub fn imports(content: &str) -> Vec<String> {
+        let mut imports = Vec::new();
+        let mut fence: Option<&str> = None;
+
+        for line in content.lines() {
+            let trimmed = line.trim_start();
+            let marker = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m));
+            match (fence, marker) {
+                (None, Some(marker)) => {
+                    fence = Some(marker);
+                    continue;
+                }
+                (Some(open), Some(marker)) if open == marker => {
+                    fence = None;
+                    continue;
+                }
+                (Some(_), _) => continue,
+                _ => {}
+            }
+
+            // Every other piece is inside a code span
+            for text in line.split('`').step_by(2) {
+                for word in text.split_whitespace() {
+                    let Some(import) = word.strip_prefix('@') else {
+                        continue;
+                    };
+                    let import = import.trim_end_matches(TRAILING_PUNCTUATION);
+                    if !import.is_empty() {
+                        imports.push(import.to_string());
+                    }
+                }
+            }
+        }
+
+        imports
+    }
+
+    /// An import as a path. Relative imports are relative to the importing
+    /// file, not the directory Claude runs in.
+    pub fn resolve(import: &str, file: &Path, home: &Path) -> PathBuf {
+        let path = match import.strip_prefix("~/") {
+            Some(rest) => home.join(rest),
+            None => PathBuf::from(import),
+        };
+
+        if path.is_absolute() {
+            path
+        } else {
+            file.parent().unwrap_or(Path::new("")).join(path)
+        }
+    }
+}
+
+impl Resolver<'_> {
+    /// Add a memory file and its imports, if it exists and isn't loaded yet
+    fn add(&mut self, path: &Path, level: MemoryLevel) {
+        if path.is_file() {
+            self.load(path, level, None, &mut Vec::new());
+        }
+    }
+
+    fn load(&mut self, path: &Path, level: MemoryLevel, imported_from: Option<&Path>, chain: &mut Vec<PathBuf>) {
+        let canonical = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if !self.included.insert(canonical.clone()) {
+            return;
+        }
+
+        let file_path = path.to_string_lossy().to_string();
+        let content = match SkillService::read_content(&file_path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::warn!("Failed to read {}: {}", file_path, e);
+                return;
+            }
+        };
+        let mut skill = match SkillService::parse_single_file(path, &AgentType::Claude) {
+            Ok(skill) => skill,
+            Err(e) => {
+                log::warn!("Failed to parse {}: {}", file_path, e);
+                return;
+            }
+        };
+        skill.kind = SkillKind::Memory;
+
+        self.context.sources.push(MemorySource {
+            skill,
+            level,
+            imported_from: imported_from.map(|p| p.to_string_lossy().to_string()),
+            depth: chain.len(),
+        });
+        self.context.text.push_str(&format!("<!-- {} -->\n{}\n\n", file_path, content.trim_end()));
+
+        chain.push(canonical);
+        for import in ClaudeMemoryService::imports(&content) {
+            let target = ClaudeMemoryService::resolve(&import, path, self.home);
+            let problem = if !target.is_file() {
+                Some(ImportProblem::Missing)
+            } else if target.canonicalize().is_ok_and(|t| chain.contains(&t)) {
+                Some(ImportProblem::Cycle)
+            } else if chain.len() > MAX_IMPORT_DEPTH {
+                Some(ImportProblem::TooDeep)
+            } else {
+                None
+            };
+
+            match problem {
+                Some(problem) => self.context.issues.push(ImportIssue {
+                    file: file_path.clone(),
+                    import,
+                    path: target.to_string_lossy().to_string(),
+                    problem,
+                }),
+                None => self.load(&target, MemoryLevel::Import, Some(path), chain),
+            }
+        }
+        chain.pop();
+    }
+}
+
+/// Memory files in folders below `dir`, skipping hidden and build folders.
+/// Symlinked folders are not followed, so links back up the tree cannot loop.
+fn find_nested(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) {
+    if depth >= MAX_NESTED_DEPTH {
+        return;
+    }
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+        if !is_dir || name.starts_with('.') || SKIPPED_DIRS.contains(&name.as_str()) {
+            continue;
+        }
+
+        for file in ["CLAUDE.md", "CLAUDE.local.md"] {
+            if path.join(file).is_file() {
+                found.push(path.join(file));
+            }
+        }
+        find_nested(&path, depth + 1, found);
+    }
+}
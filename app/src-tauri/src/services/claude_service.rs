@@ -0,0 +1,268 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{AgentType, AppError, ClaudeFrontmatter, Skill, SkillKind};
+use crate::services::rule_service::{fields, split_frontmatter, unquote};
+use crate::services::{SkillService, TemplateService};
+
+/// Models a subagent or command can name besides full `claude-*` model ids
+const MODEL_ALIASES: [&str; 4] = ["sonnet", "opus", "haiku", "inherit"];
+
+/// Claude skills, subagents, slash commands, memory, rules and output styles
+pub struct ClaudeService;
+
+impl ClaudeService {
+    /// Kind of a file from its place in a `.claude` directory
+    pub fn kind_for_path(path: &Path) -> SkillKind {
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if file_name == "CLAUDE.md" || file_name == "CLAUDE.local.md" {
+            return SkillKind::Memory;
+        }
+
+        // The top-level folder inside .claude decides, e.g. commands/frontend/review.md
+        let folders = path.parent()
+            .map(|p| p.iter().rev().take_while(|c| *c != ".claude").collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        folders.iter()
+            .rev()
+            .find_map(|folder| Self::folder_kind(&folder.to_string_lossy()))
+            .unwrap_or(SkillKind::Skill)
+    }
+
+    /// Folder inside `.claude` holding items of a kind
+    pub fn kind_folder(kind: SkillKind) -> Option<&'static str> {
+        match kind {
+            SkillKind::Skill => Some("skills"),
+            SkillKind::Subagent => Some("agents"),
+            SkillKind::SlashCommand => Some("commands"),
+            SkillKind::Rule => Some("rules"),
+            SkillKind::OutputStyle => Some("output-styles"),
+            SkillKind::Memory => None,
+        }
+    }
+
+    /// Split a Claude markdown file into its frontmatter and body. Missing
+    /// frontmatter reads as empty.
+    pub fn parse(content: &str) -> (ClaudeFrontmatter, &str) {
+        match split_frontmatter(content) {
+            Some((lines, body)) => (parse_frontmatter(&lines), body),
+            None => (ClaudeFrontmatter::default(), content),
+        }
+    }
+
+    /// Where a new item goes inside a `.claude` directory
+    pub fn item_path(claude_dir: &Path, kind: SkillKind, name: &str) -> PathBuf {
+        let slug = name.trim().to_lowercase().replace(' ', "-");
+
+        match kind {
+            SkillKind::Memory => claude_dir.join("CLAUDE.md"),
+            SkillKind::Skill => claude_dir.join("skills").join(slug).join("SKILL.md"),
+            _ => claude_dir
+                .join(Self::kind_folder(kind).unwrap_or_default())
+                .join(format!("{}.md", slug)),
+        }
+    }
+
+    /// Create an item in its canonical location, from the kind's template
+    /// when no content is given
+    pub fn create_item(
+        claude_dir: &Path,
+        kind: SkillKind,
+        name: &str,
+        description: Option<&str>,
+        content: Option<&str>,
+    ) -> Result<Skill, AppError> {
+        let path = Self::item_path(claude_dir, kind, name);
+        if path.exists() {
+            return Err(AppError::AlreadyExists(path.to_string_lossy().to_string()));
+        }
+
+        let content = match content {
+            Some(content) => content.to_string(),
+            None => TemplateService::claude_template(kind, name, description),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+
+        match (kind, path.parent()) {
+            (SkillKind::Skill, Some(folder)) => SkillService::parse_skill_folder(folder, &AgentType::Claude),
+            _ => SkillService::parse_single_file(&path, &AgentType::Claude),
+        }
+    }
+
+    /// Problems that would stop Claude from loading an item, or make it
+    /// behave unexpectedly. Empty when the item is valid.
+    pub fn validate(kind: SkillKind, content: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let (frontmatter, body) = Self::parse(content);
+
+        if body.trim().is_empty() {
+            problems.push("Instructions are empty".to_string());
+        }
+
+        if matches!(kind, SkillKind::Skill | SkillKind::Subagent) {
+            match frontmatter.name.as_deref() {
+                None => problems.push("Frontmatter needs a name".to_string()),
+                Some(name) if !is_identifier(name) => problems.push(format!(
+                    "Name \"{}\" must be lowercase letters, numbers and hyphens (at most 64 characters)",
+                    name
+                )),
+                _ => {}
+            }
+
+            match frontmatter.description.as_deref() {
+                None => problems.push("Frontmatter needs a description saying when to use it".to_string()),
+                Some(description) if description.chars().count() > 1024 => {
+                    problems.push("Description must be at most 1024 characters".to_string());
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(model) = frontmatter.model.as_deref() {
+            if matches!(kind, SkillKind::Subagent | SkillKind::SlashCommand)
+                && !MODEL_ALIASES.contains(&model)
+                && !model.starts_with("claude-")
+            {
+                problems.push(format!(
+                    "Unknown model \"{}\" (use {} or a claude-* model id)",
+                    model,
+                    MODEL_ALIASES.join(", ")
+                ));
+            }
+        }
+
+        for tool in frontmatter.tools.iter().chain(&frontmatter.allowed_tools) {
+            if tool.matches('(').count() != tool.matches(')').count() {
+                problems.push(format!("Tool \"{}\" has unbalanced parentheses", tool));
+            }
+        }
+
+        if kind != SkillKind::Subagent && !frontmatter.tools.is_empty() {
+            problems.push("`tools` only applies to subagents".to_string());
+        }
+        if kind != SkillKind::SlashCommand && frontmatter.argument_hint.is_some() {
+            problems.push("`argument-hint` only applies to slash commands".to_string());
+        }
+
+        problems
+    }
+
+    // Internal helpers
+
+    fn folder_kind(folder: &str) -> Option<SkillKind> {
+        match folder {
+            "skills" => Some(SkillKind::Skill),
+            "agents" => Some(SkillKind::Subagent),
+            "commands" => Some(SkillKind::SlashCommand),
+            "rules" => Some(SkillKind::Rule),
+            "output-styles" => Some(SkillKind::OutputStyle),
+            _ => None,
+        }
+    }
+}
+
+fn parse_frontmatter(lines: &[&str]) -> ClaudeFrontmatter {
+    let value = serde_yaml::from_str::<serde_yaml::Value>(&lines.join("\n"))
+        .unwrap_or_else(|_| lenient_yaml(lines));
+
+    frontmatter_from_yaml(&value)
+}
+
+/// `key: value` lines read as plain text, for frontmatter Claude accepts
+/// that isn't valid YAML, like `argument-hint: [pr-number] [priority]`
+fn lenient_yaml(lines: &[&str]) -> serde_yaml::Value {
+    let text = |value: &str| serde_yaml::Value::String(unquote(value).to_string());
+
+    let mut map = serde_yaml::Mapping::new();
+    for (key, values) in fields(lines) {
+        let value = match values.as_slice() {
+            [single] => text(single),
+            many => serde_yaml::Value::Sequence(many.iter().map(|v| text(v)).collect()),
+        };
+        map.insert(serde_yaml::Value::String(key.to_string()), value);
+    }
+
+    serde_yaml::Value::Mapping(map)
+}
+
+fn frontmatter_from_yaml(value: &serde_yaml::Value) -> ClaudeFrontmatter {
+    let text = |key: &str| value.get(key)
+        .and_then(yaml_text)
+        .filter(|s| !s.is_empty());
+
+    // `argument-hint: [message]` parses as a list
+    let argument_hint = match value.get("argument-hint") {
+        Some(serde_yaml::Value::Sequence(items)) => Some(items.iter()
+            .filter_map(yaml_text)
+            .map(|item| format!("[{}]", item))
+            .collect::<Vec<_>>()
+            .join(" ")),
+        Some(other) => yaml_text(other),
+        None => None,
+    };
+
+    ClaudeFrontmatter {
+        name: text("name"),
+        description: text("description"),
+        tools: value.get("tools").map(tool_list).unwrap_or_default(),
+        model: text("model"),
+        argument_hint: argument_hint.filter(|s| !s.is_empty()),
+        allowed_tools: value.get("allowed-tools").map(tool_list).unwrap_or_default(),
+    }
+}
+
+fn yaml_text(value: &serde_yaml::Value) -> Option<String> {
+    match value {
+        serde_yaml::Value::String(s) => Some(s.trim().to_string()),
+        serde_yaml::Value::Bool(b) => Some(b.to_string()),
+        serde_yaml::Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Tools given as a list or a comma-separated string. Commas inside
+/// parentheses, as in `Bash(git add:*, git commit:*)`, don't split.
+fn tool_list(value: &serde_yaml::Value) -> Vec<String> {
+    let items: Vec<String> = match value {
+        serde_yaml::Value::Sequence(items) => items.iter().filter_map(yaml_text).collect(),
+        other => yaml_text(other).into_iter().collect(),
+    };
+
+    let mut tools = Vec::new();
+    for item in items {
+        let mut depth = 0usize;
+        let mut current = String::new();
+
+        for c in item.chars() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    tools.push(current.trim().to_string());
+                    current.clear();
+                    continue;
+                }
+                _ => {}
+            }
+            current.push(c);
+        }
+        tools.push(current.trim().to_string());
+    }
+
+    tools.retain(|t| !t.is_empty());
+    tools
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 64
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
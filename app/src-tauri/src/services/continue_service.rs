@@ -0,0 +1,552 @@
+use std::fs;
+use std::ops::Range;
+use std::path::Path;
+
+use serde_json::Value;
+
+use crate::models::{AgentType, AppError, ContinueEntry, ContinueSection, Skill, SkillFormat, SkillKind};
+use crate::services::rule_service::{fields, split_frontmatter, unquote};
+use crate::services::template_service::yaml_string;
+use crate::services::SkillService;
+
+/// Config files Continue reads from `~/.continue`; the YAML one wins when both exist
+const CONFIG_FILES: [&str; 2] = ["config.yaml", "config.json"];
+
+/// Sections exposed as skills, in the order they are listed
+const SECTIONS: [ContinueSection; 5] = [
+    ContinueSection::SystemMessage,
+    ContinueSection::Rules,
+    ContinueSection::Prompts,
+    ContinueSection::CustomCommands,
+    ContinueSection::SlashCommands,
+];
+
+/// Continue config files, prompt files and rules
+pub struct ContinueService;
+
+impl ContinueService {
+    pub fn is_config_file(path: &Path) -> bool {
+        path.file_name().is_some_and(|n| CONFIG_FILES.iter().any(|f| n == *f))
+    }
+
+    /// One skill per system message, rule, prompt and command in a config file
+    pub fn config_entries(config_path: &Path) -> Result<Vec<Skill>, AppError> {
+        let config = load(config_path)?;
+        let updated_at = fs::metadata(config_path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs() as i64);
+
+        let mut skills = Vec::new();
+        for section in SECTIONS {
+            let items: Vec<&Value> = match (section, config.get(section.key())) {
+                (_, None) => continue,
+                (ContinueSection::SystemMessage, Some(value)) => vec![value],
+                (_, Some(Value::Array(items))) => items.iter().collect(),
+                _ => continue,
+            };
+
+            for (index, item) in items.into_iter().enumerate() {
+                // Hub blocks (`uses: owner/rule`) live elsewhere
+                let Some(content) = entry_content(section, item) else { continue };
+                let entry = ContinueEntry {
+                    config_path: config_path.to_string_lossy().to_string(),
+                    section,
+                    index,
+                };
+
+                let text = |key: &str| item.get(key).and_then(Value::as_str).map(str::to_string);
+                let name = match section {
+                    ContinueSection::SystemMessage => section.key().to_string(),
+                    _ => text("name").unwrap_or_else(|| {
+                        content.lines().next().unwrap_or_default().chars().take(60).collect()
+                    }),
+                };
+
+                let mut skill = Skill::new_single_file(name, entry.item_path(), AgentType::ContinueDev, SkillFormat::Markdown);
+                skill.kind = match section {
+                    ContinueSection::SystemMessage => SkillKind::Memory,
+                    ContinueSection::Rules => SkillKind::Rule,
+                    _ => SkillKind::SlashCommand,
+                };
+                skill.description = match section {
+                    ContinueSection::SlashCommands => None,
+                    _ => text("description"),
+                };
+                if let Some(updated_at) = updated_at {
+                    skill.updated_at = updated_at;
+                }
+                skill.continue_entry = Some(entry);
+                skills.push(skill);
+            }
+        }
+
+        Ok(skills)
+    }
+
+    /// Current text of a config entry
+    pub fn read_entry(entry: &ContinueEntry) -> Result<String, AppError> {
+        let config = load(Path::new(&entry.config_path))?;
+        lookup(&config, entry)
+            .and_then(|item| entry_content(entry.section, item))
+            .ok_or_else(|| AppError::NotFound(entry.item_path()))
+    }
+
+    /// Replace the text of a config entry in place. The rest of the file,
+    /// including comments, key order and formatting, is left as it was.
+    pub fn update_entry(entry: &ContinueEntry, content: &str) -> Result<Skill, AppError> {
+        let path = Path::new(&entry.config_path);
+        let text = fs::read_to_string(path)?;
+        let config = parse(path, &text)?;
+
+        let item = lookup(&config, entry).ok_or_else(|| AppError::NotFound(entry.item_path()))?;
+        // Plain string rules have no field to edit
+        let field = entry.section.content_field().filter(|_| item.is_object());
+
+        let updated = if is_json(path) {
+            let mut segments = vec![Segment::Key(entry.section.key())];
+            if entry.section != ContinueSection::SystemMessage {
+                segments.push(Segment::Index(entry.index));
+            }
+            if let Some(field) = field {
+                segments.push(Segment::Key(field));
+            }
+
+            json_span(&text, &segments).map(|span| {
+                let value = serde_json::to_string(content).unwrap_or_default();
+                format!("{}{}{}", &text[..span.start], value, &text[span.end..])
+            })
+        } else {
+            yaml_replace(&text, entry.section.key(), entry.index, field, content)
+        };
+
+        // Only write when the edit reads back as intended
+        let updated = updated
+            .filter(|updated| {
+                parse(path, updated).ok()
+                    .and_then(|config| lookup(&config, entry).and_then(|item| entry_content(entry.section, item)))
+                    .is_some_and(|written| written == content)
+            })
+            .ok_or_else(|| AppError::ParseError(format!(
+                "Cannot update {} in place; edit {} directly",
+                entry.item_path(),
+                entry.config_path
+            )))?;
+
+        fs::write(path, updated)?;
+
+        Self::config_entries(path)?
+            .into_iter()
+            .find(|s| s.continue_entry.as_ref() == Some(entry))
+            .ok_or_else(|| AppError::NotFound(entry.item_path()))
+    }
+
+    /// Create `<prompts_dir>/<name>.prompt`, available in chat as `/<name>`
+    pub fn create_prompt(
+        prompts_dir: &Path,
+        name: &str,
+        description: Option<&str>,
+        content: &str,
+    ) -> Result<Skill, AppError> {
+        let slug = name.trim().to_lowercase().replace(' ', "-");
+        let path = prompts_dir.join(format!("{}.prompt", slug));
+        if path.exists() {
+            return Err(AppError::AlreadyExists(path.to_string_lossy().to_string()));
+        }
+
+        let mut preamble = format!("name: {}\n", yaml_string(&slug));
+        if let Some(description) = description {
+            preamble.push_str(&format!("description: {}\n", yaml_string(description)));
+        }
+
+        fs::create_dir_all(prompts_dir)?;
+        fs::write(&path, format!("{}---\n{}", preamble, content))?;
+
+        SkillService::parse_single_file(&path, &AgentType::ContinueDev)
+    }
+
+    /// Description and body of a `.prompt` file or a rule in `.continue/rules`.
+    /// Prompt files put their `name`/`description` preamble above a `---`
+    /// line; rules use regular frontmatter.
+    pub fn parse_file(content: &str) -> (Option<String>, &str) {
+        let (lines, body) = match split_frontmatter(content) {
+            Some(split) => split,
+            None => match content.find("\n---\n") {
+                Some(at) => (content[..at].lines().collect(), &content[at + 5..]),
+                None => return (None, content),
+            },
+        };
+
+        let description = fields(&lines)
+            .into_iter()
+            .find(|(key, _)| *key == "description")
+            .map(|(_, values)| unquote(&values.join(" ")).to_string())
+            .filter(|d| !d.is_empty());
+
+        (description, body)
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "json")
+}
+
+fn load(path: &Path) -> Result<Value, AppError> {
+    let text = fs::read_to_string(path)?;
+    parse(path, &text)
+}
+
+fn parse(path: &Path, text: &str) -> Result<Value, AppError> {
+    if is_json(path) {
+        Ok(serde_json::from_str(text)?)
+    } else {
+        Ok(serde_yaml::from_str(text)?)
+    }
+}
+
+fn lookup<'a>(config: &'a Value, entry: &ContinueEntry) -> Option<&'a Value> {
+    let value = config.get(entry.section.key())?;
+    match entry.section {
+        ContinueSection::SystemMessage => Some(value),
+        _ => value.get(entry.index),
+    }
+}
+
+fn entry_content(section: ContinueSection, item: &Value) -> Option<String> {
+    match item {
+        Value::String(text) => Some(text.clone()),
+        Value::Object(_) => section.content_field()
+            .and_then(|field| item.get(field))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+pub(crate) enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Byte range of the value at `path` in JSON text
+pub(crate) fn json_span(text: &str, path: &[Segment]) -> Option<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut pos = skip_whitespace(bytes, 0);
+
+    for segment in path {
+        match (segment, bytes.get(pos)?) {
+            (Segment::Key(key), b'{') => {
+                pos = skip_whitespace(bytes, pos + 1);
+                loop {
+                    let key_end = skip_string(bytes, pos)?;
+                    let name: String = serde_json::from_str(&text[pos..key_end]).ok()?;
+                    pos = skip_whitespace(bytes, key_end);
+                    if bytes.get(pos)? != &b':' {
+                        return None;
+                    }
+                    pos = skip_whitespace(bytes, pos + 1);
+                    if name == *key {
+                        break;
+                    }
+                    pos = skip_whitespace(bytes, skip_value(bytes, pos)?);
+                    if bytes.get(pos)? != &b',' {
+                        return None;
+                    }
+                    pos = skip_whitespace(bytes, pos + 1);
+                }
+            }
+            (Segment::Index(index), b'[') => {
+                pos = skip_whitespace(bytes, pos + 1);
+                for _ in 0..*index {
+                    pos = skip_whitespace(bytes, skip_value(bytes, pos)?);
+                    if bytes.get(pos)? != &b',' {
+                        return None;
+                    }
+                    pos = skip_whitespace(bytes, pos + 1);
+                }
+            }
+            _ => return None,
+        }
+    }
+
+    Some(pos..skip_value(bytes, pos)?)
+}
+
+pub(crate) fn skip_whitespace(bytes: &[u8], pos: usize) -> usize {
+    bytes[pos.min(bytes.len())..].iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .map_or(bytes.len(), |n| pos + n)
+}
+
+pub(crate) fn skip_string(bytes: &[u8], pos: usize) -> Option<usize> {
+    if bytes.get(pos)? != &b'"' {
+        return None;
+    }
+
+    let mut i = pos + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return Some(i + 1),
+            _ => i += 1,
+        }
+    }
+    None
+}
+
+pub(crate) fn skip_value(bytes: &[u8], pos: usize) -> Option<usize> {
+    match bytes.get(pos)? {
+        b'"' => skip_string(bytes, pos),
+        b'{' | b'[' => {
+            let mut depth = 0usize;
+            let mut i = pos;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = skip_string(bytes, i)?;
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            None
+        }
+        b',' | b'}' | b']' => None,
+        // Numbers, booleans and null
+        _ => Some(bytes[pos..].iter()
+            .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+            .map_or(bytes.len(), |n| pos + n)),
+    }
+}
+
+/// Replace entry `index` of the top-level list `key` (or its `field`) in
+/// block-style YAML, leaving every other line untouched
+fn yaml_replace(text: &str, key: &str, index: usize, field: Option<&str>, content: &str) -> Option<String> {
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let indent = |line: &str| line.len() - line.trim_start_matches(' ').len();
+    let is_blank = |line: &str| {
+        let trimmed = line.trim();
+        trimmed.is_empty() || trimmed.starts_with('#')
+    };
+
+    // `key:` with nothing after it; flow-style lists can't be edited by line
+    let header = lines.iter().position(|line| {
+        line.strip_prefix(key)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .is_some_and(is_blank)
+    })?;
+
+    let first = (header + 1..lines.len()).find(|&i| !is_blank(lines[i]))?;
+    let dash = indent(lines[first]);
+    if !lines[first].trim_start().starts_with('-') {
+        return None;
+    }
+
+    // The list ends at the next top-level key
+    let end = (first..lines.len())
+        .find(|&i| !is_blank(lines[i]) && indent(lines[i]) < dash.max(1) && !(dash == 0 && lines[i].starts_with('-')))
+        .unwrap_or(lines.len());
+    let entries: Vec<usize> = (first..end)
+        .filter(|&i| indent(lines[i]) == dash && lines[i].trim_start().starts_with('-'))
+        .collect();
+
+    let start = *entries.get(index)?;
+    let mut stop = entries.get(index + 1).copied().unwrap_or(end);
+    while stop > start + 1 && lines[stop - 1].trim().is_empty() {
+        stop -= 1;
+    }
+
+    let pad = |n: usize| " ".repeat(n);
+    let (range, replacement) = match field {
+        None => (start..stop, format!("{}- {}", pad(dash), yaml_block(content, dash))),
+        Some(field) => {
+            let after_dash = &lines[start][dash + 1..];
+            let key_indent = if after_dash.trim().is_empty() {
+                indent(lines.get(start + 1)?)
+            } else {
+                dash + 1 + indent(after_dash)
+            };
+
+            let is_field = |line: &str| {
+                line.strip_prefix(field)
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with([' ', '\n', '\r']))
+            };
+            let field_line = (start..stop).find(|&i| {
+                if i == start {
+                    is_field(after_dash.trim_start())
+                } else {
+                    indent(lines[i]) == key_indent && is_field(lines[i].trim_start())
+                }
+            })?;
+
+            let mut field_end = (field_line + 1..stop)
+                .find(|&i| !lines[i].trim().is_empty() && indent(lines[i]) <= key_indent)
+                .unwrap_or(stop);
+            while field_end > field_line + 1 && lines[field_end - 1].trim().is_empty() {
+                field_end -= 1;
+            }
+
+            let prefix = if field_line == start {
+                format!("{}-{}", pad(dash), pad(key_indent - dash - 1))
+            } else {
+                pad(key_indent)
+            };
+            (field_line..field_end, format!("{}{}: {}", prefix, field, yaml_block(content, key_indent)))
+        }
+    };
+
+    let mut out: String = lines[..range.start].concat();
+    out.push_str(&replacement);
+    out.push_str(&lines[range.end..].concat());
+    Some(out)
+}
+
+/// A YAML value for text, as a literal block indented past `indent` when it
+/// spans lines
+fn yaml_block(content: &str, indent: usize) -> String {
+    if !content.contains('\n') {
+        return format!("{}\n", yaml_string(content));
+    }
+    // Block scalars can't start with indented text; a quoted string can
+    if content.starts_with([' ', '\t']) {
+        return format!("{}\n", serde_json::to_string(content).unwrap_or_default());
+    }
+
+    let chomping = if content.ends_with("\n\n") {
+        "+"
+    } else if content.ends_with('\n') {
+        ""
+    } else {
+        "-"
+    };
+
+    let mut out = format!("|{}\n", chomping);
+    for line in content.strip_suffix('\n').unwrap_or(content).split('\n') {
+        if !line.is_empty() {
+            out.push_str(&" ".repeat(indent + 2));
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_config(name: &str, text: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("continue-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, text).unwrap();
+        path
+    }
+
+    /// Update one entry and return the file as written
+    fn update(name: &str, text: &str, section: ContinueSection, index: usize, content: &str) -> Result<String, AppError> {
+        let path = temp_config(name, text);
+        let entry = ContinueEntry { config_path: path.to_string_lossy().to_string(), section, index };
+        let result = ContinueService::update_entry(&entry, content).map(|_| fs::read_to_string(&path).unwrap());
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+        result
+    }
+
+    #[test]
+    fn json_keeps_formatting_around_the_edited_value() {
+        let text = "{\n  // not valid JSON, so never written\n}";
+        assert!(update("config.json", text, ContinueSection::SystemMessage, 0, "x").is_err());
+
+        let text = "{\n  \"models\": [],\n  \"systemMessage\": \"Be brief\",\n  \"customCommands\": [\n    {\"name\": \"a\", \"prompt\": \"one\"},\n    {\n      \"name\": \"b\",\n      \"description\": \"B\",\n      \"prompt\": \"two\"\n    }\n  ]\n}\n";
+        assert_eq!(
+            update("config.json", text, ContinueSection::SystemMessage, 0, "Be \"very\" brief").unwrap(),
+            text.replace("\"Be brief\"", "\"Be \\\"very\\\" brief\"")
+        );
+        assert_eq!(
+            update("config.json", text, ContinueSection::CustomCommands, 1, "line 1\nline 2").unwrap(),
+            text.replace("\"two\"", "\"line 1\\nline 2\"")
+        );
+    }
+
+    #[test]
+    fn json_one_line_config_and_string_rules() {
+        let text = r#"{"rules":["Use tabs",{"name":"r","rule":"Old"}],"systemMessage":"s"}"#;
+        assert_eq!(
+            update("config.json", text, ContinueSection::Rules, 0, "Use spaces").unwrap(),
+            text.replace("Use tabs", "Use spaces")
+        );
+        assert_eq!(
+            update("config.json", text, ContinueSection::Rules, 1, "New").unwrap(),
+            text.replace("Old", "New")
+        );
+    }
+
+    #[test]
+    fn missing_entries_are_not_found() {
+        let text = r#"{"customCommands":[{"name":"a","prompt":"one"}]}"#;
+        assert!(matches!(
+            update("config.json", text, ContinueSection::CustomCommands, 1, "x"),
+            Err(AppError::NotFound(_))
+        ));
+        assert!(matches!(
+            update("config.yaml", "name: c\n", ContinueSection::Prompts, 0, "x"),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[test]
+    fn yaml_replaces_a_field_and_leaves_other_lines() {
+        let text = "# my config\nname: c\nprompts:\n  - name: a\n    prompt: one\n\n  # second\n  - name: b\n    description: B\n    prompt: |\n      two\n      lines\n    extra: true\nrules:\n  - Keep it short\n";
+        assert_eq!(
+            update("config.yaml", text, ContinueSection::Prompts, 1, "three").unwrap(),
+            text.replace("    prompt: |\n      two\n      lines\n", "    prompt: three\n")
+        );
+        assert_eq!(
+            update("config.yaml", text, ContinueSection::Prompts, 0, "multi\nline\n").unwrap(),
+            text.replace("    prompt: one\n", "    prompt: |\n      multi\n      line\n")
+        );
+    }
+
+    #[test]
+    fn yaml_replaces_whole_string_items() {
+        let text = "rules:\n- First\n- Second\nprompts: []\n";
+        assert_eq!(
+            update("config.yaml", text, ContinueSection::Rules, 1, "Updated: rule").unwrap(),
+            "rules:\n- First\n- 'Updated: rule'\nprompts: []\n"
+        );
+        assert_eq!(
+            update("config.yaml", text, ContinueSection::Rules, 0, "a\nb").unwrap(),
+            "rules:\n- |-\n  a\n  b\n- Second\nprompts: []\n"
+        );
+    }
+
+    #[test]
+    fn yaml_field_on_the_dash_line() {
+        let text = "prompts:\n  - prompt: one\n    name: a\n";
+        assert_eq!(
+            update("config.yaml", text, ContinueSection::Prompts, 0, "two").unwrap(),
+            "prompts:\n  - prompt: two\n    name: a\n"
+        );
+    }
+
+    #[test]
+    fn flow_style_yaml_is_refused() {
+        let text = "rules: [\"one\", \"two\"]\n";
+        assert!(matches!(
+            update("config.yaml", text, ContinueSection::Rules, 0, "x"),
+            Err(AppError::ParseError(_))
+        ));
+        assert_eq!(yaml_block(" indented\ntext", 0), "\" indented\\ntext\"\n");
+    }
+}
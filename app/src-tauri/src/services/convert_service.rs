@@ -0,0 +1,113 @@
+use crate::models::{AgentType, SkillFormat, AppError};
+use crate::services::rule_service::split_frontmatter;
+use crate::services::template_service::yaml_string;
+
+/// Keys that hold the prompt text in JSON/YAML skill configs
+const TEXT_KEYS: [&str; 4] = ["systemMessage", "prompt", "content", "rules"];
+
+pub struct ConvertService;
+
+impl ConvertService {
+    /// Entry-file format each agent reads natively
+    pub fn native_format(agent: &AgentType) -> SkillFormat {
+        match agent {
+            AgentType::ContinueDev => SkillFormat::Json,
+            AgentType::Aider => SkillFormat::PlainText,
+            _ => SkillFormat::Markdown,
+        }
+    }
+
+    /// Agents that read Agent Skills folders: a `SKILL.md` with `name` and
+    /// `description` frontmatter
+    pub fn uses_skill_md(agent: &AgentType) -> bool {
+        matches!(agent, AgentType::Claude | AgentType::Copilot | AgentType::Codex | AgentType::Gemini)
+    }
+
+    /// Entry file name of a skill folder for an agent
+    pub fn entry_file_name(agent: &AgentType, format: &SkillFormat) -> String {
+        if Self::uses_skill_md(agent) && *format == SkillFormat::Markdown {
+            "SKILL.md".to_string()
+        } else {
+            format!("skill.{}", format.extension())
+        }
+    }
+
+    /// Convert skill entry content to the native format of `to`.
+    /// Returns the new format and content.
+    pub fn convert(
+        name: &str,
+        content: &str,
+        from: &SkillFormat,
+        to: &AgentType,
+    ) -> Result<(SkillFormat, String), AppError> {
+        let target = Self::native_format(to);
+        if &target == from {
+            return Ok((target, Self::with_skill_frontmatter(name, None, content, to)));
+        }
+
+        let text = Self::extract_text(content, from)?;
+
+        let converted = match target {
+            SkillFormat::Json => {
+                let value = serde_json::json!({
+                    "name": name,
+                    "systemMessage": text,
+                });
+                serde_json::to_string_pretty(&value)?
+            }
+            SkillFormat::Markdown if !text.trim_start().starts_with('#') && !text.starts_with("---") => {
+                format!("# {}\n\n{}", name, text)
+            }
+            _ => text,
+        };
+
+        Ok((target, Self::with_skill_frontmatter(name, None, &converted, to)))
+    }
+
+    /// Add the `name`/`description` frontmatter Agent Skills require to
+    /// markdown that has none. Without a `description`, the first paragraph
+    /// line is used.
+    pub fn with_skill_frontmatter(name: &str, description: Option<&str>, content: &str, agent: &AgentType) -> String {
+        if !Self::uses_skill_md(agent) || split_frontmatter(content).is_some() {
+            return content.to_string();
+        }
+
+        let description: String = description
+            .map(str::trim)
+            .filter(|d| !d.is_empty())
+            .or_else(|| content.lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty() && !line.starts_with('#')))
+            .unwrap_or(name)
+            .chars()
+            .take(1024)
+            .collect();
+
+        format!(
+            "---\nname: {}\ndescription: {}\n---\n\n{}",
+            yaml_string(&name.trim().to_lowercase().replace(' ', "-")),
+            yaml_string(&description),
+            content
+        )
+    }
+
+    /// Pull the instruction text out of a skill file
+    fn extract_text(content: &str, format: &SkillFormat) -> Result<String, AppError> {
+        let value: serde_json::Value = match format {
+            SkillFormat::Json => serde_json::from_str(content)?,
+            SkillFormat::Yaml => serde_yaml::from_str(content)?,
+            SkillFormat::Python => {
+                return Err(AppError::ParseError("Python skills cannot be converted".into()));
+            }
+            _ => return Ok(content.to_string()),
+        };
+
+        TEXT_KEYS.iter()
+            .find_map(|key| value.get(key).and_then(|v| v.as_str()))
+            .map(String::from)
+            .ok_or_else(|| AppError::ParseError(format!(
+                "No prompt text found (expected one of: {})",
+                TEXT_KEYS.join(", ")
+            )))
+    }
+}
@@ -3,9 +3,11 @@ use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::fs;
 use serde::Serialize;
 
-use crate::models::{Skill, SkillFile, AgentType, SkillFormat, AppError};
+use crate::models::{Skill, SkillFile, SkillKind, AgentType, SkillFormat, AppError};
 use crate::services::backup_service::BackupService;
 use crate::services::template_service::TemplateService;
+use crate::services::windsurf_service::WindsurfService;
+use crate::services::aider_service::AiderService;
 
 pub struct CrudService {
     backup: BackupService,
@@ -91,6 +93,11 @@ impl CrudService {
             file_count: 1,
             created_at: now,
             updated_at: now,
+            kind: SkillKind::default(),
+            rule: None,
+            windsurf: None,
+            claude: None,
+            continue_entry: None,
         })
     }
 
@@ -291,7 +298,7 @@ impl CrudService {
 
     // Helper methods
 
-    fn get_agent_skills_dir(&self, agent: &AgentType) -> Result<PathBuf, AppError> {
+    pub fn get_agent_skills_dir(&self, agent: &AgentType) -> Result<PathBuf, AppError> {
         let home = dirs::home_dir()
             .ok_or_else(|| AppError::InvalidPath("Cannot find home directory".into()))?;
 
@@ -299,15 +306,19 @@ impl CrudService {
             AgentType::Claude => home.join(".claude").join("skills"),
             AgentType::Cursor => home.join(".cursor").join("skills"),
             AgentType::ContinueDev => home.join(".continue").join("skills"),
-            AgentType::Aider => home.join(".aider").join("prompts"),
-            AgentType::Windsurf => home.join(".codeium").join("skills"),
+            AgentType::Aider => AiderService::conventions_dir(&home),
+            AgentType::Windsurf => WindsurfService::global_workflows_dir(&home.join(".codeium").join("windsurf")),
+            AgentType::Copilot => home.join(".copilot").join("skills"),
+            AgentType::Codex => home.join(".codex").join("skills"),
+            AgentType::Gemini => home.join(".gemini").join("skills"),
+            AgentType::Cline => home.join("Documents").join("Cline").join("Rules"),
             AgentType::Custom(name) => home.join(format!(".{}", name.to_lowercase())).join("skills"),
         };
 
         Ok(path)
     }
 
-    fn sanitize_filename(&self, name: &str) -> String {
+    pub fn sanitize_filename(&self, name: &str) -> String {
         name.chars()
             .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
             .collect::<String>()
@@ -0,0 +1,229 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::models::AppError;
+use crate::services::download_service::DownloadService;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileChange {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Changes to one file of a skill
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileDiff {
+    /// Path relative to the skill folder (or the file name for single-file skills)
+    pub path: String,
+    pub change: FileChange,
+    /// Unified diff; None for binary files
+    pub patch: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStatus {
+    /// Local changes merged cleanly with the new version
+    Merged,
+    /// Local and new changes overlap, or the file cannot be merged
+    Conflict,
+}
+
+/// Three-way merge of one locally modified file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileMerge {
+    pub path: String,
+    pub status: MergeStatus,
+    /// Merged text, with conflict markers for conflicts. None when the file
+    /// is binary or was deleted on one side.
+    pub merged: Option<String>,
+    /// Local content (None when deleted locally)
+    #[serde(skip)]
+    pub ours: Option<Vec<u8>>,
+    /// New version's content (None when removed upstream)
+    #[serde(skip)]
+    pub theirs: Option<Vec<u8>>,
+}
+
+impl FileMerge {
+    /// Content to write for a resolution; None means the file should not exist
+    pub fn resolve(&self, resolution: MergeResolution) -> Result<Option<Vec<u8>>, AppError> {
+        match resolution {
+            MergeResolution::KeepMine => Ok(self.ours.clone()),
+            MergeResolution::TakeTheirs => Ok(self.theirs.clone()),
+            MergeResolution::Merge => match (&self.merged, &self.status) {
+                (Some(text), _) => Ok(Some(text.clone().into_bytes())),
+                (None, MergeStatus::Merged) => Ok(self.ours.clone()),
+                (None, MergeStatus::Conflict) => Err(AppError::MergeConflict(format!(
+                    "{} cannot be merged; keep your version or take theirs",
+                    self.path
+                ))),
+            },
+        }
+    }
+}
+
+/// How to resolve a locally modified file when updating
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeResolution {
+    /// Write the merged text (including any conflict markers)
+    Merge,
+    KeepMine,
+    TakeTheirs,
+}
+
+/// Line-based diffs and merges of skill content
+pub struct DiffService;
+
+impl DiffService {
+    /// Diff two versions of a file. None (missing) means the file was added
+    /// or removed. Returns None when the content is identical.
+    pub fn diff_file(path: &str, old: Option<&[u8]>, new: Option<&[u8]>) -> Option<FileDiff> {
+        let change = match (old, new) {
+            (None, None) => return None,
+            (None, Some(_)) => FileChange::Added,
+            (Some(_), None) => FileChange::Removed,
+            (Some(a), Some(b)) if a == b => return None,
+            (Some(_), Some(_)) => FileChange::Modified,
+        };
+
+        let old_text = old.map(std::str::from_utf8).unwrap_or(Ok(""));
+        let new_text = new.map(std::str::from_utf8).unwrap_or(Ok(""));
+
+        let patch = match (old_text, new_text) {
+            (Ok(a), Ok(b)) => Some(
+                diffy::DiffOptions::new()
+                    .set_original_filename(format!("a/{}", path))
+                    .set_modified_filename(format!("b/{}", path))
+                    .create_patch(a, b)
+                    .to_string(),
+            ),
+            _ => None,
+        };
+
+        Some(FileDiff {
+            path: path.to_string(),
+            change,
+            patch,
+        })
+    }
+
+    /// Diff every file of two skill folders. Either folder may be missing.
+    pub fn diff_folders(old: &Path, new: &Path) -> Result<Vec<FileDiff>, AppError> {
+        let old_files = Self::read_tree(old)?;
+        let new_files = Self::read_tree(new)?;
+        let paths: BTreeSet<&String> = old_files.keys().chain(new_files.keys()).collect();
+
+        Ok(paths.into_iter()
+            .filter_map(|path| Self::diff_file(
+                path,
+                old_files.get(path).map(Vec::as_slice),
+                new_files.get(path).map(Vec::as_slice),
+            ))
+            .collect())
+    }
+
+    /// Three-way merge of a file that was changed locally.
+    /// `base` is the content as installed; None when it is no longer available,
+    /// in which case any difference between ours and theirs is a conflict.
+    pub fn merge_file(
+        path: &str,
+        base: Option<&[u8]>,
+        ours: Option<&[u8]>,
+        theirs: Option<&[u8]>,
+    ) -> FileMerge {
+        let (status, merged) = if ours == theirs || (base.is_some() && theirs == base) {
+            // Same change on both sides, or no change upstream: keep ours
+            (MergeStatus::Merged, ours.and_then(|b| std::str::from_utf8(b).ok()).map(String::from))
+        } else {
+            match (base.map(std::str::from_utf8), ours.map(std::str::from_utf8), theirs.map(std::str::from_utf8)) {
+                (Some(Ok(base)), Some(Ok(ours)), Some(Ok(theirs))) => match diffy::merge(base, ours, theirs) {
+                    Ok(merged) => (MergeStatus::Merged, Some(merged)),
+                    Err(with_markers) => (MergeStatus::Conflict, Some(with_markers)),
+                },
+                // Unknown base: show the whole file as a conflict
+                (None, Some(Ok(ours)), Some(Ok(theirs))) => {
+                    let merged = diffy::merge("", ours, theirs).unwrap_or_else(|markers| markers);
+                    (MergeStatus::Conflict, Some(merged))
+                }
+                _ => (MergeStatus::Conflict, None),
+            }
+        };
+
+        FileMerge {
+            path: path.to_string(),
+            status,
+            merged,
+            ours: ours.map(<[u8]>::to_vec),
+            theirs: theirs.map(<[u8]>::to_vec),
+        }
+    }
+
+    /// Contents of a skill: every file of a folder keyed by relative path,
+    /// or a single file keyed by its name. Missing paths are empty.
+    pub fn read_tree(path: &Path) -> Result<BTreeMap<String, Vec<u8>>, AppError> {
+        let mut files = BTreeMap::new();
+
+        if path.is_dir() {
+            let mut paths = BTreeSet::new();
+            collect_files(path, path, &mut paths)?;
+            for relative in paths {
+                let bytes = fs::read(path.join(&relative))?;
+                files.insert(relative.to_string_lossy().replace('\\', "/"), bytes);
+            }
+        } else if path.is_file() {
+            let name = path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+            files.insert(name, fs::read(path)?);
+        }
+
+        Ok(files)
+    }
+
+    /// `sha256:<hex>` of a skill file, or of every file in a skill folder
+    pub fn content_hash(path: &Path) -> Result<String, AppError> {
+        if path.is_file() {
+            return Ok(DownloadService::checksum(&fs::read(path)?));
+        }
+
+        Ok(Self::tree_hash(&Self::read_tree(path)?))
+    }
+
+    /// `sha256:<hex>` of a folder's files as returned by `read_tree`
+    pub fn tree_hash(files: &BTreeMap<String, Vec<u8>>) -> String {
+        let listing: String = files.iter()
+            .map(|(name, bytes)| format!("{} {}\n", name, DownloadService::checksum(bytes)))
+            .collect();
+
+        DownloadService::checksum(listing.as_bytes())
+    }
+}
+
+/// Relative paths of all files under `dir`, skipping `.git` and symlinks
+fn collect_files(root: &Path, dir: &Path, out: &mut BTreeSet<PathBuf>) -> Result<(), AppError> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let file_type = entry.file_type()?;
+        let path = entry.path();
+
+        if file_type.is_symlink() {
+            continue;
+        }
+        if file_type.is_dir() {
+            if path.file_name().is_some_and(|n| n == ".git") {
+                continue;
+            }
+            collect_files(root, &path, out)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            out.insert(relative.to_path_buf());
+        }
+    }
+
+    Ok(())
+}
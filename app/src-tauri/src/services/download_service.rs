@@ -1,4 +1,5 @@
 use reqwest::Client;
+use sha2::{Sha256, Digest};
 use std::path::PathBuf;
 use std::time::Duration;
 use tokio::fs;
@@ -21,6 +22,12 @@ impl DownloadService {
 
     /// Fetch text content from a URL
     pub async fn fetch_text(&self, url: &str) -> Result<String, AppError> {
+        if let Some(path) = Self::local_path(url) {
+            return fs::read_to_string(&path)
+                .await
+                .map_err(|e| AppError::IoError(format!("Failed to read {}: {}", path.display(), e)));
+        }
+
         let response = self.client
             .get(url)
             .send()
@@ -41,8 +48,76 @@ impl DownloadService {
             .map_err(|e| AppError::IoError(format!("Failed to read response: {}", e)))
     }
 
+    /// Fetch raw bytes from a URL
+    pub async fn fetch_bytes(&self, url: &str) -> Result<Vec<u8>, AppError> {
+        if let Some(path) = Self::local_path(url) {
+            return fs::read(&path)
+                .await
+                .map_err(|e| AppError::IoError(format!("Failed to read {}: {}", path.display(), e)));
+        }
+
+        let response = self.client
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| AppError::IoError(format!("Download failed: {}", e)))?;
+
+        if !response.status().is_success() {
+            return Err(AppError::IoError(format!(
+                "HTTP {} downloading {}",
+                response.status(),
+                url
+            )));
+        }
+
+        response
+            .bytes()
+            .await
+            .map(|b| b.to_vec())
+            .map_err(|e| AppError::IoError(format!("Failed to read bytes: {}", e)))
+    }
+
+    /// `sha256:<hex>` checksum of some content
+    pub fn checksum(bytes: &[u8]) -> String {
+        format!("sha256:{:x}", Sha256::digest(bytes))
+    }
+
+    /// Verify content against a `sha256:<hex>` (or bare hex) checksum.
+    /// Checksums in other formats are not verified.
+    pub fn verify_checksum(bytes: &[u8], expected: &str, label: &str) -> Result<(), AppError> {
+        let hex = expected.strip_prefix("sha256:").unwrap_or(expected).to_lowercase();
+        if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            log::debug!("Skipping unsupported checksum for {}: {}", label, expected);
+            return Ok(());
+        }
+
+        let actual = format!("{:x}", Sha256::digest(bytes));
+        if actual != hex {
+            return Err(AppError::ChecksumMismatch(format!(
+                "{} (expected sha256:{}, got sha256:{})",
+                label, hex, actual
+            )));
+        }
+
+        Ok(())
+    }
+
     /// Download a file to a destination path
     pub async fn download_file(&self, url: &str, dest: &PathBuf) -> Result<(), AppError> {
+        if let Some(path) = Self::local_path(url) {
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)
+                    .await
+                    .map_err(|e| AppError::IoError(format!("Failed to create dir: {}", e)))?;
+            }
+
+            fs::copy(&path, dest)
+                .await
+                .map_err(|e| AppError::IoError(format!("Failed to copy {}: {}", path.display(), e)))?;
+
+            return Ok(());
+        }
+
         let response = self.client
             .get(url)
             .send()
@@ -76,6 +151,22 @@ impl DownloadService {
         Ok(())
     }
 
+    /// Resolve a `file://` URL to a local path (None for remote URLs)
+    pub fn local_path(url: &str) -> Option<PathBuf> {
+        let raw = url.strip_prefix("file://")?;
+        let decoded = urlencoding::decode(raw)
+            .map(|s| s.into_owned())
+            .unwrap_or_else(|_| raw.to_string());
+
+        // file:///C:/path on Windows
+        let path = match decoded.strip_prefix('/') {
+            Some(rest) if rest.chars().nth(1) == Some(':') => rest.to_string(),
+            _ => decoded,
+        };
+
+        Some(PathBuf::from(path))
+    }
+
     /// Convert GitHub blob URLs to raw content URLs
     pub fn convert_github_url_to_raw(&self, url: &str) -> String {
         // Convert GitHub blob URLs to raw content URLs
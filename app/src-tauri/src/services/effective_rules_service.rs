@@ -0,0 +1,325 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use glob::{MatchOptions, Pattern};
+
+use crate::models::{
+    AgentConfig, AgentType, AppError, ContinueSection, EffectiveRules, MatchReason, MemoryLevel, RuleMatch,
+    RuleMode, Skill, SkillKind, WindsurfTrigger,
+};
+use crate::services::rule_service::{fields, split_frontmatter, unquote};
+use crate::services::{AiderService, ClaudeMemoryService, SkillService};
+
+/// Which rules each agent applies to a file, following each agent's own
+/// attachment rules
+pub struct EffectiveRulesService;
+
+impl EffectiveRulesService {
+    /// Rules that apply to `file`, from the home `skills` already scanned and
+    /// the project the file is in
+    pub fn effective_rules(
+        file: &Path,
+        home: &Path,
+        skills: &[Skill],
+        configs: &[AgentConfig],
+    ) -> Result<EffectiveRules, AppError> {
+        if !file.is_absolute() {
+            return Err(AppError::InvalidPath(file.to_string_lossy().to_string()));
+        }
+
+        let folder = if file.is_dir() { file } else { file.parent().unwrap_or(file) };
+        let project_dir = Self::project_dir(folder);
+        let enabled = |agent: &AgentType| configs.iter().any(|c| c.enabled && c.agent == *agent);
+
+        let mut candidates: Vec<Skill> = skills.iter()
+            .filter(|s| enabled(&s.agent))
+            .cloned()
+            .collect();
+        candidates.extend(SkillService::scan_project_skills(&project_dir, configs));
+        candidates.sort_by(|a, b| a.folder_path.cmp(&b.folder_path));
+        candidates.dedup_by(|a, b| a.folder_path == b.folder_path && a.agent == b.agent);
+
+        let mut rules: Vec<RuleMatch> = candidates.iter()
+            .filter_map(|skill| match_skill(skill, file, &project_dir))
+            .collect();
+
+        if enabled(&AgentType::Claude) {
+            rules.extend(claude_memory(file, folder, home, configs));
+        }
+        for agent in [AgentType::Codex, AgentType::Gemini] {
+            if enabled(&agent) {
+                rules.extend(directory_instructions(&agent, folder, &project_dir));
+            }
+        }
+        if enabled(&AgentType::Aider) {
+            rules.extend(aider_conventions(home, &project_dir));
+        }
+
+        rules.sort_by_key(|m| configs.iter().position(|c| c.agent == m.skill.agent));
+
+        Ok(EffectiveRules {
+            path: file.to_string_lossy().to_string(),
+            project_dir: project_dir.to_string_lossy().to_string(),
+            rules,
+        })
+    }
+
+    /// The nearest folder at or above `folder` with a `.git`, or `folder`
+    /// itself outside a repository
+    pub fn project_dir(folder: &Path) -> PathBuf {
+        folder.ancestors()
+            .find(|dir| dir.join(".git").exists())
+            .unwrap_or(folder)
+            .to_path_buf()
+    }
+
+    /// Whether a rule glob matches a path relative to the rule's root.
+    /// Globs without a folder, like `*.ts`, match files in any folder.
+    pub fn glob_matches(glob: &str, relative: &Path) -> bool {
+        let glob = glob.trim().trim_start_matches("./").trim_start_matches('/');
+        let Ok(pattern) = Pattern::new(glob) else {
+            return false;
+        };
+
+        let options = MatchOptions { require_literal_separator: true, ..Default::default() };
+        if pattern.matches_path_with(relative, options) {
+            return true;
+        }
+
+        !glob.contains('/')
+            && relative.file_name().is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), options))
+    }
+}
+
+/// How a scanned skill applies to the file, if it does. Claude memory,
+/// Codex and Gemini instructions and Aider conventions are found separately.
+fn match_skill(skill: &Skill, file: &Path, project_dir: &Path) -> Option<RuleMatch> {
+    let path = Path::new(&skill.folder_path);
+    let base = rule_base(path, project_dir);
+
+    match skill.agent {
+        AgentType::Cursor => match &skill.rule {
+            Some(rule) => match rule.mode() {
+                RuleMode::Always => always(skill, "alwaysApply: true"),
+                RuleMode::AutoAttached => glob_match(skill, &rule.globs, file, &base),
+                RuleMode::AgentRequested => described(skill),
+                RuleMode::Manual => None,
+            },
+            None if skill.kind == SkillKind::Rule => always(skill, "Cursor includes .cursorrules in every request"),
+            None => None,
+        },
+        AgentType::Windsurf => match (&skill.windsurf, skill.kind) {
+            (Some(rule), _) => match rule.trigger {
+                WindsurfTrigger::AlwaysOn => always(skill, "trigger: always_on"),
+                WindsurfTrigger::Glob => glob_match(skill, &rule.globs, file, &base),
+                WindsurfTrigger::ModelDecision => described(skill),
+                WindsurfTrigger::Manual => None,
+            },
+            (None, SkillKind::Memory) => always(skill, "Windsurf includes it in every request"),
+            _ => None,
+        },
+        AgentType::Copilot => match skill.kind {
+            SkillKind::Memory => always(skill, "Repository-wide instructions apply to every request"),
+            // Instructions without `applyTo` are only attached by hand
+            SkillKind::Rule => glob_match(skill, &frontmatter_list(path, "applyTo"), file, &base),
+            _ => None,
+        },
+        AgentType::ContinueDev => match (&skill.continue_entry, skill.kind) {
+            (Some(entry), _) if matches!(entry.section, ContinueSection::Rules | ContinueSection::SystemMessage) => {
+                always(skill, "Rules in the Continue config apply to every request")
+            }
+            (None, SkillKind::Rule) => continue_rule(skill, file, &base),
+            _ => None,
+        },
+        AgentType::Claude if skill.kind == SkillKind::Rule => {
+            let paths = frontmatter_list(path, "paths");
+            if paths.is_empty() {
+                always(skill, "Rules without paths load in every session")
+            } else {
+                glob_match(skill, &paths, file, &base)
+            }
+        }
+        AgentType::Cline if skill.kind == SkillKind::Rule => always(skill, "Cline includes every rule in every request"),
+        // Project instructions are found folder by folder
+        AgentType::Codex | AgentType::Gemini if skill.kind == SkillKind::Memory && !path.starts_with(project_dir) => {
+            always(skill, "Global instructions load in every project")
+        }
+        _ => None,
+    }
+}
+
+/// A Continue rule file: `alwaysApply: true` always applies, globs attach it
+/// to matching files, and a rule with neither applies to every request
+fn continue_rule(skill: &Skill, file: &Path, base: &Path) -> Option<RuleMatch> {
+    let content = fs::read_to_string(&skill.folder_path).ok()?;
+    let lines = split_frontmatter(&content).map(|(lines, _)| lines).unwrap_or_default();
+    let fields = fields(&lines);
+
+    let always_apply = fields.iter()
+        .find(|(key, _)| *key == "alwaysApply")
+        .map(|(_, values)| values.first().is_some_and(|v| v.eq_ignore_ascii_case("true")));
+    let globs = fields.iter()
+        .find(|(key, _)| *key == "globs")
+        .map(|(_, values)| list(values))
+        .unwrap_or_default();
+
+    match always_apply {
+        Some(true) => always(skill, "alwaysApply: true"),
+        _ if !globs.is_empty() => glob_match(skill, &globs, file, base),
+        Some(false) if skill.description.is_some() => described(skill),
+        Some(false) => None,
+        None => always(skill, "Rules without globs or alwaysApply apply to every request"),
+    }
+}
+
+/// Claude memory for the file's folder. Memory files below the folder are
+/// left out, as Claude only loads them for files inside them.
+fn claude_memory(file: &Path, folder: &Path, home: &Path, configs: &[AgentConfig]) -> Vec<RuleMatch> {
+    let Some(config) = configs.iter().find(|c| c.agent == AgentType::Claude) else {
+        return Vec::new();
+    };
+
+    let context = match ClaudeMemoryService::directory_context(Path::new(&config.config_dir), home, folder) {
+        Ok(context) => context,
+        Err(e) => {
+            log::warn!("Failed to resolve Claude memory for {}: {}", file.display(), e);
+            return Vec::new();
+        }
+    };
+
+    context.sources.into_iter()
+        .filter_map(|source| {
+            let (reason, explanation) = match source.level {
+                MemoryLevel::User => (MatchReason::Always, "User memory loads in every project".to_string()),
+                MemoryLevel::Project | MemoryLevel::Local => {
+                    (MatchReason::Directory, folder_explanation(&source.skill.folder_path))
+                }
+                MemoryLevel::Import => (
+                    MatchReason::Import,
+                    format!("Imported by {}", source.imported_from.as_deref().unwrap_or_default()),
+                ),
+                MemoryLevel::Nested => return None,
+            };
+
+            Some(RuleMatch { skill: source.skill, reason, pattern: None, explanation })
+        })
+        .collect()
+}
+
+/// `AGENTS.md` or `GEMINI.md` in each folder from the project root down to
+/// the file's folder. Codex reads `AGENTS.override.md` instead of
+/// `AGENTS.md` where both exist.
+fn directory_instructions(agent: &AgentType, folder: &Path, project_dir: &Path) -> Vec<RuleMatch> {
+    let mut folders: Vec<&Path> = folder.ancestors()
+        .take_while(|dir| dir.starts_with(project_dir))
+        .collect();
+    folders.reverse();
+
+    folders.into_iter()
+        .filter_map(|dir| match agent {
+            AgentType::Codex => [dir.join("AGENTS.override.md"), dir.join("AGENTS.md")]
+                .into_iter()
+                .find(|path| path.is_file()),
+            _ => Some(dir.join("GEMINI.md")).filter(|path| path.is_file()),
+        })
+        .filter_map(|path| SkillService::parse_single_file(&path, agent).ok())
+        .map(|skill| {
+            let explanation = folder_explanation(&skill.folder_path);
+            RuleMatch { skill, reason: MatchReason::Directory, pattern: None, explanation }
+        })
+        .collect()
+}
+
+/// Conventions in the `read:` list Aider uses in the project
+fn aider_conventions(home: &Path, project_dir: &Path) -> Vec<RuleMatch> {
+    AiderService::conventions(home, Some(project_dir)).files.into_iter()
+        .filter(|file| file.loaded)
+        .filter_map(|file| {
+            let mut skill = SkillService::parse_single_file(Path::new(&file.path), &AgentType::Aider).ok()?;
+            skill.kind = SkillKind::Memory;
+            Some(RuleMatch {
+                skill,
+                reason: MatchReason::Always,
+                pattern: None,
+                explanation: format!("Listed under read: in {}", file.config_path),
+            })
+        })
+        .collect()
+}
+
+/// Folder a rule's globs are relative to: the folder holding its agent
+/// folder (`.cursor`, `.github`, ...) inside the project, or the project
+/// root for rules outside it
+fn rule_base(path: &Path, project_dir: &Path) -> PathBuf {
+    if !path.starts_with(project_dir) {
+        return project_dir.to_path_buf();
+    }
+
+    path.ancestors()
+        .find(|dir| dir.is_dir() && dir.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')))
+        .and_then(|dir| dir.parent())
+        .unwrap_or(project_dir)
+        .to_path_buf()
+}
+
+fn glob_match(skill: &Skill, globs: &[String], file: &Path, base: &Path) -> Option<RuleMatch> {
+    let relative = file.strip_prefix(base).ok()?;
+    let glob = globs.iter().find(|glob| EffectiveRulesService::glob_matches(glob, relative))?;
+
+    Some(RuleMatch {
+        skill: skill.clone(),
+        reason: MatchReason::Glob,
+        pattern: Some(glob.clone()),
+        explanation: format!("{} matches {}", glob, relative.display()),
+    })
+}
+
+fn always(skill: &Skill, explanation: &str) -> Option<RuleMatch> {
+    Some(RuleMatch {
+        skill: skill.clone(),
+        reason: MatchReason::Always,
+        pattern: None,
+        explanation: explanation.to_string(),
+    })
+}
+
+fn described(skill: &Skill) -> Option<RuleMatch> {
+    Some(RuleMatch {
+        skill: skill.clone(),
+        reason: MatchReason::Description,
+        pattern: None,
+        explanation: "Offered to the model by its description, which decides whether to read it".to_string(),
+    })
+}
+
+/// Why a memory file in a folder above the file applies. Files inside an
+/// agent folder, like `.claude/CLAUDE.md`, belong to the folder above it.
+fn folder_explanation(file_path: &str) -> String {
+    let mut folder = Path::new(file_path).parent().unwrap_or(Path::new(""));
+    if folder.file_name().is_some_and(|n| n.to_string_lossy().starts_with('.')) {
+        folder = folder.parent().unwrap_or(folder);
+    }
+    format!("In {}, which contains the file", folder.display())
+}
+
+/// A frontmatter key's globs, which may be a list or comma-separated
+fn frontmatter_list(path: &Path, key: &str) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let Some((lines, _)) = split_frontmatter(&content) else {
+        return Vec::new();
+    };
+
+    fields(&lines).iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, values)| list(values))
+        .unwrap_or_default()
+}
+
+fn list(values: &[&str]) -> Vec<String> {
+    values.iter()
+        .flat_map(|v| unquote(v).trim_start_matches('[').trim_end_matches(']').split(','))
+        .map(|glob| unquote(glob).to_string())
+        .filter(|glob| !glob.is_empty())
+        .collect()
+}
@@ -0,0 +1,210 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use tokio::process::Command;
+use tokio::sync::{Mutex, OwnedMutexGuard};
+use serde::Serialize;
+
+use crate::models::{AgentType, GitSource, DiscoveredSkill, AppError};
+use crate::services::SkillService;
+
+/// One lock per working copy, shared by every service in the process
+static CHECKOUT_LOCKS: OnceLock<std::sync::Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>> = OnceLock::new();
+
+pub struct GitService {
+    checkout_dir: PathBuf,
+}
+
+/// A repository checked out at a specific commit
+#[derive(Debug, Clone)]
+pub struct GitCheckout {
+    pub repo_path: PathBuf,
+    pub commit_sha: String,
+}
+
+/// Skills found inside a git repository
+#[derive(Debug, Clone, Serialize)]
+pub struct GitDiscovery {
+    pub commit_sha: String,
+    pub skills: Vec<DiscoveredSkill>,
+}
+
+impl GitService {
+    pub fn new() -> Self {
+        let checkout_dir = dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ai-skills-aggregator")
+            .join("git");
+
+        Self { checkout_dir }
+    }
+
+    /// Lock the repository's working copy. Checkouts of every ref share it,
+    /// so hold the guard from `checkout` until done reading its files.
+    pub async fn lock(&self, source: &GitSource) -> OwnedMutexGuard<()> {
+        let lock = CHECKOUT_LOCKS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .entry(self.repo_path(source))
+            .or_default()
+            .clone();
+
+        lock.lock_owned().await
+    }
+
+    /// Clone (or fetch) the repository and check out the requested ref
+    pub async fn checkout(&self, source: &GitSource) -> Result<GitCheckout, AppError> {
+        // Sources also come from install records and lockfiles
+        source.validate()?;
+        let repo_path = self.repo_path(source);
+
+        if repo_path.join(".git").exists() {
+            self.run_git(&["fetch", "--tags", "--force", "origin"], Some(&repo_path)).await?;
+        } else {
+            tokio::fs::create_dir_all(&self.checkout_dir)
+                .await
+                .map_err(|e| AppError::IoError(e.to_string()))?;
+
+            let dest = repo_path.to_string_lossy().to_string();
+            self.run_git(&["clone", "--no-checkout", "--", &source.url, &dest], None).await?;
+        }
+
+        let commit_sha = self.resolve_ref(&repo_path, source.git_ref.as_deref()).await?;
+        self.run_git(&["checkout", "--force", "--detach", &commit_sha], Some(&repo_path)).await?;
+
+        Ok(GitCheckout { repo_path, commit_sha })
+    }
+
+    /// Check out a repository and list the skill folders it contains
+    pub async fn discover(&self, source: &GitSource, agent: &AgentType) -> Result<GitDiscovery, AppError> {
+        let _guard = self.lock(source).await;
+        let checkout = self.checkout(source).await?;
+        let root = SkillService::resolve_within(&checkout.repo_path, source.subdirectory.as_deref())?;
+        let skills = SkillService::discover_skill_folders(&checkout.repo_path, &root, agent);
+
+        Ok(GitDiscovery {
+            commit_sha: checkout.commit_sha,
+            skills,
+        })
+    }
+
+    fn repo_path(&self, source: &GitSource) -> PathBuf {
+        self.checkout_dir.join(url_to_dirname(&source.url))
+    }
+
+    /// Get the commit the remote currently points at for the source's ref.
+    /// Returns None when the ref is a pinned commit that cannot move.
+    pub async fn remote_commit(&self, source: &GitSource) -> Result<Option<String>, AppError> {
+        source.validate()?;
+        let git_ref = source.git_ref.as_deref().unwrap_or("HEAD");
+
+        // Branches win over tags, as in `resolve_ref`
+        let names = if git_ref == "HEAD" || git_ref.starts_with("refs/") {
+            vec![git_ref.to_string()]
+        } else {
+            vec![format!("refs/heads/{}", git_ref), format!("refs/tags/{}", git_ref)]
+        };
+        // Annotated tags point at a tag object; the peeled `^{}` line has the
+        // commit that checkouts record. ls-remote only lists it when asked.
+        let patterns: Vec<String> = names.iter()
+            .flat_map(|name| [name.clone(), format!("{}^{{}}", name)])
+            .collect();
+
+        let mut args = vec!["ls-remote", "--", source.url.as_str()];
+        args.extend(patterns.iter().map(String::as_str));
+        let output = self.run_git(&args, None).await?;
+
+        let refs: Vec<(&str, &str)> = output
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .collect();
+        let sha = |name: &str| refs.iter().find(|(_, r)| *r == name).map(|(sha, _)| sha.to_string());
+
+        Ok(patterns
+            .chunks(2)
+            .find_map(|pair| sha(&pair[1]).or_else(|| sha(&pair[0]))))
+    }
+
+    /// One-line summaries (`<short sha> <subject>`) of the commits after
+    /// `from` up to `to`, newest first
+    pub async fn log(&self, checkout: &GitCheckout, from: Option<&str>, to: &str) -> Result<Vec<String>, AppError> {
+        let range = match from {
+            Some(from) => format!("{}..{}", from, to),
+            None => to.to_string(),
+        };
+
+        let output = self.run_git(
+            &["log", "--format=%h %s", "--max-count=100", &range],
+            Some(&checkout.repo_path),
+        ).await?;
+
+        Ok(output.lines().map(String::from).collect())
+    }
+
+    /// Derive a repository name from its URL or path
+    pub fn repo_name(url: &str) -> String {
+        url.trim_end_matches('/')
+            .rsplit(['/', ':'])
+            .next()
+            .unwrap_or(url)
+            .trim_end_matches(".git")
+            .to_string()
+    }
+
+    // Internal helpers
+
+    async fn resolve_ref(&self, repo_path: &Path, git_ref: Option<&str>) -> Result<String, AppError> {
+        let candidates = match git_ref {
+            // Prefer remote branches so fetched updates are picked up
+            Some(r) => vec![format!("origin/{}", r), r.to_string()],
+            None => vec!["origin/HEAD".to_string(), "HEAD".to_string()],
+        };
+
+        for candidate in &candidates {
+            let spec = format!("{}^{{commit}}", candidate);
+            if let Ok(sha) = self.run_git(&["rev-parse", "--verify", "--quiet", &spec], Some(repo_path)).await {
+                return Ok(sha.trim().to_string());
+            }
+        }
+
+        Err(AppError::NotFound(format!(
+            "Git ref not found: {}",
+            git_ref.unwrap_or("HEAD")
+        )))
+    }
+
+    async fn run_git(&self, args: &[&str], cwd: Option<&Path>) -> Result<String, AppError> {
+        let mut command = Command::new("git");
+        command.args(args).env("GIT_TERMINAL_PROMPT", "0");
+        if let Some(dir) = cwd {
+            command.current_dir(dir);
+        }
+
+        let output = command
+            .output()
+            .await
+            .map_err(|e| AppError::IoError(format!("Failed to run git: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(AppError::IoError(format!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+impl Default for GitService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn url_to_dirname(url: &str) -> String {
+    let hash = format!("{:x}", md5::compute(url.as_bytes()));
+    hash[..16].to_string()
+}
@@ -0,0 +1,555 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncWrite, AsyncWriteExt};
+
+use crate::models::{AgentConfig, AgentType, AppError, Skill, SkillKind};
+use crate::services::rule_service::split_frontmatter;
+use crate::services::{ScanIndex, SkillService};
+
+/// MCP protocol versions this server speaks, newest first
+const PROTOCOL_VERSIONS: [&str; 3] = ["2025-06-18", "2025-03-26", "2024-11-05"];
+
+/// How long a scan is reused before the library is scanned again
+const RESCAN_AFTER: Duration = Duration::from_secs(10);
+
+const URI_SCHEME: &str = "skills://";
+
+/// Search results returned when the client doesn't ask for a number
+const DEFAULT_LIMIT: usize = 20;
+
+const INSTRUCTIONS: &str = "Skills, rules, prompts and commands collected from the AI coding agents on this machine. \
+Use search_skills to find one and get_skill to load it; each is also a prompt and a resource.";
+
+// JSON-RPC error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// MCP's code for a resource that doesn't exist
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// Serves the skill library to MCP clients: every skill is a resource and a
+/// prompt, and the `search_skills` and `get_skill` tools find and load them
+pub struct McpLibraryService {
+    configs: Vec<AgentConfig>,
+    index: ScanIndex,
+    index_path: PathBuf,
+    entries: Vec<LibraryEntry>,
+    scanned_at: Option<Instant>,
+}
+
+/// A skill and the names it is published under
+struct LibraryEntry {
+    skill: Skill,
+    /// `<agent>-<name>`, unique across the library
+    prompt: String,
+    /// `skills://<agent>/<name>`; files of a skill folder are below it
+    uri: String,
+}
+
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl McpLibraryService {
+    /// A server for the skills of `configs`, reusing the app's scan cache
+    pub async fn new(configs: Vec<AgentConfig>) -> Self {
+        let index_path = ScanIndex::default_path();
+
+        Self {
+            configs,
+            index: ScanIndex::load(&index_path).await,
+            index_path,
+            entries: Vec::new(),
+            scanned_at: None,
+        }
+    }
+
+    /// Answer newline-delimited JSON-RPC messages from `input` until it closes
+    pub async fn serve<R, W>(&mut self, input: R, mut output: W) -> Result<(), AppError>
+    where
+        R: AsyncBufRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let mut lines = input.lines();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let response = match serde_json::from_str::<Value>(&line) {
+                Ok(Value::Array(batch)) => {
+                    let mut responses = Vec::new();
+                    for message in batch {
+                        responses.extend(self.handle(message).await);
+                    }
+                    (!responses.is_empty()).then_some(Value::Array(responses))
+                }
+                Ok(message) => self.handle(message).await,
+                Err(e) => Some(error_response(Value::Null, RpcError::new(PARSE_ERROR, e.to_string()))),
+            };
+
+            if let Some(response) = response {
+                let mut text = serde_json::to_string(&response)?;
+                text.push('\n');
+                output.write_all(text.as_bytes()).await?;
+                output.flush().await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The response to one JSON-RPC message; None for notifications
+    pub async fn handle(&mut self, message: Value) -> Option<Value> {
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // We never send requests, so there are no responses to expect
+            if message.get("result").is_some() || message.get("error").is_some() {
+                return None;
+            }
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                RpcError::new(INVALID_REQUEST, "Missing method".into()),
+            ));
+        };
+
+        let id = id?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        Some(match self.dispatch(method, &params).await {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(e) => error_response(id, e),
+        })
+    }
+
+    async fn dispatch(&mut self, method: &str, params: &Value) -> Result<Value, RpcError> {
+        match method {
+            "initialize" => return Ok(initialize(params)),
+            "ping" => return Ok(json!({})),
+            _ => {}
+        }
+
+        self.refresh().await;
+
+        match method {
+            "resources/list" => Ok(json!({
+                "resources": self.entries.iter().map(resource).collect::<Vec<_>>(),
+            })),
+            "resources/templates/list" => Ok(json!({ "resourceTemplates": [] })),
+            "resources/read" => {
+                let uri = string_param(params, "uri")?;
+                let (path, content) = self.read(uri)?;
+                Ok(json!({
+                    "contents": [{ "uri": uri, "mimeType": mime_type(&path), "text": content }],
+                }))
+            }
+            "prompts/list" => Ok(json!({
+                "prompts": self.entries.iter().map(prompt).collect::<Vec<_>>(),
+            })),
+            "prompts/get" => {
+                let name = string_param(params, "name")?;
+                let input = params.pointer("/arguments/input").and_then(Value::as_str).unwrap_or("");
+                let entry = self.entries.iter()
+                    .find(|e| e.prompt == name)
+                    .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Unknown prompt: {}", name)))?;
+                let content = entry_content(&entry.skill)
+                    .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+
+                Ok(json!({
+                    "description": entry.skill.description,
+                    "messages": [{
+                        "role": "user",
+                        "content": { "type": "text", "text": prompt_text(&content, input) },
+                    }],
+                }))
+            }
+            "tools/list" => Ok(json!({ "tools": tools() })),
+            "tools/call" => {
+                let name = string_param(params, "name")?;
+                let arguments = params.get("arguments").cloned().unwrap_or(Value::Null);
+                let result = match name {
+                    "search_skills" => self.search_tool(&arguments),
+                    "get_skill" => self.get_tool(&arguments),
+                    _ => return Err(RpcError::new(INVALID_PARAMS, format!("Unknown tool: {}", name))),
+                };
+
+                // Failures are reported to the model rather than as protocol errors
+                Ok(match result {
+                    Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+                    Err(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": true }),
+                })
+            }
+            _ => Err(RpcError::new(METHOD_NOT_FOUND, format!("Method not found: {}", method))),
+        }
+    }
+
+    /// Scan the library again once the last scan is `RESCAN_AFTER` old.
+    /// Unchanged folders come from the scan cache.
+    async fn refresh(&mut self) {
+        if self.scanned_at.is_some_and(|at| at.elapsed() < RESCAN_AFTER) {
+            return;
+        }
+
+        let skills = SkillService::scan_all_skills(&self.configs, &mut self.index, |_| {}).await;
+        if let Err(e) = self.index.save(&self.index_path).await {
+            log::warn!("Failed to save scan index: {}", e);
+        }
+
+        self.entries = library_entries(skills);
+        self.scanned_at = Some(Instant::now());
+    }
+
+    /// Skills matching every word of `query`, best matches first. Names
+    /// count most, then tags, the description and finally the content.
+    fn search(&self, query: &str, agent: Option<&AgentType>, kind: Option<SkillKind>) -> Vec<&LibraryEntry> {
+        let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+
+        let mut matches: Vec<(usize, &LibraryEntry)> = self.entries.iter()
+            .filter(|e| agent.map_or(true, |a| e.skill.agent == *a))
+            .filter(|e| kind.map_or(true, |k| e.skill.kind == k))
+            .filter_map(|e| score(&e.skill, &terms).map(|s| (s, e)))
+            .collect();
+        matches.sort_by(|(a, x), (b, y)| b.cmp(a).then_with(|| x.prompt.cmp(&y.prompt)));
+
+        matches.into_iter().map(|(_, e)| e).collect()
+    }
+
+    fn search_tool(&self, arguments: &Value) -> Result<String, String> {
+        let query = arguments.get("query").and_then(Value::as_str).unwrap_or("");
+        let agent = arguments.get("agent").and_then(Value::as_str).map(AgentType::parse);
+        let kind = match arguments.get("kind").and_then(Value::as_str) {
+            Some(kind) => Some(parse_kind(kind).ok_or_else(|| format!("Unknown kind: {}", kind))?),
+            None => None,
+        };
+        let limit = arguments.get("limit")
+            .and_then(Value::as_u64)
+            .map_or(DEFAULT_LIMIT, |l| l.max(1) as usize);
+
+        let found = self.search(query, agent.as_ref(), kind);
+        if found.is_empty() {
+            return Ok(format!("No skills match \"{}\"", query));
+        }
+
+        let mut text = format!("{} skill(s) found", found.len());
+        if found.len() > limit {
+            text.push_str(&format!(", showing the first {}", limit));
+        }
+        text.push('\n');
+
+        for entry in found.into_iter().take(limit) {
+            let skill = &entry.skill;
+            text.push_str(&format!("\n- {} ({}, {})", entry.prompt, skill.agent, kind_name(skill.kind)));
+            if let Some(description) = &skill.description {
+                text.push_str(&format!(": {}", description));
+            }
+            text.push_str(&format!("\n  {}", entry.uri));
+        }
+
+        Ok(text)
+    }
+
+    fn get_tool(&self, arguments: &Value) -> Result<String, String> {
+        let name = arguments.get("name")
+            .and_then(Value::as_str)
+            .ok_or("Missing argument: name")?;
+
+        // A skill's file, by URI
+        if name.starts_with(URI_SCHEME) && self.entries.iter().all(|e| e.uri != name) {
+            return self.read(name).map(|(_, content)| content).map_err(|e| e.message);
+        }
+
+        let entry = self.entries.iter()
+            .find(|e| e.prompt == name || e.uri == name)
+            .ok_or_else(|| format!("No skill named {}; use search_skills to find one", name))?;
+        let mut text = entry_content(&entry.skill).map_err(|e| e.to_string())?;
+
+        let others = other_files(entry);
+        if !others.is_empty() {
+            text.truncate(text.trim_end().len());
+            text.push_str("\n\n---\nOther files in this skill, readable with get_skill:\n");
+            for (uri, _) in others {
+                text.push_str(&format!("- {}\n", uri));
+            }
+        }
+
+        Ok(text)
+    }
+
+    /// A resource's file path and content. Only the files of skills in the
+    /// library can be read.
+    fn read(&self, uri: &str) -> Result<(PathBuf, String), RpcError> {
+        let not_found = || RpcError::new(RESOURCE_NOT_FOUND, format!("Resource not found: {}", uri));
+
+        let entry = self.entries.iter()
+            .filter(|e| uri == e.uri || uri.strip_prefix(e.uri.as_str()).is_some_and(|rest| rest.starts_with('/')))
+            .max_by_key(|e| e.uri.len())
+            .ok_or_else(not_found)?;
+
+        let path = if uri == entry.uri {
+            entry_path(&entry.skill).ok_or_else(not_found)?
+        } else {
+            other_files(entry).into_iter()
+                .find(|(file_uri, _)| file_uri == uri)
+                .map(|(_, path)| path)
+                .ok_or_else(not_found)?
+        };
+
+        let content = if uri == entry.uri {
+            entry_content(&entry.skill)
+        } else {
+            SkillService::read_content(&path.to_string_lossy())
+        };
+
+        content
+            .map(|content| (path, content))
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))
+    }
+}
+
+impl RpcError {
+    fn new(code: i64, message: String) -> Self {
+        Self { code, message }
+    }
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = PROTOCOL_VERSIONS.iter()
+        .find(|v| Some(**v) == requested)
+        .unwrap_or(&PROTOCOL_VERSIONS[0]);
+
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "resources": {}, "prompts": {}, "tools": {} },
+        "serverInfo": { "name": "ai-skills-aggregator", "version": env!("CARGO_PKG_VERSION") },
+        "instructions": INSTRUCTIONS,
+    })
+}
+
+fn error_response(id: Value, error: RpcError) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": error.code, "message": error.message },
+    })
+}
+
+fn string_param<'a>(params: &'a Value, key: &str) -> Result<&'a str, RpcError> {
+    params.get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| RpcError::new(INVALID_PARAMS, format!("Missing parameter: {}", key)))
+}
+
+/// Name every skill, in a stable order so names survive rescans
+fn library_entries(mut skills: Vec<Skill>) -> Vec<LibraryEntry> {
+    skills.sort_by(|a, b| {
+        slug(&a.agent.to_string())
+            .cmp(&slug(&b.agent.to_string()))
+            .then_with(|| a.name.cmp(&b.name))
+            .then_with(|| a.folder_path.cmp(&b.folder_path))
+    });
+
+    let mut taken = HashSet::new();
+    skills.into_iter()
+        .map(|skill| {
+            let agent = slug(&skill.agent.to_string());
+            let base = slug(&skill.name);
+            let mut name = base.clone();
+            let mut n = 2;
+            while !taken.insert(format!("{}/{}", agent, name)) {
+                name = format!("{}-{}", base, n);
+                n += 1;
+            }
+
+            LibraryEntry {
+                prompt: format!("{}-{}", agent, name),
+                uri: format!("{}{}/{}", URI_SCHEME, agent, name),
+                skill,
+            }
+        })
+        .collect()
+}
+
+/// Lowercase letters, digits and single dashes
+fn slug(name: &str) -> String {
+    let mut slug = String::new();
+    for c in name.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() { "skill".to_string() } else { slug.to_string() }
+}
+
+fn resource(entry: &LibraryEntry) -> Value {
+    json!({
+        "uri": entry.uri,
+        "name": entry.prompt,
+        "title": entry.skill.name,
+        "description": entry.skill.description,
+        "mimeType": entry_path(&entry.skill).map(|p| mime_type(&p)),
+    })
+}
+
+fn prompt(entry: &LibraryEntry) -> Value {
+    json!({
+        "name": entry.prompt,
+        "title": entry.skill.name,
+        "description": entry.skill.description,
+        "arguments": [{
+            "name": "input",
+            "description": "What to apply the prompt to; fills in $ARGUMENTS or is added at the end",
+            "required": false,
+        }],
+    })
+}
+
+fn tools() -> Value {
+    json!([
+        {
+            "name": "search_skills",
+            "title": "Search skills",
+            "description": "Find skills, rules, prompts and commands in the library by keywords",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "query": { "type": "string", "description": "Words that must all appear in the skill; empty lists everything" },
+                    "agent": { "type": "string", "description": "Only skills of this agent, e.g. claude or cursor" },
+                    "kind": {
+                        "type": "string",
+                        "enum": ["Skill", "Subagent", "SlashCommand", "Memory", "Rule", "OutputStyle"],
+                    },
+                    "limit": { "type": "integer", "minimum": 1, "description": "Results to return (default 20)" },
+                },
+                "required": ["query"],
+            },
+        },
+        {
+            "name": "get_skill",
+            "title": "Get skill",
+            "description": "Load a skill's content by the name or URI search_skills returned",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string", "description": "Name such as claude-code-review, or a skills:// URI" },
+                },
+                "required": ["name"],
+            },
+        },
+    ])
+}
+
+fn kind_name(kind: SkillKind) -> String {
+    serde_json::to_value(kind)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn parse_kind(kind: &str) -> Option<SkillKind> {
+    [
+        SkillKind::Skill,
+        SkillKind::Subagent,
+        SkillKind::SlashCommand,
+        SkillKind::Memory,
+        SkillKind::Rule,
+        SkillKind::OutputStyle,
+    ]
+    .into_iter()
+    .find(|k| kind_name(*k).eq_ignore_ascii_case(kind))
+}
+
+/// Weighted count of where the terms appear; None unless every term does
+fn score(skill: &Skill, terms: &[String]) -> Option<usize> {
+    let name = skill.name.to_lowercase();
+    let tags = skill.tags.join(" ").to_lowercase();
+    let description = skill.description.as_deref().unwrap_or("").to_lowercase();
+    let mut content: Option<String> = None;
+
+    let mut total = 0;
+    for term in terms {
+        let weight = if name.contains(term.as_str()) {
+            8
+        } else if tags.contains(term.as_str()) {
+            4
+        } else if description.contains(term.as_str()) {
+            2
+        } else {
+            // Only read the file when the metadata doesn't match
+            let content = content.get_or_insert_with(|| {
+                entry_content(skill).map(|c| c.to_lowercase()).unwrap_or_default()
+            });
+            if content.contains(term.as_str()) { 1 } else { return None }
+        };
+        total += weight;
+    }
+
+    Some(total)
+}
+
+fn entry_path(skill: &Skill) -> Option<PathBuf> {
+    skill.entry_file.as_ref()
+        .or_else(|| skill.files.first().map(|f| &f.file_path))
+        .map(PathBuf::from)
+}
+
+fn entry_content(skill: &Skill) -> Result<String, AppError> {
+    let path = entry_path(skill).ok_or_else(|| AppError::FileNotFound(skill.folder_path.clone()))?;
+    SkillService::read_content(&path.to_string_lossy())
+}
+
+/// URIs and paths of a skill folder's files other than its entry file
+fn other_files(entry: &LibraryEntry) -> Vec<(String, PathBuf)> {
+    if !entry.skill.is_folder {
+        return Vec::new();
+    }
+
+    let folder = Path::new(&entry.skill.folder_path);
+    let entry_path = entry_path(&entry.skill);
+    entry.skill.files.iter()
+        .map(|f| PathBuf::from(&f.file_path))
+        .filter(|path| Some(path) != entry_path.as_ref())
+        .filter_map(|path| {
+            let relative = path.strip_prefix(folder).ok()?;
+            let relative: Vec<String> = relative.iter().map(|c| c.to_string_lossy().to_string()).collect();
+            Some((format!("{}/{}", entry.uri, relative.join("/")), path))
+        })
+        .collect()
+}
+
+/// The text a prompt sends: the skill without its frontmatter, with the
+/// user's input in place of `$ARGUMENTS` or after it
+fn prompt_text(content: &str, input: &str) -> String {
+    let body = split_frontmatter(content).map_or(content, |(_, body)| body).trim();
+
+    if body.contains("$ARGUMENTS") {
+        body.replace("$ARGUMENTS", input)
+    } else if input.trim().is_empty() {
+        body.to_string()
+    } else {
+        format!("{}\n\n{}", body, input.trim())
+    }
+}
+
+fn mime_type(path: &Path) -> &'static str {
+    match path.extension().and_then(|e| e.to_str()).unwrap_or("") {
+        "md" | "mdc" | "markdown" => "text/markdown",
+        "json" => "application/json",
+        "yaml" | "yml" => "application/yaml",
+        "toml" => "application/toml",
+        "py" => "text/x-python",
+        "sh" => "text/x-shellscript",
+        _ => "text/plain",
+    }
+}
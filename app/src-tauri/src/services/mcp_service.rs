@@ -0,0 +1,1042 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use glob::glob;
+use serde_json::{Map, Value};
+
+use crate::models::{AgentConfig, AgentType, AppError, McpScope, McpServer, McpServerEntry, McpTransport};
+use crate::services::backup_service::BackupService;
+use crate::services::continue_service::{json_span, skip_string, skip_value, skip_whitespace, Segment};
+use crate::services::template_service::yaml_string;
+
+/// Keys of a JSON server entry managed through `McpServer`. Others, like
+/// Cline's `autoApprove`, are kept when a server is edited.
+const JSON_KEYS: [&str; 11] = [
+    "type", "transport", "command", "args", "env", "url", "serverUrl", "httpUrl", "headers", "disabled", "enabled",
+];
+
+/// Keys of a Codex server table managed through `McpServer`
+const TOML_KEYS: [&str; 6] = ["command", "args", "env", "url", "http_headers", "enabled"];
+
+/// How a config file lays out its servers
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Dialect {
+    Claude,
+    Cursor,
+    Windsurf,
+    Gemini,
+    Cline,
+    /// `.vscode/mcp.json`, used by Copilot in VS Code
+    VsCode,
+    /// `~/.copilot/mcp-config.json`, used by the Copilot CLI
+    CopilotCli,
+    /// Block files in `mcpServers/`, and the list in `config.yaml`
+    Continue,
+    /// `[mcp_servers.<name>]` tables in `config.toml`
+    Codex,
+}
+
+impl Dialect {
+    fn of(agent: &AgentType, path: &Path) -> Option<Self> {
+        let dialect = match agent {
+            AgentType::Claude => Dialect::Claude,
+            AgentType::Cursor => Dialect::Cursor,
+            AgentType::Windsurf => Dialect::Windsurf,
+            AgentType::Gemini => Dialect::Gemini,
+            AgentType::Cline => Dialect::Cline,
+            AgentType::Copilot if path.file_name().is_some_and(|n| n == "mcp.json") => Dialect::VsCode,
+            AgentType::Copilot => Dialect::CopilotCli,
+            AgentType::ContinueDev => Dialect::Continue,
+            AgentType::Codex => Dialect::Codex,
+            _ => return None,
+        };
+
+        Some(dialect)
+    }
+
+    fn servers_key(self) -> &'static str {
+        match self {
+            Dialect::VsCode => "servers",
+            Dialect::Codex => "mcp_servers",
+            _ => "mcpServers",
+        }
+    }
+}
+
+/// MCP server definitions in each agent's own config files
+pub struct McpService {
+    backup: BackupService,
+}
+
+impl McpService {
+    pub fn new() -> Self {
+        Self {
+            backup: BackupService::new(),
+        }
+    }
+
+    /// Config files an agent reads MCP servers from, whether they exist or not.
+    /// Continue's are the main config and the block files that exist.
+    pub fn config_files(config: &AgentConfig, project_dir: Option<&Path>) -> Vec<(McpScope, PathBuf)> {
+        if config.agent != AgentType::ContinueDev {
+            return [McpScope::User, McpScope::Project].into_iter()
+                .filter_map(|scope| Some((scope, Self::target_path(config, scope, project_dir, "")?)))
+                .collect();
+        }
+
+        let config_dir = Path::new(&config.config_dir);
+        let mut files = vec![(McpScope::User, config_dir.join("config.yaml"))];
+        files.extend(block_files(&config_dir.join("mcpServers")).into_iter().map(|p| (McpScope::User, p)));
+        if let Some(project_dir) = project_dir {
+            let dir = project_dir.join(".continue").join("mcpServers");
+            files.extend(block_files(&dir).into_iter().map(|p| (McpScope::Project, p)));
+        }
+        files
+    }
+
+    /// The file a new server called `name` is written to, `None` when the
+    /// agent has no MCP config at that scope
+    pub fn target_path(config: &AgentConfig, scope: McpScope, project_dir: Option<&Path>, name: &str) -> Option<PathBuf> {
+        let config_dir = Path::new(&config.config_dir);
+        let block_file = format!("{}.yaml", name);
+
+        let path = match (scope, &config.agent) {
+            // `~/.claude.json` sits next to `~/.claude`
+            (McpScope::User, AgentType::Claude) => config_dir.parent()?.join(".claude.json"),
+            (McpScope::User, AgentType::Cursor) => config_dir.join("mcp.json"),
+            (McpScope::User, AgentType::Windsurf) => config_dir.join("mcp_config.json"),
+            (McpScope::User, AgentType::Gemini) => config_dir.join("settings.json"),
+            (McpScope::User, AgentType::Copilot) => config_dir.join("mcp-config.json"),
+            (McpScope::User, AgentType::Codex) => config_dir.join("config.toml"),
+            (McpScope::User, AgentType::Cline) => cline_settings_path()?,
+            (McpScope::User, AgentType::ContinueDev) => config_dir.join("mcpServers").join(block_file),
+            (McpScope::Project, AgentType::Claude) => project_dir?.join(".mcp.json"),
+            (McpScope::Project, AgentType::Cursor) => project_dir?.join(".cursor").join("mcp.json"),
+            (McpScope::Project, AgentType::Gemini) => project_dir?.join(".gemini").join("settings.json"),
+            (McpScope::Project, AgentType::Copilot) => project_dir?.join(".vscode").join("mcp.json"),
+            (McpScope::Project, AgentType::ContinueDev) => {
+                project_dir?.join(".continue").join("mcpServers").join(block_file)
+            }
+            _ => return None,
+        };
+
+        Some(path)
+    }
+
+    /// Servers of every enabled agent, at user scope and in `project_dir`
+    pub fn list_servers(configs: &[AgentConfig], project_dir: Option<&Path>) -> Vec<McpServerEntry> {
+        let mut entries = Vec::new();
+
+        for config in configs.iter().filter(|c| c.enabled) {
+            for (scope, path) in Self::config_files(config, project_dir) {
+                if !path.is_file() {
+                    continue;
+                }
+
+                let servers = match Self::read_servers(&config.agent, &path) {
+                    Ok(servers) => servers,
+                    Err(e) => {
+                        log::warn!("Failed to read MCP servers from {}: {}", path.display(), e);
+                        continue;
+                    }
+                };
+
+                let editable = config.agent != AgentType::ContinueDev || (is_block_file(&path) && servers.len() == 1);
+                entries.extend(servers.into_iter().map(|server| McpServerEntry {
+                    server,
+                    agent: config.agent.clone(),
+                    scope,
+                    config_path: path.to_string_lossy().to_string(),
+                    editable,
+                }));
+            }
+        }
+
+        entries
+    }
+
+    /// Servers in one of an agent's config files; empty when it doesn't exist
+    pub fn read_servers(agent: &AgentType, path: &Path) -> Result<Vec<McpServer>, AppError> {
+        let dialect = Dialect::of(agent, path).ok_or_else(|| unsupported(agent))?;
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        parse_servers(dialect, &fs::read_to_string(path)?)
+    }
+
+    /// Whether an agent reads MCP servers from a config file at all
+    pub fn supports(agent: &AgentType) -> bool {
+        !matches!(agent, AgentType::Aider | AgentType::Custom(_))
+    }
+
+    /// Problems that would stop an agent from starting a server. Empty when
+    /// the server is valid.
+    pub fn validate(agent: &AgentType, server: &McpServer) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let name = server.name.trim();
+        if name.is_empty() {
+            problems.push("Name is required".to_string());
+        } else if !name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')) {
+            problems.push("Names can only contain letters, digits, '-', '_' and '.'".to_string());
+        }
+
+        let has_url = server.url.as_deref().is_some_and(|u| !u.trim().is_empty());
+        match server.transport {
+            McpTransport::Stdio => {
+                if server.command.as_deref().map_or(true, |c| c.trim().is_empty()) {
+                    problems.push("Stdio servers need a command".to_string());
+                }
+                if has_url {
+                    problems.push("Stdio servers don't take a URL".to_string());
+                }
+            }
+            McpTransport::Http | McpTransport::Sse => {
+                match server.url.as_deref().map(str::trim) {
+                    Some(url) if url.starts_with("http://") || url.starts_with("https://") => {}
+                    Some(url) if !url.is_empty() => problems.push("URL must start with http:// or https://".to_string()),
+                    _ => problems.push("Remote servers need a URL".to_string()),
+                }
+                if server.command.is_some() {
+                    problems.push("Remote servers don't take a command".to_string());
+                }
+            }
+        }
+
+        if server.env.keys().chain(server.headers.keys()).any(|k| k.trim().is_empty()) {
+            problems.push("Variable and header names can't be empty".to_string());
+        }
+
+        if !Self::supports(agent) {
+            problems.push(unsupported(agent).to_string());
+        } else if *agent == AgentType::Codex && server.transport == McpTransport::Sse {
+            problems.push("Codex doesn't support SSE servers".to_string());
+        }
+        if server.disabled && !matches!(agent, AgentType::Windsurf | AgentType::Cline | AgentType::Codex) {
+            problems.push(format!("{} can't disable a server in its config; remove it instead", agent));
+        }
+
+        problems
+    }
+
+    /// Add a server to a config file, or replace `previous_name` with it.
+    /// Only the server's entry changes; the file is backed up first.
+    pub async fn save_server(
+        &self,
+        agent: &AgentType,
+        config_path: &Path,
+        server: &McpServer,
+        previous_name: Option<&str>,
+    ) -> Result<McpServer, AppError> {
+        let problems = Self::validate(agent, server);
+        if !problems.is_empty() {
+            return Err(AppError::ParseError(problems.join("; ")));
+        }
+
+        let dialect = Dialect::of(agent, config_path).ok_or_else(|| unsupported(agent))?;
+        let text = read_text(config_path)?;
+        let servers = parse_servers(dialect, &text)?;
+
+        if let Some(previous) = previous_name {
+            if !servers.iter().any(|s| s.name == previous) {
+                return Err(AppError::NotFound(format!("MCP server {}", previous)));
+            }
+        }
+        if previous_name != Some(server.name.as_str()) && servers.iter().any(|s| s.name == server.name) {
+            return Err(AppError::AlreadyExists(format!("MCP server {} in {}", server.name, config_path.display())));
+        }
+
+        let updated = match dialect {
+            Dialect::Continue => {
+                if !is_block_file(config_path) || servers.len() > usize::from(previous_name.is_some()) {
+                    return Err(not_editable(config_path));
+                }
+                Some(continue_block(server))
+            }
+            Dialect::Codex => toml_edit(&text, previous_name, Some(server)),
+            _ => json_edit(dialect, &text, previous_name, Some(server)),
+        };
+
+        // Only write when the edit reads back as intended
+        let updated = updated
+            .filter(|updated| {
+                parse_servers(dialect, updated).is_ok_and(|written| {
+                    written.iter().any(|s| s == server)
+                        && previous_name.map_or(true, |p| p == server.name || written.iter().all(|s| s.name != p))
+                })
+            })
+            .ok_or_else(|| not_editable(config_path))?;
+
+        self.write(config_path, Some(updated)).await?;
+        Ok(server.clone())
+    }
+
+    /// Remove a server from a config file, backing the file up first.
+    /// Continue block files holding only this server are deleted.
+    pub async fn remove_server(&self, agent: &AgentType, config_path: &Path, name: &str) -> Result<(), AppError> {
+        let dialect = Dialect::of(agent, config_path).ok_or_else(|| unsupported(agent))?;
+        let text = read_text(config_path)?;
+        let servers = parse_servers(dialect, &text)?;
+        if !servers.iter().any(|s| s.name == name) {
+            return Err(AppError::NotFound(format!("MCP server {}", name)));
+        }
+
+        let updated = match dialect {
+            Dialect::Continue if is_block_file(config_path) && servers.len() == 1 => {
+                return self.write(config_path, None).await;
+            }
+            Dialect::Continue => None,
+            Dialect::Codex => toml_edit(&text, Some(name), None),
+            _ => json_edit(dialect, &text, Some(name), None),
+        };
+
+        let updated = updated
+            .filter(|updated| {
+                parse_servers(dialect, updated).is_ok_and(|written| written.iter().all(|s| s.name != name))
+            })
+            .ok_or_else(|| not_editable(config_path))?;
+
+        self.write(config_path, Some(updated)).await
+    }
+
+    /// Write a server into each agent's config at `scope`, in that agent's
+    /// own format. Every target is checked before any file is written.
+    pub async fn copy_server(
+        &self,
+        server: &McpServer,
+        agents: &[AgentType],
+        scope: McpScope,
+        configs: &[AgentConfig],
+        project_dir: Option<&Path>,
+        overwrite: bool,
+    ) -> Result<Vec<McpServerEntry>, AppError> {
+        let mut targets = Vec::new();
+
+        for agent in agents {
+            let config = configs.iter()
+                .find(|c| c.agent == *agent)
+                .ok_or_else(|| AppError::NotFound(format!("Agent config not found: {}", agent)))?;
+            let path = Self::target_path(config, scope, project_dir, &server.name)
+                .ok_or_else(|| AppError::InvalidPath(format!("{} has no MCP config for this scope", agent)))?;
+
+            let problems = Self::validate(agent, server);
+            if !problems.is_empty() {
+                return Err(AppError::ParseError(format!("{}: {}", agent, problems.join("; "))));
+            }
+
+            let exists = Self::read_servers(agent, &path)?.iter().any(|s| s.name == server.name);
+            if exists && !overwrite {
+                return Err(AppError::AlreadyExists(format!("MCP server {} in {}", server.name, path.display())));
+            }
+
+            targets.push((agent, path, exists));
+        }
+
+        let mut entries = Vec::new();
+        for (agent, path, exists) in targets {
+            let previous_name = exists.then_some(server.name.as_str());
+            self.save_server(agent, &path, server, previous_name).await?;
+
+            entries.push(McpServerEntry {
+                server: server.clone(),
+                agent: agent.clone(),
+                scope,
+                config_path: path.to_string_lossy().to_string(),
+                editable: true,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    async fn write(&self, path: &Path, content: Option<String>) -> Result<(), AppError> {
+        if path.exists() {
+            self.backup.backup_file(&path.to_string_lossy()).await?;
+        }
+
+        match content {
+            Some(content) => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, content)?;
+            }
+            None => fs::remove_file(path)?,
+        }
+        Ok(())
+    }
+}
+
+impl Default for McpService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn unsupported(agent: &AgentType) -> AppError {
+    AppError::InvalidPath(format!("{} doesn't support MCP servers", agent))
+}
+
+fn not_editable(path: &Path) -> AppError {
+    AppError::ParseError(format!("Cannot update MCP servers in {} in place; edit it directly", path.display()))
+}
+
+fn read_text(path: &Path) -> Result<String, AppError> {
+    if path.exists() {
+        Ok(fs::read_to_string(path)?)
+    } else {
+        Ok(String::new())
+    }
+}
+
+/// Cline keeps its settings in VS Code's extension storage
+fn cline_settings_path() -> Option<PathBuf> {
+    Some(dirs::config_dir()?
+        .join("Code")
+        .join("User")
+        .join("globalStorage")
+        .join("saoudrizwan.claude-dev")
+        .join("settings")
+        .join("cline_mcp_settings.json"))
+}
+
+fn block_files(dir: &Path) -> Vec<PathBuf> {
+    ["yaml", "yml"].iter()
+        .filter_map(|ext| glob(&dir.join(format!("*.{}", ext)).to_string_lossy()).ok())
+        .flat_map(|entries| entries.flatten())
+        .collect()
+}
+
+fn is_block_file(path: &Path) -> bool {
+    path.parent().and_then(|p| p.file_name()).is_some_and(|n| n == "mcpServers")
+}
+
+fn parse_servers(dialect: Dialect, text: &str) -> Result<Vec<McpServer>, AppError> {
+    if text.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    match dialect {
+        Dialect::Codex => {
+            let config: toml::Table = toml::from_str(text).map_err(|e| AppError::ParseError(e.to_string()))?;
+            Ok(config.get(dialect.servers_key())
+                .and_then(toml::Value::as_table)
+                .map(|servers| servers.iter().map(|(name, value)| server_from_toml(name, value)).collect())
+                .unwrap_or_default())
+        }
+        Dialect::Continue => {
+            let config: serde_yaml::Value = serde_yaml::from_str(text)?;
+            let items = config.get(dialect.servers_key()).and_then(serde_yaml::Value::as_sequence);
+            Ok(items.into_iter()
+                .flatten()
+                .filter_map(|item| serde_json::to_value(item).ok())
+                .map(|item| {
+                    let name = item.get("name").and_then(Value::as_str).unwrap_or_default().to_string();
+                    server_from_json(dialect, name, &item)
+                })
+                .collect())
+        }
+        _ => {
+            let config: Value = serde_json::from_str(text)?;
+            Ok(config.get(dialect.servers_key())
+                .and_then(Value::as_object)
+                .map(|servers| servers.iter()
+                    .map(|(name, value)| server_from_json(dialect, name.clone(), value))
+                    .collect())
+                .unwrap_or_default())
+        }
+    }
+}
+
+fn server_from_json(dialect: Dialect, name: String, value: &Value) -> McpServer {
+    let text = |key: &str| value.get(key).and_then(Value::as_str).map(str::to_string);
+    let url = text("url").or_else(|| text("serverUrl")).or_else(|| text("httpUrl"));
+
+    let transport = match text("type").or_else(|| text("transport")).as_deref() {
+        Some("sse") => McpTransport::Sse,
+        Some("http" | "streamableHttp" | "streamable-http") => McpTransport::Http,
+        Some("stdio" | "local") => McpTransport::Stdio,
+        _ if value.get("command").is_some() => McpTransport::Stdio,
+        _ if value.get("httpUrl").is_some() => McpTransport::Http,
+        // Gemini reads `url` as an SSE endpoint
+        _ if dialect == Dialect::Gemini && url.is_some() => McpTransport::Sse,
+        _ if url.as_deref().is_some_and(|u| u.trim_end_matches('/').ends_with("/sse")) => McpTransport::Sse,
+        _ if url.is_some() => McpTransport::Http,
+        _ => McpTransport::Stdio,
+    };
+
+    // Continue nests headers in `requestOptions`
+    let headers = value.get("headers").or_else(|| value.pointer("/requestOptions/headers"));
+
+    McpServer {
+        name,
+        transport,
+        command: text("command"),
+        args: value.get("args")
+            .and_then(Value::as_array)
+            .map(|args| args.iter().map(json_text).collect())
+            .unwrap_or_default(),
+        env: json_map(value.get("env")),
+        url,
+        headers: json_map(headers),
+        disabled: value.get("disabled") == Some(&Value::Bool(true)) || value.get("enabled") == Some(&Value::Bool(false)),
+    }
+}
+
+/// A server entry in an agent's JSON dialect, keeping any keys of `existing`
+/// that `McpServer` doesn't manage
+fn server_to_json(dialect: Dialect, server: &McpServer, existing: Option<&Value>) -> Value {
+    let mut entry = existing.and_then(Value::as_object).cloned().unwrap_or_default();
+    for key in JSON_KEYS {
+        entry.remove(key);
+    }
+
+    match server.transport {
+        McpTransport::Stdio => {
+            match dialect {
+                Dialect::Claude | Dialect::VsCode => {
+                    entry.insert("type".into(), "stdio".into());
+                }
+                Dialect::CopilotCli => {
+                    entry.insert("type".into(), "local".into());
+                }
+                _ => {}
+            }
+            entry.insert("command".into(), server.command.clone().unwrap_or_default().into());
+            if !server.args.is_empty() {
+                entry.insert("args".into(), server.args.clone().into());
+            }
+            if !server.env.is_empty() {
+                entry.insert("env".into(), string_map(&server.env));
+            }
+        }
+        McpTransport::Http | McpTransport::Sse => {
+            let sse = server.transport == McpTransport::Sse;
+            let (kind, url_key) = match dialect {
+                Dialect::Claude | Dialect::VsCode | Dialect::CopilotCli => (Some(if sse { "sse" } else { "http" }), "url"),
+                Dialect::Cline => (Some(if sse { "sse" } else { "streamableHttp" }), "url"),
+                Dialect::Windsurf => (None, "serverUrl"),
+                Dialect::Gemini => (None, if sse { "url" } else { "httpUrl" }),
+                // Cursor tells the two apart by the URL unless told otherwise
+                _ => (sse.then_some("sse"), "url"),
+            };
+
+            if let Some(kind) = kind {
+                entry.insert("type".into(), kind.into());
+            }
+            entry.insert(url_key.into(), server.url.clone().unwrap_or_default().into());
+            if !server.headers.is_empty() {
+                entry.insert("headers".into(), string_map(&server.headers));
+            }
+        }
+    }
+
+    if server.disabled {
+        entry.insert("disabled".into(), true.into());
+    }
+    // The Copilot CLI only exposes the tools it is told to
+    if dialect == Dialect::CopilotCli && !entry.contains_key("tools") {
+        entry.insert("tools".into(), vec!["*"].into());
+    }
+
+    Value::Object(entry)
+}
+
+fn json_text(value: &Value) -> String {
+    value.as_str().map_or_else(|| value.to_string(), str::to_string)
+}
+
+fn json_map(value: Option<&Value>) -> BTreeMap<String, String> {
+    value.and_then(Value::as_object)
+        .map(|map| map.iter().map(|(k, v)| (k.clone(), json_text(v))).collect())
+        .unwrap_or_default()
+}
+
+fn string_map(map: &BTreeMap<String, String>) -> Value {
+    Value::Object(map.iter().map(|(k, v)| (k.clone(), Value::String(v.clone()))).collect::<Map<_, _>>())
+}
+
+/// A member of a JSON object: where its key starts and where its value is
+struct Member {
+    key: String,
+    start: usize,
+    value: Range<usize>,
+}
+
+/// Remove `previous` from a JSON config and/or add `server`, touching only
+/// the server's entry. The rest of the file keeps its formatting.
+fn json_edit(dialect: Dialect, text: &str, previous: Option<&str>, server: Option<&McpServer>) -> Option<String> {
+    // New files are written pretty-printed, not as a one-line object
+    let mut text = if text.trim().is_empty() { "{\n}\n".to_string() } else { text.to_string() };
+    let key = dialect.servers_key();
+
+    let config: Value = serde_json::from_str(&text).ok()?;
+    let existing = previous
+        .or(server.map(|s| s.name.as_str()))
+        .and_then(|name| config.get(key)?.get(name))
+        .cloned();
+
+    if let Some(previous) = previous.filter(|p| server.map_or(true, |s| s.name != *p)) {
+        let start = json_span(&text, &[Segment::Key(key)])?.start;
+        text = json_remove(&text, start, previous)?;
+    }
+
+    if let Some(server) = server {
+        if json_span(&text, &[Segment::Key(key)]).is_none() {
+            let root = skip_whitespace(text.as_bytes(), 0);
+            text = json_set(&text, root, key, &Value::Object(Map::new()))?;
+        }
+        let start = json_span(&text, &[Segment::Key(key)])?.start;
+        let value = server_to_json(dialect, server, existing.as_ref());
+        text = json_set(&text, start, &server.name, &value)?;
+    }
+
+    Some(text)
+}
+
+fn json_members(text: &str, object_start: usize) -> Option<Vec<Member>> {
+    let bytes = text.as_bytes();
+    if bytes.get(object_start)? != &b'{' {
+        return None;
+    }
+
+    let mut members = Vec::new();
+    let mut pos = skip_whitespace(bytes, object_start + 1);
+    if bytes.get(pos)? == &b'}' {
+        return Some(members);
+    }
+
+    loop {
+        let key_end = skip_string(bytes, pos)?;
+        let key: String = serde_json::from_str(&text[pos..key_end]).ok()?;
+        let colon = skip_whitespace(bytes, key_end);
+        if bytes.get(colon)? != &b':' {
+            return None;
+        }
+        let value_start = skip_whitespace(bytes, colon + 1);
+        let value_end = skip_value(bytes, value_start)?;
+        members.push(Member { key, start: pos, value: value_start..value_end });
+
+        pos = skip_whitespace(bytes, value_end);
+        match bytes.get(pos)? {
+            b',' => pos = skip_whitespace(bytes, pos + 1),
+            b'}' => return Some(members),
+            _ => return None,
+        }
+    }
+}
+
+/// Set `key` of the object at `object_start`, replacing its value or adding
+/// it as the first member
+fn json_set(text: &str, object_start: usize, key: &str, value: &Value) -> Option<String> {
+    let members = json_members(text, object_start)?;
+
+    // Objects written on one line stay on one line
+    let one_line = |member: &Member| !text[object_start..member.start].contains('\n');
+
+    if let Some(member) = members.iter().find(|m| m.key == key) {
+        let value = if one_line(member) {
+            serde_json::to_string(value).ok()?
+        } else {
+            render_json(value, line_indent(text, member.start))
+        };
+        return Some(format!("{}{}{}", &text[..member.value.start], value, &text[member.value.end..]));
+    }
+
+    let key = serde_json::to_string(key).ok()?;
+    match members.first() {
+        Some(first) if one_line(first) => {
+            let member = format!("{}: {}, ", key, serde_json::to_string(value).ok()?);
+            Some(format!("{}{}{}", &text[..first.start], member, &text[first.start..]))
+        }
+        Some(first) => {
+            let indent = line_indent(text, first.start);
+            let member = format!("{}: {},\n{}", key, render_json(value, indent), indent);
+            Some(format!("{}{}{}", &text[..first.start], member, &text[first.start..]))
+        }
+        None if !text.trim_end().contains('\n') => {
+            let close = skip_whitespace(text.as_bytes(), object_start + 1);
+            let member = format!("{}: {}", key, serde_json::to_string(value).ok()?);
+            Some(format!("{}{}{}", &text[..=object_start], member, &text[close..]))
+        }
+        None => {
+            let outer = line_indent(text, object_start);
+            let indent = format!("{}  ", outer);
+            let close = skip_whitespace(text.as_bytes(), object_start + 1);
+            Some(format!(
+                "{}\n{}{}: {}\n{}{}",
+                &text[..=object_start],
+                indent,
+                key,
+                render_json(value, &indent),
+                outer,
+                &text[close..]
+            ))
+        }
+    }
+}
+
+/// Remove `key` and its separating comma from the object at `object_start`
+fn json_remove(text: &str, object_start: usize, key: &str) -> Option<String> {
+    let members = json_members(text, object_start)?;
+    let index = members.iter().position(|m| m.key == key)?;
+
+    let range = if members.len() == 1 {
+        object_start + 1..skip_whitespace(text.as_bytes(), members[0].value.end)
+    } else if index + 1 < members.len() {
+        members[index].start..members[index + 1].start
+    } else {
+        members[index - 1].value.end..members[index].value.end
+    };
+
+    Some(format!("{}{}", &text[..range.start], &text[range.end..]))
+}
+
+/// Leading whitespace of the line containing `pos`
+fn line_indent(text: &str, pos: usize) -> &str {
+    let line = &text[text[..pos].rfind('\n').map_or(0, |i| i + 1)..];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+fn render_json(value: &Value, indent: &str) -> String {
+    serde_json::to_string_pretty(value)
+        .unwrap_or_default()
+        .replace('\n', &format!("\n{}", indent))
+}
+
+fn server_from_toml(name: &str, value: &toml::Value) -> McpServer {
+    let text = |key: &str| value.get(key).and_then(toml::Value::as_str).map(str::to_string);
+    let strings = |key: &str| -> BTreeMap<String, String> {
+        value.get(key)
+            .and_then(toml::Value::as_table)
+            .map(|table| table.iter().map(|(k, v)| (k.clone(), toml_text(v))).collect())
+            .unwrap_or_default()
+    };
+
+    let command = text("command");
+    let url = text("url");
+    McpServer {
+        name: name.to_string(),
+        transport: if command.is_none() && url.is_some() { McpTransport::Http } else { McpTransport::Stdio },
+        command,
+        args: value.get("args")
+            .and_then(toml::Value::as_array)
+            .map(|args| args.iter().map(toml_text).collect())
+            .unwrap_or_default(),
+        env: strings("env"),
+        url,
+        headers: strings("http_headers"),
+        disabled: value.get("enabled").and_then(toml::Value::as_bool) == Some(false),
+    }
+}
+
+fn toml_text(value: &toml::Value) -> String {
+    value.as_str().map_or_else(|| value.to_string(), str::to_string)
+}
+
+/// Remove the `[mcp_servers.<previous>]` table and/or write `server`'s, leaving
+/// the rest of `config.toml` as it was
+fn toml_edit(text: &str, previous: Option<&str>, server: Option<&McpServer>) -> Option<String> {
+    let config: toml::Table = toml::from_str(text).ok()?;
+    let existing = previous
+        .or(server.map(|s| s.name.as_str()))
+        .and_then(|name| config.get("mcp_servers")?.get(name)?.as_table());
+
+    let lines: Vec<&str> = text.split_inclusive('\n').collect();
+    let section = server.map(|s| toml_section(s, existing)).unwrap_or_default();
+    let old = previous.or(server.map(|s| s.name.as_str())).and_then(|name| toml_section_span(&lines, name));
+
+    // A renamed server's table is replaced where it is
+    let updated = match old {
+        Some(span) => {
+            // Keep the blank lines separating the table from the next one
+            let mut end = span.end;
+            if server.is_some() {
+                while end > span.start + 1 && lines[end - 1].trim().is_empty() {
+                    end -= 1;
+                }
+            }
+            match server {
+                Some(_) => format!("{}{}{}", lines[..span.start].concat(), section, lines[end..].concat()),
+                None => toml_without(&lines, span),
+            }
+        }
+        None if text.trim().is_empty() => section,
+        None => format!("{}{}\n{}", text, if text.ends_with('\n') { "" } else { "\n" }, section),
+    };
+
+    Some(updated)
+}
+
+fn toml_section(server: &McpServer, existing: Option<&toml::Table>) -> String {
+    let mut table = existing.cloned().unwrap_or_default();
+    for key in TOML_KEYS {
+        table.remove(key);
+    }
+
+    let strings = |map: &BTreeMap<String, String>| {
+        toml::Value::Table(map.iter().map(|(k, v)| (k.clone(), toml::Value::String(v.clone()))).collect())
+    };
+    if let Some(command) = &server.command {
+        table.insert("command".into(), toml::Value::String(command.clone()));
+    }
+    if !server.args.is_empty() {
+        table.insert("args".into(), toml::Value::Array(server.args.iter().cloned().map(toml::Value::String).collect()));
+    }
+    if !server.env.is_empty() {
+        table.insert("env".into(), strings(&server.env));
+    }
+    if let Some(url) = &server.url {
+        table.insert("url".into(), toml::Value::String(url.clone()));
+    }
+    if !server.headers.is_empty() {
+        table.insert("http_headers".into(), strings(&server.headers));
+    }
+    if server.disabled {
+        table.insert("enabled".into(), toml::Value::Boolean(false));
+    }
+
+    let mut out = format!("[mcp_servers.{}]\n", toml_key(&server.name));
+    for (key, value) in &table {
+        out.push_str(&format!("{} = {}\n", toml_key(key), value));
+    }
+    out
+}
+
+fn toml_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        key.to_string()
+    } else {
+        toml::Value::String(key.to_string()).to_string()
+    }
+}
+
+/// Lines of the `[mcp_servers.<name>]` table, including its subtables, up to
+/// the next table header
+fn toml_section_span(lines: &[&str], name: &str) -> Option<Range<usize>> {
+    let start = lines.iter().position(|line| toml_header(line).is_some_and(|path| path == ["mcp_servers", name]))?;
+    let end = (start + 1..lines.len())
+        .find(|&i| {
+            lines[i].starts_with('[')
+                && toml_header(lines[i]).map_or(true, |path| path.len() <= 2 || path[..2] != ["mcp_servers", name])
+        })
+        .unwrap_or(lines.len());
+
+    Some(start..end)
+}
+
+/// `lines` without `span`, dropping the blank lines that separated a removed
+/// last table from the one before it
+fn toml_without(lines: &[&str], span: Range<usize>) -> String {
+    let mut start = span.start;
+    if span.end == lines.len() {
+        while start > 0 && lines[start - 1].trim().is_empty() {
+            start -= 1;
+        }
+    }
+    format!("{}{}", lines[..start].concat(), lines[span.end..].concat())
+}
+
+/// Key path of a `[table.header]` line
+fn toml_header(line: &str) -> Option<Vec<String>> {
+    if !line.starts_with('[') || line.starts_with("[[") {
+        return None;
+    }
+
+    let table: toml::Table = toml::from_str(line).ok()?;
+    let mut path = Vec::new();
+    let mut current = &table;
+    while current.len() == 1 {
+        let (key, value) = current.iter().next()?;
+        path.push(key.clone());
+        current = value.as_table()?;
+    }
+    Some(path)
+}
+
+/// A Continue block file holding one server
+fn continue_block(server: &McpServer) -> String {
+    let mut out = format!("name: {}\nversion: 0.0.1\nschema: v1\nmcpServers:\n", yaml_string(&server.name));
+    out.push_str(&format!("  - name: {}\n", yaml_string(&server.name)));
+
+    match server.transport {
+        McpTransport::Stdio => {
+            out.push_str(&format!("    command: {}\n", yaml_string(server.command.as_deref().unwrap_or_default())));
+            if !server.args.is_empty() {
+                out.push_str("    args:\n");
+                for arg in &server.args {
+                    out.push_str(&format!("      - {}\n", yaml_string(arg)));
+                }
+            }
+        }
+        McpTransport::Http | McpTransport::Sse => {
+            let kind = if server.transport == McpTransport::Sse { "sse" } else { "streamable-http" };
+            out.push_str(&format!("    type: {}\n", kind));
+            out.push_str(&format!("    url: {}\n", yaml_string(server.url.as_deref().unwrap_or_default())));
+        }
+    }
+
+    if !server.env.is_empty() {
+        out.push_str("    env:\n");
+        for (key, value) in &server.env {
+            out.push_str(&format!("      {}: {}\n", yaml_string(key), yaml_string(value)));
+        }
+    }
+    if !server.headers.is_empty() {
+        out.push_str("    requestOptions:\n      headers:\n");
+        for (key, value) in &server.headers {
+            out.push_str(&format!("        {}: {}\n", yaml_string(key), yaml_string(value)));
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stdio(name: &str, command: &str) -> McpServer {
+        McpServer {
+            name: name.to_string(),
+            transport: McpTransport::Stdio,
+            command: Some(command.to_string()),
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            url: None,
+            headers: BTreeMap::new(),
+            disabled: false,
+        }
+    }
+
+    fn add(text: &str, server: &McpServer) -> String {
+        json_edit(Dialect::Cursor, text, None, Some(server)).unwrap()
+    }
+
+    fn remove(text: &str, name: &str) -> String {
+        json_edit(Dialect::Cursor, text, Some(name), None).unwrap()
+    }
+
+    #[test]
+    fn json_new_file_is_pretty_printed() {
+        for text in ["", " \n"] {
+            assert_eq!(
+                add(text, &stdio("gh", "npx")),
+                "{\n  \"mcpServers\": {\n    \"gh\": {\n      \"command\": \"npx\"\n    }\n  }\n}\n"
+            );
+        }
+    }
+
+    #[test]
+    fn json_one_line_objects_stay_on_one_line() {
+        let text = "{\"mcpServers\": {\"a\": {\"command\": \"x\"}}, \"theme\": \"dark\"}\n";
+        assert_eq!(
+            add(text, &stdio("gh", "npx")),
+            "{\"mcpServers\": {\"gh\": {\"command\":\"npx\"}, \"a\": {\"command\": \"x\"}}, \"theme\": \"dark\"}\n"
+        );
+        assert_eq!(
+            add("{\"theme\": \"dark\"}", &stdio("gh", "npx")),
+            "{\"mcpServers\": {\"gh\": {\"command\":\"npx\"}}, \"theme\": \"dark\"}"
+        );
+    }
+
+    #[test]
+    fn json_pretty_objects_keep_their_indentation() {
+        let text = "{\n    \"theme\": \"dark\",\n    \"mcpServers\": {\n        \"a\": {\"command\": \"x\"}\n    }\n}\n";
+        assert_eq!(
+            add(text, &stdio("gh", "npx")),
+            "{\n    \"theme\": \"dark\",\n    \"mcpServers\": {\n        \"gh\": {\n          \"command\": \"npx\"\n        },\n        \"a\": {\"command\": \"x\"}\n    }\n}\n"
+        );
+
+        // Replacing a server only rewrites its value
+        let replaced = add(text, &stdio("a", "y"));
+        assert_eq!(
+            replaced,
+            "{\n    \"theme\": \"dark\",\n    \"mcpServers\": {\n        \"a\": {\n          \"command\": \"y\"\n        }\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn json_removes_first_middle_last_and_only_members() {
+        let text = "{\n  \"mcpServers\": {\n    \"a\": {},\n    \"b\": {},\n    \"c\": {}\n  },\n  \"x\": 1\n}\n";
+        assert_eq!(remove(text, "a"), "{\n  \"mcpServers\": {\n    \"b\": {},\n    \"c\": {}\n  },\n  \"x\": 1\n}\n");
+        assert_eq!(remove(text, "b"), "{\n  \"mcpServers\": {\n    \"a\": {},\n    \"c\": {}\n  },\n  \"x\": 1\n}\n");
+        assert_eq!(remove(text, "c"), "{\n  \"mcpServers\": {\n    \"a\": {},\n    \"b\": {}\n  },\n  \"x\": 1\n}\n");
+        assert_eq!(remove("{\"mcpServers\": {\"a\": {}}}", "a"), "{\"mcpServers\": {}}");
+        assert_eq!(remove("{\"mcpServers\": {\"a\": {}, \"b\": []}}", "b"), "{\"mcpServers\": {\"a\": {}}}");
+    }
+
+    #[test]
+    fn json_missing_servers_are_left_alone() {
+        assert_eq!(json_edit(Dialect::Cursor, "{\"mcpServers\": {}}", Some("a"), None), None);
+        assert_eq!(json_edit(Dialect::Cursor, "{\"x\": 1}", Some("a"), None), None);
+        assert_eq!(json_edit(Dialect::Cursor, "not json", None, Some(&stdio("a", "x"))), None);
+    }
+
+    #[test]
+    fn json_rename_keeps_unmanaged_keys() {
+        let text = "{\"mcpServers\": {\"old\": {\"command\": \"x\", \"autoApprove\": [\"read\"]}, \"b\": {}}}";
+        let renamed = json_edit(Dialect::Cline, text, Some("old"), Some(&stdio("new", "y"))).unwrap();
+        assert_eq!(
+            renamed,
+            "{\"mcpServers\": {\"new\": {\"autoApprove\":[\"read\"],\"command\":\"y\"}, \"b\": {}}}"
+        );
+    }
+
+    #[test]
+    fn json_servers_key_follows_dialect() {
+        let edited = json_edit(Dialect::VsCode, "{}", None, Some(&stdio("gh", "npx"))).unwrap();
+        let servers = parse_servers(Dialect::VsCode, &edited).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert!(edited.contains("\"servers\""));
+    }
+
+    #[test]
+    fn toml_adds_to_empty_and_existing_configs() {
+        let mut server = stdio("gh", "npx");
+        server.args = vec!["gh-mcp".into()];
+        assert_eq!(toml_edit("", None, Some(&server)).unwrap(), "[mcp_servers.gh]\nargs = [\"gh-mcp\"]\ncommand = \"npx\"\n");
+        assert_eq!(
+            toml_edit("model = \"o3\"", None, Some(&server)).unwrap(),
+            "model = \"o3\"\n\n[mcp_servers.gh]\nargs = [\"gh-mcp\"]\ncommand = \"npx\"\n"
+        );
+    }
+
+    #[test]
+    fn toml_replaces_tables_with_their_subtables() {
+        let text = "model = \"o3\"\n\n[mcp_servers.gh]\ncommand = \"old\"\nstartup_timeout_sec = 20\n\n[mcp_servers.gh.env]\nTOKEN = \"t\"\n\n[mcp_servers.docs]\nurl = \"https://docs.dev\"\n";
+        let edited = toml_edit(text, None, Some(&stdio("gh", "npx"))).unwrap();
+        assert_eq!(
+            edited,
+            "model = \"o3\"\n\n[mcp_servers.gh]\ncommand = \"npx\"\nstartup_timeout_sec = 20\n\n[mcp_servers.docs]\nurl = \"https://docs.dev\"\n"
+        );
+    }
+
+    #[test]
+    fn toml_removes_first_and_last_tables() {
+        let text = "model = \"o3\"\n\n[mcp_servers.a]\ncommand = \"a\"\n\n[mcp_servers.a.env]\nK = \"v\"\n\n[mcp_servers.b]\ncommand = \"b\"\n";
+        assert_eq!(toml_edit(text, Some("a"), None).unwrap(), "model = \"o3\"\n\n[mcp_servers.b]\ncommand = \"b\"\n");
+        assert_eq!(
+            toml_edit(text, Some("b"), None).unwrap(),
+            "model = \"o3\"\n\n[mcp_servers.a]\ncommand = \"a\"\n\n[mcp_servers.a.env]\nK = \"v\"\n"
+        );
+    }
+
+    #[test]
+    fn toml_rename_drops_the_old_table() {
+        let text = "[mcp_servers.old]\ncommand = \"x\"\n\n[other]\nk = 1\n";
+        let renamed = toml_edit(text, Some("old"), Some(&stdio("new", "y"))).unwrap();
+        assert_eq!(renamed, "[mcp_servers.new]\ncommand = \"y\"\n\n[other]\nk = 1\n");
+    }
+
+    #[test]
+    fn toml_section_span_stops_at_other_tables() {
+        let lines = ["[mcp_servers.a]\n", "x = 1\n", "[mcp_servers.a.env]\n", "[mcp_servers.ab]\n", "[z]\n"];
+        assert_eq!(toml_section_span(&lines, "a"), Some(0..3));
+        assert_eq!(toml_section_span(&lines, "ab"), Some(3..4));
+        assert_eq!(toml_section_span(&lines, "zz"), None);
+    }
+}
@@ -7,6 +7,23 @@ pub mod registry_service;
 pub mod update_service;
 pub mod keyring_service;
 pub mod auth_service;
+pub mod git_service;
+pub mod archive_service;
+pub mod convert_service;
+pub mod bundle_service;
+pub mod version_service;
+pub mod diff_service;
+pub mod scheduler_service;
+pub mod state_service;
+pub mod rule_service;
+pub mod claude_service;
+pub mod claude_memory_service;
+pub mod windsurf_service;
+pub mod continue_service;
+pub mod aider_service;
+pub mod effective_rules_service;
+pub mod mcp_service;
+pub mod mcp_library_service;
 
 pub use skill_service::*;
 pub use backup_service::BackupService;
@@ -17,3 +34,20 @@ pub use registry_service::RegistryService;
 pub use update_service::UpdateService;
 pub use keyring_service::KeyringService;
 pub use auth_service::AuthService;
+pub use git_service::GitService;
+pub use archive_service::ArchiveService;
+pub use convert_service::ConvertService;
+pub use bundle_service::BundleService;
+pub use version_service::VersionService;
+pub use diff_service::DiffService;
+pub use scheduler_service::SchedulerService;
+pub use state_service::StateService;
+pub use rule_service::RuleService;
+pub use claude_service::ClaudeService;
+pub use claude_memory_service::ClaudeMemoryService;
+pub use windsurf_service::WindsurfService;
+pub use continue_service::ContinueService;
+pub use aider_service::AiderService;
+pub use effective_rules_service::EffectiveRulesService;
+pub use mcp_service::McpService;
+pub use mcp_library_service::McpLibraryService;
@@ -1,39 +1,79 @@
-use std::path::PathBuf;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
+use serde::{Deserialize, Serialize};
 use tokio::fs;
 
 use crate::models::{
-    SkillRegistry, RemoteSkill, InstalledSkill, RegistryConfig, SkillUpdate, AppError
+    SkillRegistry, RemoteSkill, SkillVersion, InstalledSkill, InstallSource, GitSource, RegistryConfig,
+    SkillUpdate, AgentType, DiscoveredSkill, ProjectManifest, Lockfile, LockedSkill, SyncAction,
+    SyncActionKind, SyncReport, UpdateChannel, UpdatePolicy, DriftItem, DriftKind, ReconcileFix, ReconcileReport,
+    AgentConfig, CursorRule, McpScope, McpServer, AppError, PROJECT_MANIFEST_FILE, PROJECT_LOCK_FILE, LOCKFILE_VERSION
 };
 use crate::services::download_service::DownloadService;
+use crate::services::git_service::GitService;
+use crate::services::archive_service::ArchiveService;
+use crate::services::backup_service::BackupService;
+use crate::services::version_service::VersionService;
+use crate::services::diff_service::DiffService;
+use crate::services::state_service::{StateFile, StateService};
+use crate::services::aider_service::{self, AiderService};
+use crate::services::rule_service::split_frontmatter;
+use crate::services::{ConvertService, McpService, RuleService, SkillService, WindsurfService};
+
+/// Agents registry skills can be installed for
+const INSTALL_AGENTS: [&str; 9] = ["claude", "cursor", "continuedev", "aider", "windsurf", "copilot", "codex", "gemini", "cline"];
 
 pub struct RegistryService {
     download: DownloadService,
+    git: GitService,
+    backup: BackupService,
+    mcp: McpService,
     cache_dir: PathBuf,
-    installed_db_path: PathBuf,
+    state: StateService,
+}
+
+/// The installed skills database
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct InstalledDb(Vec<InstalledSkill>);
+
+impl StateFile for InstalledDb {
+    const NAME: &'static str = "installed-skills.json";
+    // 0: bare list of records, unchanged apart from the envelope
+    const SCHEMA: u32 = 1;
 }
 
 impl RegistryService {
     pub fn new() -> Self {
+        Self::with_state(StateService::new())
+    }
+
+    /// A service sharing the given state store
+    pub fn with_state(state: StateService) -> Self {
         let cache_dir = dirs::cache_dir()
             .unwrap_or_else(|| PathBuf::from("."))
             .join("ai-skills-aggregator")
             .join("registries");
 
-        let installed_db_path = dirs::data_local_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join("ai-skills-aggregator")
-            .join("installed-skills.json");
-
         Self {
             download: DownloadService::new(),
+            git: GitService::new(),
+            backup: BackupService::new(),
+            mcp: McpService::new(),
             cache_dir,
-            installed_db_path,
+            state,
         }
     }
 
     /// Fetch and parse a registry from URL
     pub async fn fetch_registry(&self, config: &RegistryConfig) -> Result<SkillRegistry, AppError> {
+        // Registries on disk are cheap to read and should reflect edits immediately
+        if let Some(path) = DownloadService::local_path(&config.url) {
+            return self.read_local_registry(&path, &config.url).await;
+        }
+
         // Check cache first
         let cache_file = self.cache_dir.join(url_to_filename(&config.url));
         if let Ok(cached) = self.read_cache(&cache_file).await {
@@ -68,52 +108,306 @@ impl RegistryService {
         Ok(registry)
     }
 
-    /// Install a skill from a remote registry
+    /// Install a skill from a remote registry.
+    /// `version` is an exact version or a constraint such as `^1.2`;
+    /// None installs the latest stable version.
     pub async fn install_skill(
         &self,
         skill: &RemoteSkill,
         registry_url: &str,
         agent: &str,
+        version: Option<&str>,
     ) -> Result<InstalledSkill, AppError> {
-        // Get file path for this agent
-        let file_path = self.get_agent_file(skill, agent)
-            .ok_or_else(|| AppError::InvalidPath(
-                format!("Skill {} doesn't support {}", skill.id, agent)
-            ))?;
+        let skill = &Self::resolve_version(skill, version)?;
+        let url = self.resolve_file_url(skill, registry_url, agent)?;
+
+        self.install_with_servers(skill, &url, registry_url, agent).await
+    }
 
-        // Resolve full URL
-        let file_url = if file_path.starts_with("http") {
-            file_path.clone()
+    /// Install a registry skill's file from `url`, adding the MCP servers it
+    /// ships to the agent's config
+    async fn install_with_servers(
+        &self,
+        skill: &RemoteSkill,
+        url: &str,
+        registry_url: &str,
+        agent: &str,
+    ) -> Result<InstalledSkill, AppError> {
+        // Check the skill's MCP servers before installing anything
+        let agent_type = AgentType::parse(agent);
+        let servers = if McpService::supports(&agent_type) {
+            skill.mcp_servers.as_slice()
         } else {
-            // Build URL relative to registry
-            let base_url = registry_url.trim_end_matches("registry.json").trim_end_matches('/');
-            format!("{}/{}", base_url, file_path.trim_start_matches('/'))
+            if !skill.mcp_servers.is_empty() {
+                log::warn!("{} doesn't support MCP servers; skipping those of {}", agent, skill.id);
+            }
+            &[]
         };
+        for server in servers {
+            let problems = McpService::validate(&agent_type, server);
+            if !problems.is_empty() {
+                return Err(AppError::ParseError(
+                    format!("MCP server {} of {}: {}", server.name, skill.id, problems.join("; "))
+                ));
+            }
+        }
+        let installed_skills = self.get_installed_skills().await?;
+        let previous = installed_skills.iter()
+            .find(|s| s.skill_id == skill.id && s.agent == agent)
+            .map(|s| s.mcp_servers.clone())
+            .unwrap_or_default();
+        let (to_write, recorded) = self.plan_mcp_servers(&skill.id, agent, servers, &installed_skills)?;
 
-        let raw_url = self.download.convert_github_url_to_raw(&file_url);
+        let mut installed = self.install_file(skill, url, registry_url, agent).await?;
 
-        // Determine destination path
-        let dest_path = self.get_install_path(agent, &skill.id)?;
+        if !recorded.is_empty() || !previous.is_empty() {
+            let configs = AgentConfig::defaults();
+            for name in previous.iter().filter(|name| !recorded.contains(name)) {
+                self.remove_mcp_server(&skill.id, agent, &configs, name, &installed_skills).await?;
+            }
+            for server in to_write {
+                self.mcp.copy_server(server, std::slice::from_ref(&agent_type), McpScope::User, &configs, None, true).await?;
+            }
 
-        // Download file
-        self.download.download_file(&raw_url, &dest_path).await?;
+            installed.mcp_servers = recorded;
+            self.record_installation(&installed).await?;
+        }
 
-        // Record installation
-        let installed = InstalledSkill {
-            skill_id: skill.id.clone(),
-            registry_url: registry_url.to_string(),
-            version: skill.version.clone(),
-            installed_path: dest_path.to_string_lossy().to_string(),
-            agent: agent.to_string(),
-            installed_at: SystemTime::now()
-                .duration_since(UNIX_EPOCH)
-                .unwrap_or_default()
-                .as_secs() as i64,
+        Ok(installed)
+    }
+
+    /// Which of a skill's MCP servers to write into the agent's user config,
+    /// and which to record as managed by the install. A server of the same
+    /// name that the user or another skill configured differently is never
+    /// replaced; an identical one is left as it is.
+    fn plan_mcp_servers<'a>(
+        &self,
+        skill_id: &str,
+        agent: &str,
+        servers: &'a [McpServer],
+        installed: &[InstalledSkill],
+    ) -> Result<(Vec<&'a McpServer>, Vec<String>), AppError> {
+        let agent_type = AgentType::parse(agent);
+        let configs = AgentConfig::defaults();
+        let mut to_write = Vec::new();
+        let mut recorded = Vec::new();
+
+        for server in servers {
+            let owners: Vec<&str> = installed.iter()
+                .filter(|s| s.agent == agent && s.mcp_servers.contains(&server.name))
+                .map(|s| s.skill_id.as_str())
+                .collect();
+
+            let existing = match configs.iter().find(|c| c.agent == agent_type) {
+                Some(config) => match McpService::target_path(config, McpScope::User, None, &server.name) {
+                    Some(path) => McpService::read_servers(&agent_type, &path)?
+                        .into_iter()
+                        .find(|s| s.name == server.name),
+                    None => None,
+                },
+                None => None,
+            };
+
+            match existing {
+                None => {
+                    to_write.push(server);
+                    recorded.push(server.name.clone());
+                }
+                // The user's own identical server stays theirs
+                Some(existing) if existing == *server => {
+                    if !owners.is_empty() {
+                        recorded.push(server.name.clone());
+                    }
+                }
+                Some(_) if !owners.is_empty() && owners.iter().all(|owner| *owner == skill_id) => {
+                    to_write.push(server);
+                    recorded.push(server.name.clone());
+                }
+                Some(_) => {
+                    return Err(AppError::AlreadyExists(match owners.iter().find(|owner| **owner != skill_id) {
+                        Some(owner) => format!(
+                            "MCP server {} of {} differs from the one {} installed for {}",
+                            server.name, skill_id, owner, agent
+                        ),
+                        None => format!(
+                            "MCP server {} is already configured for {}; remove or rename it before installing {}",
+                            server.name, agent, skill_id
+                        ),
+                    }));
+                }
+            }
+        }
+
+        Ok((to_write, recorded))
+    }
+
+    /// Remove an MCP server a skill installed, unless another installed skill
+    /// still uses it; it may already be gone
+    async fn remove_mcp_server(
+        &self,
+        skill_id: &str,
+        agent: &str,
+        configs: &[AgentConfig],
+        name: &str,
+        installed: &[InstalledSkill],
+    ) -> Result<(), AppError> {
+        let shared = installed.iter()
+            .any(|s| s.agent == agent && s.skill_id != skill_id && s.mcp_servers.iter().any(|n| n == name));
+        if shared {
+            return Ok(());
+        }
+
+        let agent_type = AgentType::parse(agent);
+        let path = configs.iter()
+            .find(|c| c.agent == agent_type)
+            .and_then(|config| McpService::target_path(config, McpScope::User, None, name));
+        let Some(path) = path else {
+            return Ok(());
         };
 
-        self.record_installation(&installed).await?;
+        match self.mcp.remove_server(&agent_type, &path, name).await {
+            Ok(()) | Err(AppError::NotFound(_)) => Ok(()),
+            Err(e) => Err(e),
+        }
+    }
 
-        Ok(installed)
+    /// Install skill folders from a git repository.
+    /// `paths` are skill folder paths relative to the repository root.
+    pub async fn install_from_git(
+        &self,
+        source: &GitSource,
+        paths: &[String],
+        agent: &str,
+    ) -> Result<Vec<InstalledSkill>, AppError> {
+        let _guard = self.git.lock(source).await;
+        let checkout = self.git.checkout(source).await?;
+        let mut results = Vec::new();
+
+        for path in paths {
+            let folder = SkillService::resolve_within(&checkout.repo_path, Some(path))?;
+
+            // The repository root may itself be the skill
+            let skill_id = if folder == checkout.repo_path {
+                GitService::repo_name(&source.url)
+            } else {
+                folder_name(&folder)
+            };
+
+            let source = InstallSource::Git(GitSource {
+                url: source.url.clone(),
+                git_ref: source.git_ref.clone(),
+                subdirectory: Some(path.trim_matches('/').to_string()),
+            });
+
+            let mut installed = self.install_folder(&folder, &skill_id, agent, source).await?;
+            installed.version = checkout.commit_sha.chars().take(7).collect();
+            installed.commit_sha = Some(checkout.commit_sha.clone());
+
+            self.record_installation(&installed).await?;
+            results.push(installed);
+        }
+
+        Ok(results)
+    }
+
+    /// List the skill folders inside a local directory or archive
+    pub async fn discover_local(&self, path: &str, agent: &str) -> Result<Vec<DiscoveredSkill>, AppError> {
+        let source_path = PathBuf::from(path);
+        let agent_type = AgentType::parse(agent);
+
+        if ArchiveService::detect(&source_path).is_some() {
+            let root = ArchiveService::extract_to_temp(&source_path)?;
+            let skills = SkillService::discover_skill_folders(&root, &root, &agent_type);
+            let _ = fs::remove_dir_all(&root).await;
+            return Ok(skills);
+        }
+
+        if !source_path.is_dir() {
+            return Err(AppError::FileNotFound(path.to_string()));
+        }
+
+        Ok(SkillService::discover_skill_folders(&source_path, &source_path, &agent_type))
+    }
+
+    /// Install skill folders from a local directory or `.zip`/`.tar.gz` archive.
+    /// `paths` are skill folder paths relative to the directory or archive root.
+    pub async fn install_from_local(
+        &self,
+        path: &str,
+        paths: &[String],
+        agent: &str,
+    ) -> Result<Vec<InstalledSkill>, AppError> {
+        let source_path = PathBuf::from(path);
+        let is_archive = ArchiveService::detect(&source_path).is_some();
+
+        let root = if is_archive {
+            ArchiveService::extract_to_temp(&source_path)?
+        } else if source_path.is_dir() {
+            source_path.clone()
+        } else {
+            return Err(AppError::FileNotFound(path.to_string()));
+        };
+
+        let result = self.install_local_folders(&source_path, &root, is_archive, paths, agent).await;
+
+        if is_archive {
+            let _ = fs::remove_dir_all(&root).await;
+        }
+
+        result
+    }
+
+    /// Re-install a git-sourced skill at the latest commit of its ref
+    pub async fn update_git_skill(&self, installed: &InstalledSkill) -> Result<InstalledSkill, AppError> {
+        let source = match &installed.source {
+            InstallSource::Git(source) => source,
+            _ => return Err(AppError::InvalidPath(
+                format!("Skill {} was not installed from git", installed.skill_id)
+            )),
+        };
+
+        let path = source.subdirectory.clone().unwrap_or_default();
+        let repo = GitSource {
+            subdirectory: None,
+            ..source.clone()
+        };
+
+        self.install_from_git(&repo, &[path], &installed.agent)
+            .await?
+            .pop()
+            .ok_or_else(|| AppError::NotFound(installed.skill_id.clone()))
+    }
+
+    /// Compare installed git skills against the current commit of their ref
+    pub async fn check_git_updates(&self) -> Result<Vec<SkillUpdate>, AppError> {
+        let installed = self.get_installed_skills().await?;
+        let mut updates = Vec::new();
+
+        for skill in &installed {
+            let InstallSource::Git(source) = &skill.source else {
+                continue;
+            };
+
+            let remote = match self.git.remote_commit(source).await {
+                Ok(Some(sha)) => sha,
+                Ok(None) => continue,
+                Err(e) => {
+                    log::warn!("Failed to check {} for updates: {}", source.url, e);
+                    continue;
+                }
+            };
+
+            if skill.commit_sha.as_deref() != Some(remote.as_str()) {
+                updates.push(SkillUpdate {
+                    skill_id: skill.skill_id.clone(),
+                    current_version: skill.version.clone(),
+                    new_version: remote.chars().take(7).collect(),
+                    agent: skill.agent.clone(),
+                });
+            }
+        }
+
+        Ok(updates)
     }
 
     /// Uninstall a remote skill
@@ -140,28 +434,22 @@ impl RegistryService {
             }
         }
 
-        // Update installed DB
-        let remaining: Vec<_> = installed.into_iter()
-            .filter(|s| !(s.skill_id == skill_id && s.agent == agent))
-            .collect();
+        if AgentType::parse(agent) == AgentType::Aider {
+            self.unregister_aider_read(&path).await?;
+        }
 
-        self.save_installed_skills(&remaining).await?;
+        let configs = AgentConfig::defaults();
+        for name in &skill.mcp_servers {
+            self.remove_mcp_server(skill_id, agent, &configs, name, &installed).await?;
+        }
 
-        Ok(())
+        // Update installed DB
+        self.forget(skill_id, agent).await
     }
 
     /// Get list of installed skills
     pub async fn get_installed_skills(&self) -> Result<Vec<InstalledSkill>, AppError> {
-        if !self.installed_db_path.exists() {
-            return Ok(Vec::new());
-        }
-
-        let content = fs::read_to_string(&self.installed_db_path)
-            .await
-            .map_err(|e| AppError::IoError(e.to_string()))?;
-
-        serde_json::from_str(&content)
-            .map_err(|e| AppError::ParseError(e.to_string()))
+        Ok(self.state.read::<InstalledDb>().await?.0)
     }
 
     /// Check for available updates
@@ -169,24 +457,874 @@ impl RegistryService {
         let installed = self.get_installed_skills().await?;
         let mut updates = Vec::new();
 
-        for installed_skill in &installed {
-            if let Some(remote) = registry.skills.iter().find(|s| s.id == installed_skill.skill_id) {
-                if remote.version != installed_skill.version {
-                    updates.push(SkillUpdate {
-                        skill_id: installed_skill.skill_id.clone(),
-                        current_version: installed_skill.version.clone(),
-                        new_version: remote.version.clone(),
-                        agent: installed_skill.agent.clone(),
+        for installed_skill in installed.iter().filter(|s| s.source == InstallSource::Registry) {
+            if let Some(remote) = self.available_update(installed_skill, registry) {
+                updates.push(SkillUpdate {
+                    skill_id: installed_skill.skill_id.clone(),
+                    current_version: installed_skill.version.clone(),
+                    new_version: remote.version.clone(),
+                    agent: installed_skill.agent.clone(),
+                });
+            }
+        }
+
+        Ok(updates)
+    }
+
+    /// The registry version an installed skill may update to, honouring its
+    /// pin and update channel. Never offers a downgrade or a yanked version.
+    pub fn available_update(
+        &self,
+        installed: &InstalledSkill,
+        registry: &SkillRegistry,
+    ) -> Option<RemoteSkill> {
+        let remote = registry.skills.iter().find(|s| s.id == installed.skill_id)?;
+
+        remote.releases().into_iter()
+            .filter(|r| !r.yanked)
+            .filter(|r| VersionService::is_update_allowed(
+                &installed.version,
+                &r.version,
+                installed.pinned_version.as_deref(),
+                &installed.channel,
+            ))
+            .max_by(|a, b| VersionService::compare(&a.version, &b.version))
+            .and_then(|r| remote.at_version(&r.version))
+    }
+
+    /// Pick the newest non-yanked published version matching a constraint.
+    /// Without a constraint, pre-releases are only chosen when nothing
+    /// stable has been published.
+    pub fn resolve_version(skill: &RemoteSkill, requirement: Option<&str>) -> Result<RemoteSkill, AppError> {
+        if let Some(requirement) = requirement {
+            VersionService::parse_requirement(requirement)?;
+        }
+
+        let candidates: Vec<_> = skill.releases().into_iter()
+            .filter(|r| !r.yanked && VersionService::satisfies(requirement, &r.version))
+            .collect();
+
+        let newest = |stable_only: bool| candidates.iter()
+            .filter(|r| !stable_only || !VersionService::is_prerelease(&r.version))
+            .max_by(|a, b| VersionService::compare(&a.version, &b.version));
+
+        newest(requirement.is_none())
+            .or_else(|| newest(false))
+            .and_then(|r| skill.at_version(&r.version))
+            .ok_or_else(|| AppError::NotFound(format!(
+                "No published version of {} matches {}",
+                skill.id,
+                requirement.unwrap_or("*")
+            )))
+    }
+
+    /// Non-yanked releases newer than `from` up to and including `to`,
+    /// newest first
+    pub fn releases_between(skill: &RemoteSkill, from: &str, to: &str) -> Vec<SkillVersion> {
+        let mut releases: Vec<SkillVersion> = skill.releases().into_iter()
+            .filter(|r| !r.yanked)
+            .filter(|r| VersionService::compare(&r.version, from) == Ordering::Greater)
+            .filter(|r| VersionService::compare(&r.version, to) != Ordering::Greater)
+            .collect();
+
+        releases.sort_by(|a, b| VersionService::compare(&b.version, &a.version));
+        releases
+    }
+
+    /// The newest non-yanked version published before `version`
+    pub fn previous_version(skill: &RemoteSkill, version: &str) -> Option<RemoteSkill> {
+        skill.releases().into_iter()
+            .filter(|r| !r.yanked)
+            .filter(|r| VersionService::compare(&r.version, version) == Ordering::Less)
+            .max_by(|a, b| VersionService::compare(&a.version, &b.version))
+            .and_then(|r| skill.at_version(&r.version))
+    }
+
+    /// Pin an installed skill to a version constraint (None to unpin)
+    pub async fn set_pin(&self, skill_id: &str, agent: &str, pin: Option<String>) -> Result<InstalledSkill, AppError> {
+        if let Some(requirement) = &pin {
+            VersionService::parse_requirement(requirement)?;
+        }
+
+        self.update_record(skill_id, agent, |skill| skill.pinned_version = pin).await
+    }
+
+    /// Choose what the background scheduler does with a skill's updates
+    pub async fn set_update_policy(&self, skill_id: &str, agent: &str, policy: UpdatePolicy) -> Result<InstalledSkill, AppError> {
+        self.update_record(skill_id, agent, |skill| skill.update_policy = policy).await
+    }
+
+    /// Choose whether an installed skill follows stable or pre-release versions
+    pub async fn set_channel(&self, skill_id: &str, agent: &str, channel: UpdateChannel) -> Result<InstalledSkill, AppError> {
+        self.update_record(skill_id, agent, |skill| skill.channel = channel).await
+    }
+
+    /// Resolve `skills.toml` in a project directory against its registries
+    /// and write `skills.lock`
+    pub async fn lock_project(&self, project_dir: &Path) -> Result<Lockfile, AppError> {
+        let manifest = read_project_manifest(project_dir).await?;
+        let mut locked = Vec::new();
+
+        for (skill_id, requirement) in &manifest.skills {
+            let registry_url = manifest.registries
+                .get(&requirement.registry)
+                .cloned()
+                .unwrap_or_else(|| requirement.registry.clone());
+
+            let config = RegistryConfig {
+                url: registry_url.clone(),
+                name: requirement.registry.clone(),
+                enabled: true,
+                auth_token: None,
+            };
+            let registry = self.fetch_registry(&config).await?;
+
+            let remote = registry.skills.iter()
+                .find(|s| &s.id == skill_id)
+                .ok_or_else(|| AppError::NotFound(
+                    format!("Skill {} not found in {}", skill_id, registry_url)
+                ))?;
+
+            let remote = &Self::resolve_version(remote, requirement.version.as_deref())?;
+
+            let agents = if requirement.agents.is_empty() {
+                self.supported_agents(remote)
+            } else {
+                requirement.agents.clone()
+            };
+
+            for agent in agents {
+                let source = self.resolve_file_url(remote, &registry_url, &agent)?;
+                let bytes = self.download.fetch_bytes(&source).await?;
+
+                locked.push(LockedSkill {
+                    id: skill_id.clone(),
+                    version: remote.version.clone(),
+                    agent: agent.to_lowercase(),
+                    registry: registry_url.clone(),
+                    source,
+                    checksum: DownloadService::checksum(&bytes),
+                });
+            }
+        }
+
+        let lockfile = Lockfile {
+            version: LOCKFILE_VERSION,
+            skills: locked,
+        };
+
+        write_lockfile(project_dir, &lockfile).await?;
+
+        Ok(lockfile)
+    }
+
+    /// Make installed skills match a project's `skills.lock`.
+    /// With `check_only`, nothing is changed and the report lists the drift.
+    ///
+    /// Skills an earlier sync of this lockfile installed that it no longer
+    /// lists are removed, unless another project's lockfile still lists them;
+    /// everything else is left alone.
+    pub async fn sync_project(&self, project_dir: &Path, check_only: bool) -> Result<SyncReport, AppError> {
+        let lockfile = read_lockfile(project_dir).await?;
+        let lockfile_path = lockfile_key(project_dir);
+        let installed = self.get_installed_skills().await?;
+        let mut actions = Vec::new();
+
+        for locked in &lockfile.skills {
+            let current = installed.iter()
+                .find(|s| s.skill_id == locked.id && s.agent.eq_ignore_ascii_case(&locked.agent));
+
+            let action = match current {
+                None => Some((SyncActionKind::Install, "not installed".to_string())),
+                Some(s) if s.version != locked.version => {
+                    Some((SyncActionKind::Update, "version differs from lockfile".to_string()))
+                }
+                // Installs may add frontmatter, so what was written is
+                // checked against the install's own hash
+                Some(s) => match fs::read(&s.installed_path).await {
+                    Ok(bytes) if [Some(&locked.checksum), s.content_hash.as_ref()]
+                        .contains(&Some(&DownloadService::checksum(&bytes))) => None,
+                    Ok(_) => Some((SyncActionKind::Update, "content differs from lockfile".to_string())),
+                    Err(_) => Some((SyncActionKind::Install, "installed file is missing".to_string())),
+                },
+            };
+
+            if let Some((kind, reason)) = action {
+                actions.push(SyncAction {
+                    kind,
+                    skill_id: locked.id.clone(),
+                    agent: locked.agent.clone(),
+                    installed_version: current.map(|s| s.version.clone()),
+                    locked_version: Some(locked.version.clone()),
+                    reason,
+                });
+            }
+        }
+
+        let is_locked = |skill: &InstalledSkill| lockfile.skills.iter()
+            .any(|l| l.id == skill.skill_id && l.agent.eq_ignore_ascii_case(&skill.agent));
+
+        // Installs are shared by every project, so only skills this lockfile
+        // installed, and no other lockfile still lists, are removed
+        for skill in &installed {
+            let installed_here = skill.lockfiles.contains(&lockfile_path);
+            let elsewhere = skill.lockfiles.iter().any(|l| *l != lockfile_path);
+
+            if installed_here && !elsewhere && !is_locked(skill) {
+                actions.push(SyncAction {
+                    kind: SyncActionKind::Remove,
+                    skill_id: skill.skill_id.clone(),
+                    agent: skill.agent.clone(),
+                    installed_version: Some(skill.version.clone()),
+                    locked_version: None,
+                    reason: "not in lockfile".to_string(),
+                });
+            }
+        }
+
+        let in_sync = actions.is_empty();
+
+        if !check_only {
+            let mut registries = HashMap::new();
+            for action in &actions {
+                match action.kind {
+                    SyncActionKind::Install | SyncActionKind::Update => {
+                        let locked = lockfile.skills.iter()
+                            .find(|l| l.id == action.skill_id && l.agent == action.agent)
+                            .ok_or_else(|| AppError::NotFound(action.skill_id.clone()))?;
+
+                        let skill = self.locked_skill(locked, &mut registries).await?;
+                        self.install_with_servers(&skill, &locked.source, &locked.registry, &locked.agent).await?;
+
+                        // Installing writes a fresh record; keep the
+                        // lockfiles the previous install belonged to
+                        let mut lockfiles = installed.iter()
+                            .find(|s| s.skill_id == locked.id && s.agent == locked.agent)
+                            .map(|s| s.lockfiles.clone())
+                            .unwrap_or_default();
+                        if !lockfiles.contains(&lockfile_path) {
+                            lockfiles.push(lockfile_path.clone());
+                        }
+                        self.update_record(&locked.id, &locked.agent, |s| s.lockfiles = lockfiles).await?;
+                    }
+                    SyncActionKind::Remove => {
+                        self.uninstall_skill(&action.skill_id, &action.agent).await?;
+                    }
+                }
+            }
+
+            // Lockfile installs this lockfile also needs stay until the last
+            // lockfile listing them drops them
+            for skill in &installed {
+                let listed = skill.lockfiles.contains(&lockfile_path);
+                let adopt = is_locked(skill) && !listed && !skill.lockfiles.is_empty();
+                let release = !is_locked(skill) && listed && skill.lockfiles.len() > 1;
+                if !adopt && !release {
+                    continue;
+                }
+
+                self.update_record(&skill.skill_id, &skill.agent, |s| {
+                    s.lockfiles.retain(|l| *l != lockfile_path);
+                    if adopt {
+                        s.lockfiles.push(lockfile_path.clone());
+                    }
+                }).await?;
+            }
+        }
+
+        Ok(SyncReport {
+            actions,
+            in_sync,
+            applied: !check_only,
+        })
+    }
+
+    /// Compare the installed skills database with the disk. `registry_urls`
+    /// are searched, together with the registries skills were installed
+    /// from, for skills matching untracked folders.
+    pub async fn reconcile(&self, registry_urls: &[String]) -> Result<ReconcileReport, AppError> {
+        let installed = self.get_installed_skills().await?;
+        let mut items = Vec::new();
+
+        for skill in &installed {
+            let path = PathBuf::from(&skill.installed_path);
+
+            // Registry installs record the file inside the skill's folder
+            let folder = match skill.source {
+                InstallSource::Registry => path.parent().map(Path::to_path_buf).unwrap_or_else(|| path.clone()),
+                _ => path.clone(),
+            };
+
+            let kind = if !folder.exists() {
+                DriftKind::Orphan
+            } else if !has_skill_content(skill, &path) {
+                DriftKind::Missing
+            } else if skill.content_hash.as_ref().is_some_and(|hash| {
+                DiffService::content_hash(&path).ok().as_ref() != Some(hash)
+            }) {
+                DriftKind::Modified
+            } else {
+                continue;
+            };
+
+            let fixes = match kind {
+                DriftKind::Modified => vec![ReconcileFix::Reinstall],
+                _ => vec![ReconcileFix::Forget, ReconcileFix::Reinstall],
+            };
+
+            items.push(DriftItem {
+                kind,
+                skill_id: skill.skill_id.clone(),
+                agent: skill.agent.clone(),
+                path: skill.installed_path.clone(),
+                registry_url: Some(skill.registry_url.clone()).filter(|url| !url.is_empty()),
+                fixes,
+            });
+        }
+
+        // Untracked folders only matter when a registry offers the same skill
+        let mut urls: Vec<&String> = registry_urls.iter()
+            .chain(installed.iter().filter(|s| s.source == InstallSource::Registry).map(|s| &s.registry_url))
+            .collect();
+        urls.sort();
+        urls.dedup();
+
+        let mut registries = Vec::new();
+        for url in urls {
+            let config = RegistryConfig {
+                url: url.clone(),
+                name: "".into(),
+                enabled: true,
+                auth_token: None,
+            };
+
+            match self.fetch_registry(&config).await {
+                Ok(registry) => registries.push(registry),
+                Err(e) => log::warn!("Failed to fetch {}: {}", url, e),
+            }
+        }
+
+        for agent in INSTALL_AGENTS {
+            let Some(skills_dir) = self.get_install_dir(agent, "").ok().filter(|d| d.is_dir()) else {
+                continue;
+            };
+
+            for entry in std::fs::read_dir(&skills_dir)?.flatten() {
+                let folder = entry.path();
+                let skill_id = folder_name(&folder);
+                if !folder.is_dir() || skill_id.starts_with('.') {
+                    continue;
+                }
+
+                let tracked = installed.iter().any(|s| {
+                    let path = Path::new(&s.installed_path);
+                    s.agent == agent && (path == folder || path.parent() == Some(folder.as_path()))
+                });
+                if tracked {
+                    continue;
+                }
+
+                let matching = registries.iter().find(|registry| {
+                    registry.skills.iter().any(|s| s.id == skill_id && self.get_agent_file(s, agent).is_some())
+                });
+
+                if let Some(registry) = matching {
+                    items.push(DriftItem {
+                        kind: DriftKind::Untracked,
+                        skill_id,
+                        agent: agent.to_string(),
+                        path: folder.to_string_lossy().to_string(),
+                        registry_url: Some(registry.url.clone()),
+                        fixes: vec![ReconcileFix::Adopt],
                     });
                 }
             }
         }
 
-        Ok(updates)
+        Ok(ReconcileReport {
+            items,
+            checked: installed.len(),
+        })
+    }
+
+    /// Apply a fix to an item from `reconcile`. Returns the new install
+    /// record, or None when the record was forgotten.
+    pub async fn fix_drift(&self, item: &DriftItem, fix: ReconcileFix) -> Result<Option<InstalledSkill>, AppError> {
+        if !item.fixes.contains(&fix) {
+            return Err(AppError::InvalidPath(format!(
+                "{:?} does not apply to {:?} skill {}",
+                fix, item.kind, item.skill_id
+            )));
+        }
+
+        match fix {
+            ReconcileFix::Forget => {
+                self.forget(&item.skill_id, &item.agent).await?;
+                Ok(None)
+            }
+            ReconcileFix::Reinstall => self.reinstall(&item.skill_id, &item.agent).await.map(Some),
+            ReconcileFix::Adopt => self.adopt(item).await.map(Some),
+        }
+    }
+
+    /// Download a skill's file for an agent without installing it
+    pub async fn fetch_release(&self, skill: &RemoteSkill, registry_url: &str, agent: &str) -> Result<Vec<u8>, AppError> {
+        let url = self.resolve_file_url(skill, registry_url, agent)?;
+        let bytes = self.download.fetch_bytes(&url).await?;
+        if let Some(expected) = &skill.checksum {
+            DownloadService::verify_checksum(&bytes, expected, &url)?;
+        }
+
+        Ok(Self::installed_content(skill, agent, bytes))
+    }
+
+    /// What an install writes for a downloaded registry file: Agent Skills
+    /// folders need `name`/`description` frontmatter, and a Cursor rule
+    /// without frontmatter would only ever be attached by hand
+    fn installed_content(skill: &RemoteSkill, agent: &str, bytes: Vec<u8>) -> Vec<u8> {
+        if !Self::rewrites_content(agent) {
+            return bytes;
+        }
+        let agent = AgentType::parse(agent);
+        let content = match String::from_utf8(bytes) {
+            Ok(content) => content,
+            Err(e) => return e.into_bytes(),
+        };
+
+        match agent {
+            AgentType::Cursor if split_frontmatter(&content).is_none() => {
+                let description = skill.description.as_deref()
+                    .map(str::trim)
+                    .filter(|d| !d.is_empty())
+                    .unwrap_or(&skill.name);
+                let rule = CursorRule {
+                    description: Some(description.to_string()),
+                    globs: Vec::new(),
+                    always_apply: false,
+                };
+                RuleService::render(&rule, &content).into_bytes()
+            }
+            AgentType::Cursor => content.into_bytes(),
+            _ => ConvertService::with_skill_frontmatter(
+                &skill.name,
+                skill.description.as_deref(),
+                &content,
+                &agent,
+            ).into_bytes(),
+        }
+    }
+
+    /// Resolve a path from a registry (file, changelog) to a full URL
+    pub fn resolve_registry_url(&self, registry_url: &str, path: &str) -> String {
+        let url = if path.starts_with("http") {
+            path.to_string()
+        } else {
+            // Build URL relative to registry
+            let base_url = registry_url.trim_end_matches("registry.json").trim_end_matches('/');
+            format!("{}/{}", base_url, path.trim_start_matches('/'))
+        };
+
+        self.download.convert_github_url_to_raw(&url)
     }
 
     // Helper methods
 
+    /// Install a recorded skill again from its source
+    async fn reinstall(&self, skill_id: &str, agent: &str) -> Result<InstalledSkill, AppError> {
+        let record = self.get_installed_skills().await?
+            .into_iter()
+            .find(|s| s.skill_id == skill_id && s.agent == agent)
+            .ok_or_else(|| AppError::NotFound(format!("Skill {} not installed for {}", skill_id, agent)))?;
+
+        match &record.source {
+            InstallSource::Registry => {
+                let config = RegistryConfig {
+                    url: record.registry_url.clone(),
+                    name: "".into(),
+                    enabled: true,
+                    auth_token: None,
+                };
+                let registry = self.fetch_registry(&config).await?;
+                let remote = registry.skills.iter()
+                    .find(|s| s.id == skill_id)
+                    .ok_or_else(|| AppError::NotFound(format!("Skill {} not found in {}", skill_id, record.registry_url)))?;
+
+                // Same version when it is still published
+                let version = remote.at_version(&record.version).map(|_| record.version.as_str());
+                self.install_skill(remote, &record.registry_url, agent, version).await
+            }
+            InstallSource::Git(_) => self.update_git_skill(&record).await,
+            InstallSource::Local { path } => {
+                self.install_from_local(path, &[String::new()], agent).await?
+                    .pop()
+                    .ok_or_else(|| AppError::NotFound(path.clone()))
+            }
+            InstallSource::Archive { path } => {
+                // Find the skill's folder inside the archive again
+                let folder = self.discover_local(path, agent).await?
+                    .into_iter()
+                    .find(|d| folder_name(Path::new(&d.path)) == skill_id)
+                    .map(|d| d.path)
+                    .unwrap_or_default();
+
+                self.install_from_local(path, &[folder], agent).await?
+                    .pop()
+                    .ok_or_else(|| AppError::NotFound(path.clone()))
+            }
+        }
+    }
+
+    /// Record an untracked folder as a registry install. The version is the
+    /// release whose content matches; otherwise the skill is recorded at
+    /// 0.0.0 so the newest release is offered as an update.
+    async fn adopt(&self, item: &DriftItem) -> Result<InstalledSkill, AppError> {
+        let registry_url = item.registry_url.as_deref()
+            .ok_or_else(|| AppError::NotFound(format!("No registry offers {}", item.skill_id)))?;
+
+        let config = RegistryConfig {
+            url: registry_url.to_string(),
+            name: "".into(),
+            enabled: true,
+            auth_token: None,
+        };
+        let registry = self.fetch_registry(&config).await?;
+        let remote = registry.skills.iter()
+            .find(|s| s.id == item.skill_id)
+            .ok_or_else(|| AppError::NotFound(format!("Skill {} not found in {}", item.skill_id, registry_url)))?;
+
+        let path = self.get_install_path(&item.agent, &item.skill_id)?;
+        if !path.is_file() {
+            return Err(AppError::FileNotFound(format!(
+                "{} has no {} to adopt",
+                item.path,
+                folder_name(&path)
+            )));
+        }
+
+        let content = fs::read(&path)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+        let hash = DownloadService::checksum(&content);
+
+        let mut matched = None;
+        for release in remote.releases() {
+            let Some(candidate) = remote.at_version(&release.version) else { continue };
+            // Published checksums are of the file before install adds frontmatter
+            let checksum = match &candidate.checksum {
+                Some(checksum) if !Self::rewrites_content(&item.agent) => Some(checksum.clone()),
+                _ => self.fetch_release(&candidate, registry_url, &item.agent)
+                    .await
+                    .ok()
+                    .map(|bytes| DownloadService::checksum(&bytes)),
+            };
+
+            if checksum.is_some_and(|c| c.eq_ignore_ascii_case(&hash)) {
+                matched = Some(candidate.version);
+                break;
+            }
+        }
+
+        let installed = InstalledSkill {
+            skill_id: item.skill_id.clone(),
+            registry_url: registry_url.to_string(),
+            version: matched.clone().unwrap_or_else(|| "0.0.0".to_string()),
+            installed_path: path.to_string_lossy().to_string(),
+            agent: item.agent.clone(),
+            installed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64,
+            source: InstallSource::Registry,
+            commit_sha: None,
+            pinned_version: None,
+            channel: UpdateChannel::default(),
+            // Without a matching release the installed content is unknown
+            content_hash: matched.map(|_| hash),
+            update_policy: UpdatePolicy::default(),
+            mcp_servers: Vec::new(),
+            lockfiles: Vec::new(),
+        };
+
+        self.record_installation(&installed).await?;
+
+        Ok(installed)
+    }
+
+    /// Agents whose installs may differ from the published file
+    fn rewrites_content(agent: &str) -> bool {
+        matches!(
+            AgentType::parse(agent),
+            AgentType::Cursor | AgentType::Copilot | AgentType::Codex | AgentType::Gemini
+        )
+    }
+
+    /// The registry skill a lockfile entry pins, at the locked version and
+    /// checksum. Its name, description and MCP servers come from the registry.
+    async fn locked_skill(
+        &self,
+        locked: &LockedSkill,
+        registries: &mut HashMap<String, SkillRegistry>,
+    ) -> Result<RemoteSkill, AppError> {
+        if !registries.contains_key(&locked.registry) {
+            let config = RegistryConfig {
+                url: locked.registry.clone(),
+                name: "".into(),
+                enabled: true,
+                auth_token: None,
+            };
+            let registry = self.fetch_registry(&config).await?;
+            registries.insert(locked.registry.clone(), registry);
+        }
+
+        let remote = registries[&locked.registry].skills.iter()
+            .find(|s| s.id == locked.id)
+            .ok_or_else(|| AppError::NotFound(format!("Skill {} not found in {}", locked.id, locked.registry)))?;
+        let release = remote.at_version(&locked.version).unwrap_or_else(|| remote.clone());
+
+        Ok(RemoteSkill {
+            version: locked.version.clone(),
+            checksum: Some(locked.checksum.clone()),
+            ..release
+        })
+    }
+
+    /// Download a single skill file, verify it and record the installation
+    async fn install_file(
+        &self,
+        skill: &RemoteSkill,
+        url: &str,
+        registry_url: &str,
+        agent: &str,
+    ) -> Result<InstalledSkill, AppError> {
+        let skill_id = skill.id.as_str();
+        let bytes = self.download.fetch_bytes(url).await?;
+        if let Some(expected) = &skill.checksum {
+            DownloadService::verify_checksum(&bytes, expected, url)?;
+        }
+        let bytes = Self::installed_content(skill, agent, bytes);
+
+        // Determine destination path
+        let dest_path = self.get_install_path(agent, skill_id)?;
+        self.ensure_registry_managed(skill_id, agent, &dest_path).await?;
+
+        // Keep a copy of whatever we are about to replace
+        if dest_path.exists() {
+            self.backup.backup_skill(skill_id, agent, &dest_path.to_string_lossy()).await?;
+        } else if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent)
+                .await
+                .map_err(|e| AppError::IoError(e.to_string()))?;
+        }
+
+        fs::write(&dest_path, &bytes)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        if AgentType::parse(agent) == AgentType::Aider {
+            self.register_aider_read(&dest_path).await?;
+        }
+
+        // Record installation
+        let installed = InstalledSkill {
+            skill_id: skill_id.to_string(),
+            registry_url: registry_url.to_string(),
+            version: skill.version.clone(),
+            installed_path: dest_path.to_string_lossy().to_string(),
+            agent: agent.to_string(),
+            installed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64,
+            source: InstallSource::Registry,
+            commit_sha: None,
+            pinned_version: None,
+            channel: UpdateChannel::default(),
+            content_hash: Some(DownloadService::checksum(&bytes)),
+            update_policy: UpdatePolicy::default(),
+            mcp_servers: Vec::new(),
+            lockfiles: Vec::new(),
+        };
+
+        self.record_installation(&installed).await?;
+
+        Ok(installed)
+    }
+
+    /// Refuse to overwrite a skill of the same name that was not installed
+    /// from a registry, whether recorded from another source or untracked
+    async fn ensure_registry_managed(&self, skill_id: &str, agent: &str, dest_path: &Path) -> Result<(), AppError> {
+        let record = self.get_installed_skills().await
+            .unwrap_or_default()
+            .into_iter()
+            .find(|s| s.skill_id == skill_id && s.agent == agent);
+
+        // Single-file installs share their folder with the user's own files,
+        // so only the file itself can clash
+        let target = if Self::is_file_layout(agent) {
+            dest_path
+        } else {
+            dest_path.parent().unwrap_or(dest_path)
+        };
+        // Uninstalling a registry skill leaves its empty folder behind
+        let occupied = if target.is_dir() {
+            std::fs::read_dir(target).is_ok_and(|mut entries| entries.next().is_some())
+        } else {
+            target.exists()
+        };
+
+        match record {
+            Some(record) if record.source != InstallSource::Registry => Err(AppError::AlreadyExists(format!(
+                "{} is installed for {} from {}; uninstall it first",
+                skill_id, agent, record.registry_url
+            ))),
+            None if occupied => Err(AppError::AlreadyExists(format!(
+                "{} was not installed from a registry; adopt or remove it first",
+                target.display()
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Resolve the download URL of a skill's file for an agent
+    fn resolve_file_url(&self, skill: &RemoteSkill, registry_url: &str, agent: &str) -> Result<String, AppError> {
+        // Get file path for this agent
+        let file_path = self.get_agent_file(skill, agent)
+            .ok_or_else(|| AppError::InvalidPath(
+                format!("Skill {} doesn't support {}", skill.id, agent)
+            ))?;
+
+        Ok(self.resolve_registry_url(registry_url, &file_path))
+    }
+
+    /// Agents a registry skill ships a file for
+    fn supported_agents(&self, skill: &RemoteSkill) -> Vec<String> {
+        INSTALL_AGENTS
+            .iter()
+            .filter(|agent| self.get_agent_file(skill, agent).is_some())
+            .map(|agent| agent.to_string())
+            .collect()
+    }
+
+    async fn install_local_folders(
+        &self,
+        source_path: &Path,
+        root: &Path,
+        is_archive: bool,
+        paths: &[String],
+        agent: &str,
+    ) -> Result<Vec<InstalledSkill>, AppError> {
+        let mut results = Vec::new();
+
+        for path in paths {
+            let folder = SkillService::resolve_within(root, Some(path))?;
+
+            let skill_id = if folder == root && is_archive {
+                ArchiveService::stem(source_path)
+            } else {
+                folder_name(&folder)
+            };
+
+            let source = if is_archive {
+                InstallSource::Archive { path: source_path.to_string_lossy().to_string() }
+            } else {
+                InstallSource::Local { path: folder.to_string_lossy().to_string() }
+            };
+
+            let installed = self.install_folder(&folder, &skill_id, agent, source).await?;
+            self.record_installation(&installed).await?;
+            results.push(installed);
+        }
+
+        Ok(results)
+    }
+
+    /// Validate a skill folder and copy it into the agent's skills directory,
+    /// backing up any existing install first. The caller records the result.
+    async fn install_folder(
+        &self,
+        folder: &Path,
+        skill_id: &str,
+        agent: &str,
+        source: InstallSource,
+    ) -> Result<InstalledSkill, AppError> {
+        let skill = SkillService::parse_skill_folder(folder, &AgentType::parse(agent))?;
+        if skill.entry_file.is_none() {
+            return Err(AppError::InvalidPath(format!(
+                "{} is not a skill folder (no SKILL.md, skill.md, index.md or README.md)",
+                folder.display()
+            )));
+        }
+
+        let dest_dir = self.get_install_dir(agent, skill_id)?;
+        if dest_dir.exists() {
+            self.backup.backup_skill(skill_id, agent, &dest_dir.to_string_lossy()).await?;
+            fs::remove_dir_all(&dest_dir)
+                .await
+                .map_err(|e| AppError::IoError(e.to_string()))?;
+        }
+
+        SkillService::copy_dir_recursive(folder, &dest_dir)?;
+
+        // Don't carry repository metadata into the agent folder
+        let git_dir = dest_dir.join(".git");
+        if git_dir.exists() {
+            fs::remove_dir_all(&git_dir)
+                .await
+                .map_err(|e| AppError::IoError(e.to_string()))?;
+        }
+
+        if AgentType::parse(agent) == AgentType::Aider {
+            let entry_file = SkillService::parse_skill_folder(&dest_dir, &AgentType::Aider)?.entry_file;
+            if let Some(entry_file) = entry_file {
+                self.register_aider_read(Path::new(&entry_file)).await?;
+            }
+        }
+
+        Ok(InstalledSkill {
+            skill_id: skill_id.to_string(),
+            registry_url: installed_source_url(&source),
+            version: skill.version.unwrap_or_else(|| "local".to_string()),
+            installed_path: dest_dir.to_string_lossy().to_string(),
+            agent: agent.to_string(),
+            installed_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs() as i64,
+            source,
+            commit_sha: None,
+            pinned_version: None,
+            channel: UpdateChannel::default(),
+            content_hash: DiffService::content_hash(&dest_dir).ok(),
+            update_policy: UpdatePolicy::default(),
+            mcp_servers: Vec::new(),
+            lockfiles: Vec::new(),
+        })
+    }
+
+    async fn read_local_registry(&self, path: &Path, url: &str) -> Result<SkillRegistry, AppError> {
+        let manifest = if path.is_dir() {
+            path.join("registry.json")
+        } else {
+            path.to_path_buf()
+        };
+
+        let content = fs::read_to_string(&manifest)
+            .await
+            .map_err(|_| AppError::FileNotFound(manifest.to_string_lossy().to_string()))?;
+
+        let mut registry: SkillRegistry = serde_json::from_str(&content)
+            .or_else(|_| serde_yaml::from_str(&content))
+            .map_err(|e| AppError::ParseError(format!("Invalid registry format: {}", e)))?;
+
+        registry.url = url.to_string();
+        registry.last_updated = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs() as i64;
+
+        Ok(registry)
+    }
+
     fn get_agent_file(&self, skill: &RemoteSkill, agent: &str) -> Option<String> {
         match agent.to_lowercase().as_str() {
             "claude" => skill.files.claude.clone(),
@@ -194,27 +1332,109 @@ impl RegistryService {
             "continuedev" | "continue" => skill.files.continue_dev.clone(),
             "aider" => skill.files.aider.clone(),
             "windsurf" | "codeium" => skill.files.windsurf.clone(),
+            "copilot" | "github-copilot" => skill.files.copilot.clone(),
+            "codex" => skill.files.codex.clone(),
+            "gemini" | "gemini-cli" => skill.files.gemini.clone(),
+            "cline" => skill.files.cline.clone(),
             _ => None,
         }
     }
 
     fn get_install_path(&self, agent: &str, skill_id: &str) -> Result<PathBuf, AppError> {
+        // Windsurf only reads workflows directly inside global_workflows
+        if Self::is_file_layout(agent) {
+            return Ok(Self::windsurf_workflows_dir()?.join(format!("{}.md", skill_id)));
+        }
+
+        let dir = self.get_install_dir(agent, skill_id)?;
+
+        // Create skill folder (not just a file)
+        let path = match agent.to_lowercase().as_str() {
+            "claude" => dir.join("skill.md"),
+            "cursor" => dir.join(format!("{}.mdc", skill_id)),
+            "continuedev" | "continue" => dir.join("skill.json"),
+            "aider" => dir.join(aider_service::CONVENTIONS_FILE),
+            // Agent Skills folders
+            "copilot" | "github-copilot" | "codex" | "gemini" | "gemini-cli" => dir.join("SKILL.md"),
+            "cline" => dir.join(format!("{}.md", skill_id)),
+            _ => return Err(AppError::InvalidPath(format!("Unknown agent: {}", agent))),
+        };
+
+        Ok(path)
+    }
+
+    /// Agents whose installs are single files in a folder shared with other
+    /// skills, rather than a folder of their own
+    fn is_file_layout(agent: &str) -> bool {
+        matches!(agent.to_lowercase().as_str(), "windsurf" | "codeium")
+    }
+
+    fn get_install_dir(&self, agent: &str, skill_id: &str) -> Result<PathBuf, AppError> {
         let home = dirs::home_dir()
             .ok_or_else(|| AppError::InvalidPath("Cannot find home directory".into()))?;
 
-        // Create skill folder (not just a file)
         let path = match agent.to_lowercase().as_str() {
-            "claude" => home.join(".claude").join("skills").join(skill_id).join("skill.md"),
-            "cursor" => home.join(".cursor").join("skills").join(skill_id).join("skill.cursorrules"),
-            "continuedev" | "continue" => home.join(".continue").join("skills").join(skill_id).join("skill.json"),
-            "aider" => home.join(".aider").join("skills").join(skill_id).join("skill.txt"),
-            "windsurf" | "codeium" => home.join(".codeium").join("skills").join(skill_id).join("skill.yaml"),
+            "claude" => home.join(".claude").join("skills").join(skill_id),
+            "cursor" => RuleService::rules_dir(&home).join(skill_id),
+            "continuedev" | "continue" => home.join(".continue").join("skills").join(skill_id),
+            "aider" => AiderService::conventions_dir(&home).join(skill_id),
+            "windsurf" | "codeium" => {
+                return Err(AppError::InvalidPath(format!(
+                    "Windsurf workflows are single files; install {} from a registry instead",
+                    skill_id
+                )));
+            }
+            "copilot" | "github-copilot" => home.join(".copilot").join("skills").join(skill_id),
+            "codex" => home.join(".codex").join("skills").join(skill_id),
+            "gemini" | "gemini-cli" => home.join(".gemini").join("skills").join(skill_id),
+            "cline" => home.join("Documents").join("Cline").join("Rules").join(skill_id),
             _ => return Err(AppError::InvalidPath(format!("Unknown agent: {}", agent))),
         };
 
         Ok(path)
     }
 
+    /// List an Aider install in `read:` of `~/.aider.conf.yml` so Aider
+    /// loads it, backing the config up first
+    async fn register_aider_read(&self, file: &Path) -> Result<(), AppError> {
+        let config = Self::aider_config_path()?;
+        if AiderService::is_listed(&config, file) {
+            return Ok(());
+        }
+
+        if config.exists() {
+            self.backup.backup_file(&config.to_string_lossy()).await?;
+        }
+        AiderService::add_read(&config, file)?;
+        Ok(())
+    }
+
+    /// Drop an uninstalled file or folder from `read:` of `~/.aider.conf.yml`
+    async fn unregister_aider_read(&self, path: &Path) -> Result<(), AppError> {
+        let config = Self::aider_config_path()?;
+        if !config.exists() {
+            return Ok(());
+        }
+
+        self.backup.backup_file(&config.to_string_lossy()).await?;
+        AiderService::remove_read(&config, path)?;
+        Ok(())
+    }
+
+    fn aider_config_path() -> Result<PathBuf, AppError> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| AppError::InvalidPath("Cannot find home directory".into()))?;
+
+        Ok(home.join(aider_service::CONFIG_FILE))
+    }
+
+    fn windsurf_workflows_dir() -> Result<PathBuf, AppError> {
+        let home = dirs::home_dir()
+            .ok_or_else(|| AppError::InvalidPath("Cannot find home directory".into()))?;
+
+        Ok(WindsurfService::global_workflows_dir(&home.join(".codeium").join("windsurf")))
+    }
+
     async fn read_cache(&self, path: &PathBuf) -> Result<SkillRegistry, AppError> {
         let content = fs::read_to_string(path)
             .await
@@ -224,44 +1444,61 @@ impl RegistryService {
             .map_err(|e| AppError::ParseError(e.to_string()))
     }
 
-    async fn write_cache(&self, path: &PathBuf, registry: &SkillRegistry) -> Result<(), AppError> {
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .await
-                .map_err(|e| AppError::IoError(e.to_string()))?;
-        }
-
+    async fn write_cache(&self, path: &Path, registry: &SkillRegistry) -> Result<(), AppError> {
         let content = serde_json::to_string_pretty(registry)
             .map_err(|e| AppError::ParseError(e.to_string()))?;
 
-        fs::write(path, content)
-            .await
-            .map_err(|e| AppError::IoError(e.to_string()))
+        StateService::write_atomic(path, content.as_bytes()).await
     }
 
-    async fn record_installation(&self, skill: &InstalledSkill) -> Result<(), AppError> {
-        let mut installed = self.get_installed_skills().await.unwrap_or_default();
+    /// Record several installations with a single write of the installed database
+    pub async fn record_installations(&self, skills: &[InstalledSkill]) -> Result<(), AppError> {
+        self.state.update(|InstalledDb(installed): &mut InstalledDb| {
+            for skill in skills {
+                let mut record = skill.clone();
+
+                // Reinstalls keep the user's update preferences
+                if let Some(previous) = installed.iter().find(|s| s.skill_id == skill.skill_id && s.agent == skill.agent) {
+                    record.pinned_version = previous.pinned_version.clone();
+                    record.channel = previous.channel.clone();
+                    record.update_policy = previous.update_policy.clone();
+                }
 
-        // Remove existing entry for same skill+agent
-        installed.retain(|s| !(s.skill_id == skill.skill_id && s.agent == skill.agent));
-        installed.push(skill.clone());
+                // Remove existing entry for same skill+agent
+                installed.retain(|s| !(s.skill_id == skill.skill_id && s.agent == skill.agent));
+                installed.push(record);
+            }
 
-        self.save_installed_skills(&installed).await
+            Ok(())
+        }).await
     }
 
-    async fn save_installed_skills(&self, skills: &[InstalledSkill]) -> Result<(), AppError> {
-        if let Some(parent) = self.installed_db_path.parent() {
-            fs::create_dir_all(parent)
-                .await
-                .map_err(|e| AppError::IoError(e.to_string()))?;
-        }
+    async fn record_installation(&self, skill: &InstalledSkill) -> Result<(), AppError> {
+        self.record_installations(std::slice::from_ref(skill)).await
+    }
 
-        let content = serde_json::to_string_pretty(skills)
-            .map_err(|e| AppError::ParseError(e.to_string()))?;
+    async fn update_record<F>(&self, skill_id: &str, agent: &str, change: F) -> Result<InstalledSkill, AppError>
+    where
+        F: FnOnce(&mut InstalledSkill),
+    {
+        self.state.update(|InstalledDb(installed): &mut InstalledDb| {
+            let skill = installed.iter_mut()
+                .find(|s| s.skill_id == skill_id && s.agent == agent)
+                .ok_or_else(|| AppError::NotFound(
+                    format!("Skill {} not installed for {}", skill_id, agent)
+                ))?;
 
-        fs::write(&self.installed_db_path, content)
-            .await
-            .map_err(|e| AppError::IoError(e.to_string()))
+            change(skill);
+            Ok(skill.clone())
+        }).await
+    }
+
+    /// Drop the install record of a skill
+    async fn forget(&self, skill_id: &str, agent: &str) -> Result<(), AppError> {
+        self.state.update(|InstalledDb(installed): &mut InstalledDb| {
+            installed.retain(|s| !(s.skill_id == skill_id && s.agent == agent));
+            Ok(())
+        }).await
     }
 }
 
@@ -271,6 +1508,82 @@ impl Default for RegistryService {
     }
 }
 
+async fn read_project_manifest(project_dir: &Path) -> Result<ProjectManifest, AppError> {
+    let path = project_dir.join(PROJECT_MANIFEST_FILE);
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|_| AppError::FileNotFound(path.to_string_lossy().to_string()))?;
+
+    toml::from_str(&content)
+        .map_err(|e| AppError::ParseError(format!("Invalid {}: {}", PROJECT_MANIFEST_FILE, e)))
+}
+
+/// How installs refer to a project's lockfile
+fn lockfile_key(project_dir: &Path) -> String {
+    project_dir.canonicalize()
+        .unwrap_or_else(|_| project_dir.to_path_buf())
+        .join(PROJECT_LOCK_FILE)
+        .to_string_lossy()
+        .to_string()
+}
+
+async fn read_lockfile(project_dir: &Path) -> Result<Lockfile, AppError> {
+    let path = project_dir.join(PROJECT_LOCK_FILE);
+    let content = fs::read_to_string(&path)
+        .await
+        .map_err(|_| AppError::FileNotFound(format!(
+            "{} (run lock first)",
+            path.to_string_lossy()
+        )))?;
+
+    let lockfile: Lockfile = toml::from_str(&content)
+        .map_err(|e| AppError::ParseError(format!("Invalid {}: {}", PROJECT_LOCK_FILE, e)))?;
+
+    if lockfile.version > LOCKFILE_VERSION {
+        return Err(AppError::ParseError(format!(
+            "Unsupported lockfile version {}",
+            lockfile.version
+        )));
+    }
+
+    Ok(lockfile)
+}
+
+async fn write_lockfile(project_dir: &Path, lockfile: &Lockfile) -> Result<(), AppError> {
+    let content = toml::to_string_pretty(lockfile)
+        .map_err(|e| AppError::ParseError(e.to_string()))?;
+
+    let header = "# Generated by AI Skills Aggregator. Do not edit by hand.\n\n";
+    fs::write(project_dir.join(PROJECT_LOCK_FILE), format!("{}{}", header, content))
+        .await
+        .map_err(|e| AppError::IoError(e.to_string()))
+}
+
+/// Whether the recorded file, or the entry file of a recorded folder, exists
+fn has_skill_content(skill: &InstalledSkill, path: &Path) -> bool {
+    match skill.source {
+        InstallSource::Registry => path.is_file(),
+        _ => SkillService::parse_skill_folder(path, &AgentType::parse(&skill.agent))
+            .is_ok_and(|s| s.entry_file.is_some()),
+    }
+}
+
+fn folder_name(path: &Path) -> String {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("skill")
+        .to_string()
+}
+
+/// URL or path recorded as `registry_url` for non-registry installs
+fn installed_source_url(source: &InstallSource) -> String {
+    match source {
+        InstallSource::Registry => String::new(),
+        InstallSource::Git(git) => git.url.clone(),
+        InstallSource::Local { path } | InstallSource::Archive { path } => path.clone(),
+    }
+}
+
 fn url_to_filename(url: &str) -> String {
     let hash = format!("{:x}", md5::compute(url.as_bytes()));
     format!("{}.json", &hash[..16])
@@ -0,0 +1,236 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use glob::glob;
+
+use crate::models::{AgentType, AppError, CursorRule, RuleMode, Skill};
+use crate::services::{SkillService, TemplateService};
+
+/// Frontmatter keys managed through `CursorRule`
+const RULE_KEYS: [&str; 3] = ["description", "globs", "alwaysApply"];
+
+/// Cursor `.mdc` project rules
+pub struct RuleService;
+
+impl RuleService {
+    /// Rules directory of a home or project root
+    pub fn rules_dir(root: &Path) -> PathBuf {
+        root.join(".cursor").join("rules")
+    }
+
+    /// Split an `.mdc` file into its rule settings and body. Files without
+    /// frontmatter are manual rules.
+    pub fn parse(content: &str) -> (CursorRule, &str) {
+        let Some((lines, body)) = split_frontmatter(content) else {
+            return (CursorRule::default(), content);
+        };
+
+        let mut rule = CursorRule::default();
+        for (key, values) in fields(&lines) {
+            match key {
+                "description" => {
+                    rule.description = Some(unquote(&values.join(" ")).to_string())
+                        .filter(|d| !d.is_empty());
+                }
+                "globs" => {
+                    rule.globs = values.iter()
+                        .flat_map(|v| v.trim_start_matches('[').trim_end_matches(']').split(','))
+                        .map(|glob| unquote(glob).to_string())
+                        .filter(|glob| !glob.is_empty())
+                        .collect();
+                }
+                "alwaysApply" => {
+                    rule.always_apply = values.first().is_some_and(|v| v.eq_ignore_ascii_case("true"));
+                }
+                _ => {}
+            }
+        }
+
+        (rule, body)
+    }
+
+    /// An `.mdc` file with the rule's frontmatter followed by `body`
+    pub fn render(rule: &CursorRule, body: &str) -> String {
+        render_with(rule, &[], body)
+    }
+
+    /// Rule settings for an attachment mode, checking the mode has what it needs
+    pub fn rule_for_mode(
+        mode: RuleMode,
+        description: Option<String>,
+        globs: Vec<String>,
+    ) -> Result<CursorRule, AppError> {
+        let description = description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        let globs: Vec<String> = globs.into_iter()
+            .map(|g| g.trim().to_string())
+            .filter(|g| !g.is_empty())
+            .collect();
+
+        let rule = match mode {
+            RuleMode::Always => CursorRule { description, globs: Vec::new(), always_apply: true },
+            RuleMode::AutoAttached if globs.is_empty() => {
+                return Err(AppError::ParseError("Auto-attached rules need at least one glob".into()));
+            }
+            RuleMode::AutoAttached => CursorRule { description, globs, always_apply: false },
+            RuleMode::AgentRequested if description.is_none() => {
+                return Err(AppError::ParseError("Agent-requested rules need a description".into()));
+            }
+            RuleMode::AgentRequested => CursorRule { description, globs: Vec::new(), always_apply: false },
+            RuleMode::Manual => CursorRule::default(),
+        };
+
+        Ok(rule)
+    }
+
+    /// All rules in a root's `.cursor/rules`, including its subfolders
+    pub fn scan_rules(root: &Path) -> Vec<Skill> {
+        let pattern = Self::rules_dir(root).join("**").join("*.mdc");
+
+        let mut rules: Vec<Skill> = match glob(&pattern.to_string_lossy()) {
+            Ok(entries) => entries.flatten()
+                .filter(|path| path.is_file())
+                .filter_map(|path| SkillService::parse_single_file(&path, &AgentType::Cursor).ok())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+
+        rules.sort_by(|a, b| a.folder_path.cmp(&b.folder_path));
+        rules
+    }
+
+    /// Change how a rule is attached, keeping its body and any other
+    /// frontmatter keys
+    pub fn set_mode(
+        file_path: &str,
+        mode: RuleMode,
+        description: Option<String>,
+        globs: Vec<String>,
+    ) -> Result<Skill, AppError> {
+        let path = Path::new(file_path);
+        if !path.is_file() {
+            return Err(AppError::FileNotFound(file_path.to_string()));
+        }
+
+        let rule = Self::rule_for_mode(mode, description, globs)?;
+        let content = fs::read_to_string(path)?;
+
+        let updated = match split_frontmatter(&content) {
+            Some((lines, body)) => render_with(&rule, &other_lines(&lines, &RULE_KEYS), body),
+            None => Self::render(&rule, &content),
+        };
+
+        fs::write(path, updated)?;
+        SkillService::parse_single_file(path, &AgentType::Cursor)
+    }
+
+    /// Create `<rules_dir>/<name>.mdc`, using the rule template when no
+    /// content is given
+    pub fn create_rule(
+        rules_dir: &Path,
+        name: &str,
+        rule: &CursorRule,
+        content: Option<&str>,
+    ) -> Result<Skill, AppError> {
+        let file_name = format!("{}.mdc", name.trim().to_lowercase().replace(' ', "-"));
+        let path = rules_dir.join(&file_name);
+
+        if path.exists() {
+            return Err(AppError::AlreadyExists(path.to_string_lossy().to_string()));
+        }
+
+        let body = match content {
+            Some(content) => content.to_string(),
+            None => TemplateService::cursor_rule_body(name),
+        };
+
+        fs::create_dir_all(rules_dir)?;
+        fs::write(&path, Self::render(rule, &body))?;
+
+        SkillService::parse_single_file(&path, &AgentType::Cursor)
+    }
+}
+
+/// Frontmatter lines and the body after the closing `---`
+pub(crate) fn split_frontmatter(content: &str) -> Option<(Vec<&str>, &str)> {
+    let rest = content.trim_start_matches('\u{feff}');
+    let rest = rest.strip_prefix("---")?;
+    let rest = rest.strip_prefix("\r\n").or_else(|| rest.strip_prefix('\n'))?;
+
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        offset += line.len();
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if trimmed == "---" {
+            return Some((lines, &rest[offset..]));
+        }
+        lines.push(trimmed);
+    }
+
+    None
+}
+
+/// `key: value` pairs of frontmatter lines. Indented lines and `- item`
+/// lines continue the previous key.
+pub(crate) fn fields<'a>(lines: &[&'a str]) -> Vec<(&'a str, Vec<&'a str>)> {
+    let mut fields: Vec<(&str, Vec<&str>)> = Vec::new();
+
+    for line in lines {
+        let continues = line.starts_with([' ', '\t']) || line.trim_start().starts_with("- ");
+        match (continues, fields.last_mut()) {
+            (true, Some((_, values))) => {
+                let value = line.trim().trim_start_matches("- ").trim();
+                if !value.is_empty() {
+                    values.push(value);
+                }
+            }
+            _ => {
+                if let Some((key, value)) = line.split_once(':') {
+                    let value = value.trim();
+                    let values = if value.is_empty() { Vec::new() } else { vec![value] };
+                    fields.push((key.trim(), values));
+                }
+            }
+        }
+    }
+
+    fields
+}
+
+/// Frontmatter lines that don't belong to one of the managed `keys`
+pub(crate) fn other_lines<'a>(lines: &[&'a str], keys: &[&str]) -> Vec<&'a str> {
+    let mut kept = Vec::new();
+    let mut managed = false;
+
+    for line in lines {
+        let continues = line.starts_with([' ', '\t']) || line.trim_start().starts_with("- ");
+        if !continues {
+            managed = line.split_once(':').is_some_and(|(key, _)| keys.contains(&key.trim()));
+        }
+        if !managed {
+            kept.push(*line);
+        }
+    }
+
+    kept
+}
+
+fn render_with(rule: &CursorRule, extra: &[&str], body: &str) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("description: {}\n", rule.description.as_deref().unwrap_or("")));
+    out.push_str(&format!("globs: {}\n", rule.globs.join(",")));
+    out.push_str(&format!("alwaysApply: {}\n", rule.always_apply));
+    for line in extra {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str("---\n");
+    out.push_str(body);
+    out
+}
+
+pub(crate) fn unquote(value: &str) -> &str {
+    let value = value.trim();
+    value.strip_prefix('"').and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+}
@@ -0,0 +1,295 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::models::{InstalledSkill, InstallSource, RegistryConfig, UpdatePolicy, AppError};
+use crate::services::registry_service::RegistryService;
+use crate::services::state_service::{StateFile, StateService};
+use crate::services::update_service::{UpdateService, SkillUpdate, UpdateCheckResult};
+use crate::services::version_service::VersionService;
+
+/// How often the background loop wakes up to see whether a check is due
+pub const SCHEDULER_TICK: Duration = Duration::from_secs(60);
+
+const DEFAULT_INTERVAL_MINUTES: u64 = 360;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerSettings {
+    pub enabled: bool,
+    /// Minutes between background update checks
+    pub interval_minutes: u64,
+}
+
+impl Default for SchedulerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval_minutes: DEFAULT_INTERVAL_MINUTES,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailedUpdate {
+    pub update: SkillUpdate,
+    pub error: String,
+}
+
+/// Summary of one scheduled check, also emitted to the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchedulerRun {
+    pub started_at: i64,
+    /// Updates not reported by the previous check
+    pub new_updates: Vec<SkillUpdate>,
+    /// Updates applied automatically by skill policy
+    pub applied: Vec<SkillUpdate>,
+    pub failed: Vec<FailedUpdate>,
+}
+
+/// Persisted scheduler settings and the outcome of the last check
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct SchedulerState {
+    #[serde(default)]
+    pub settings: SchedulerSettings,
+    #[serde(default)]
+    pub last_checked: Option<i64>,
+    /// Updates still available after the last run
+    #[serde(default)]
+    pub last_result: Option<UpdateCheckResult>,
+    #[serde(default)]
+    pub last_run: Option<SchedulerRun>,
+}
+
+/// Periodic update checks that auto-apply updates allowed by each skill's
+/// update policy
+pub struct SchedulerService {
+    updates: UpdateService,
+    registry: RegistryService,
+    state: StateService,
+}
+
+impl StateFile for SchedulerState {
+    const NAME: &'static str = "update-scheduler.json";
+    // 0: the same state without the envelope
+    const SCHEMA: u32 = 1;
+}
+
+impl SchedulerService {
+    pub fn new() -> Self {
+        Self::with_state(StateService::new())
+    }
+
+    /// A service sharing the given state store
+    pub fn with_state(state: StateService) -> Self {
+        Self {
+            updates: UpdateService::with_state(state.clone()),
+            registry: RegistryService::with_state(state.clone()),
+            state,
+        }
+    }
+
+    pub async fn get_state(&self) -> SchedulerState {
+        match self.state.read::<SchedulerState>().await {
+            Ok(state) => state,
+            Err(e) => {
+                log::warn!("Failed to read update scheduler state: {}", e);
+                SchedulerState::default()
+            }
+        }
+    }
+
+    pub async fn set_settings(&self, settings: SchedulerSettings) -> Result<SchedulerState, AppError> {
+        if settings.interval_minutes == 0 {
+            return Err(AppError::ParseError("Update check interval must be at least one minute".into()));
+        }
+
+        self.state.update(|state: &mut SchedulerState| {
+            state.settings = settings;
+            Ok(state.clone())
+        }).await
+    }
+
+    /// Run a check if the scheduler is enabled and the interval has elapsed
+    pub async fn run_if_due(&self) -> Result<Option<SchedulerRun>, AppError> {
+        let state = self.get_state().await;
+        if !state.settings.enabled {
+            return Ok(None);
+        }
+
+        let interval = state.settings.interval_minutes as i64 * 60;
+        let due = state.last_checked
+            .map_or(true, |last| Utc::now().timestamp() - last >= interval);
+
+        if !due {
+            return Ok(None);
+        }
+
+        self.run_now().await.map(Some)
+    }
+
+    /// Check for updates, auto-apply those allowed by policy and persist the result
+    pub async fn run_now(&self) -> Result<SchedulerRun, AppError> {
+        let started_at = Utc::now().timestamp();
+        let state = self.get_state().await;
+
+        // Skipped versions are already filtered out by the check
+        let mut result = self.updates.check_all_updates().await;
+        if let Some(error) = &result.error {
+            log::warn!("Scheduled update check failed: {}", error);
+        }
+
+        // Auto-apply policies only cover registry versions; git commits are notify-only
+        let installed: HashMap<(String, String), InstalledSkill> = self.registry
+            .get_installed_skills()
+            .await?
+            .into_iter()
+            .filter(|s| s.source == InstallSource::Registry)
+            .map(|s| ((s.skill_id.clone(), s.agent.clone()), s))
+            .collect();
+
+        let previous = state.last_result.as_ref()
+            .map(|r| r.available_updates.as_slice())
+            .unwrap_or_default();
+
+        let mut run = SchedulerRun {
+            started_at,
+            new_updates: result.available_updates.iter()
+                .filter(|u| !previous.iter().any(|p| same_update(p, u)))
+                .cloned()
+                .collect(),
+            applied: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        let mut remaining = Vec::new();
+        for update in result.available_updates {
+            let skill = installed.get(&(update.skill_id.clone(), update.agent.clone()));
+            let Some(target) = self.policy_target(skill, &update).await else {
+                remaining.push(update);
+                continue;
+            };
+
+            // Installs back up the current content; conflicting local edits fail here
+            match self.updates.apply_update(&target, &HashMap::new()).await {
+                Ok(()) => {
+                    log::info!("Auto-updated {} ({}) to {}", target.skill_id, target.agent, target.new_version);
+
+                    // A newer version outside the policy is still on offer
+                    if target.new_version != update.new_version {
+                        remaining.push(SkillUpdate {
+                            current_version: target.new_version.clone(),
+                            ..update
+                        });
+                    }
+                    run.applied.push(target);
+                }
+                Err(e) => {
+                    log::warn!("Auto-update of {} failed: {}", target.skill_id, e);
+                    run.failed.push(FailedUpdate {
+                        update: target,
+                        error: e.to_string(),
+                    });
+                    remaining.push(update);
+                }
+            }
+        }
+
+        result.available_updates = remaining;
+
+        // Settings may have changed while the check ran
+        self.state.update(|state: &mut SchedulerState| {
+            state.last_checked = Some(result.last_checked);
+            state.last_result = Some(result);
+            state.last_run = Some(run.clone());
+            Ok(())
+        }).await?;
+
+        Ok(run)
+    }
+
+    // Internal helpers
+
+    /// The update to apply automatically under the skill's policy, if any.
+    /// When the newest version is outside the policy, the newest release
+    /// inside it is chosen instead.
+    async fn policy_target(&self, skill: Option<&InstalledSkill>, update: &SkillUpdate) -> Option<SkillUpdate> {
+        let skill = skill?;
+        if !matches!(skill.update_policy, UpdatePolicy::AutoPatch | UpdatePolicy::AutoMinor) {
+            return None;
+        }
+
+        if within_policy(&skill.update_policy, &skill.version, &update.new_version) {
+            return Some(update.clone());
+        }
+
+        let config = RegistryConfig {
+            url: update.registry_url.clone(),
+            name: "".into(),
+            enabled: true,
+            auth_token: None,
+        };
+
+        let registry = match self.registry.fetch_registry(&config).await {
+            Ok(registry) => registry,
+            Err(e) => {
+                log::warn!("Failed to fetch {}: {}", update.registry_url, e);
+                return None;
+            }
+        };
+
+        let remote = registry.skills.iter().find(|s| s.id == update.skill_id)?;
+
+        RegistryService::releases_between(remote, &skill.version, &update.new_version)
+            .into_iter()
+            .find(|release| {
+                within_policy(&skill.update_policy, &skill.version, &release.version)
+                    && VersionService::is_update_allowed(
+                        &skill.version,
+                        &release.version,
+                        skill.pinned_version.as_deref(),
+                        &skill.channel,
+                    )
+            })
+            .map(|release| SkillUpdate {
+                new_version: release.version,
+                changelog: release.changelog,
+                is_major: false,
+                ..update.clone()
+            })
+    }
+}
+
+impl Default for SchedulerService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SchedulerRun {
+    /// Whether there is anything worth telling the user about
+    pub fn has_changes(&self) -> bool {
+        !self.new_updates.is_empty() || !self.applied.is_empty() || !self.failed.is_empty()
+    }
+}
+
+fn same_update(a: &SkillUpdate, b: &SkillUpdate) -> bool {
+    a.skill_id == b.skill_id && a.agent == b.agent && a.new_version == b.new_version
+}
+
+/// Whether `current` -> `new` is a patch (AutoPatch) or minor/patch
+/// (AutoMinor) update
+fn within_policy(policy: &UpdatePolicy, current: &str, new: &str) -> bool {
+    let (Some(current), Some(new)) = (
+        VersionService::parse_version(current),
+        VersionService::parse_version(new),
+    ) else {
+        return false;
+    };
+
+    match policy {
+        UpdatePolicy::AutoPatch => new.major == current.major && new.minor == current.minor,
+        UpdatePolicy::AutoMinor => new.major == current.major,
+        UpdatePolicy::Notify | UpdatePolicy::Pinned => false,
+    }
+}
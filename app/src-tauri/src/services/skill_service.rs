@@ -1,52 +1,297 @@
-use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
+use std::path::{Component, Path, PathBuf};
 use std::fs;
+use std::time::UNIX_EPOCH;
+use futures::future;
+use futures::stream::{self, StreamExt};
 use glob::glob;
+use serde::{Deserialize, Serialize};
 
-use crate::models::{AgentConfig, AgentType, Skill, SkillFile, SkillFormat, AppError};
+use crate::models::{AgentConfig, AgentType, Skill, SkillFile, SkillFormat, DiscoveredSkill, AppError, RuleMode, SkillKind, ContinueEntry};
+use crate::services::rule_service::split_frontmatter;
+use crate::services::{AiderService, ClaudeService, ContinueService, ConvertService, DownloadService, RuleService, StateService, WindsurfService};
+
+/// Skill folders parsed at the same time while scanning
+const SCAN_CONCURRENCY: usize = 16;
+
+/// Bumped whenever the index or `Skill` changes shape; older indexes are discarded
+const SCAN_INDEX_VERSION: u32 = 5;
+
+/// Whole-repository instruction files read by Copilot, Codex and Gemini
+const INSTRUCTION_FILES: [&str; 4] = ["copilot-instructions.md", "AGENTS.md", "AGENTS.override.md", "GEMINI.md"];
+
+/// Parsed skill folders from previous scans, keyed by folder path. A folder
+/// is only parsed again when the fingerprint of its files changes.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ScanIndex {
+    version: u32,
+    folders: HashMap<String, ScanEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ScanEntry {
+    /// Latest modification time of the folder and its files, in seconds
+    modified: i64,
+    /// Total size of the folder's files
+    size: u64,
+    /// Checksum of every file's path, size and modification time
+    hash: String,
+    skill: Skill,
+}
+
+impl ScanIndex {
+    /// Default location in the app cache directory
+    pub fn default_path() -> PathBuf {
+        dirs::cache_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ai-skills-aggregator")
+            .join("scan-index.json")
+    }
+
+    /// Load an index, starting empty when it is missing, unreadable or
+    /// written by another index version
+    pub async fn load(path: &Path) -> Self {
+        let index = match tokio::fs::read_to_string(path).await {
+            Ok(content) => serde_json::from_str::<ScanIndex>(&content).ok(),
+            Err(_) => None,
+        };
+
+        match index {
+            Some(index) if index.version == SCAN_INDEX_VERSION => index,
+            _ => Self::default(),
+        }
+    }
+
+    pub async fn save(&self, path: &Path) -> Result<(), AppError> {
+        let content = serde_json::to_vec(self)?;
+
+        StateService::write_atomic(path, &content).await
+    }
+
+    pub fn len(&self) -> usize {
+        self.folders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.folders.is_empty()
+    }
+}
 
 pub struct SkillService;
 
 impl SkillService {
-    /// Scan all enabled agents for skills
-    pub fn scan_all_skills(configs: &[AgentConfig]) -> Result<Vec<Skill>, AppError> {
+    /// Scan all enabled agents for skills. Agents and skill folders are
+    /// scanned in parallel off the async runtime; `on_found` receives each
+    /// batch of skills as it is parsed, before the full list is returned.
+    /// Unchanged folders are taken from `index`, which is updated in place.
+    pub async fn scan_all_skills<F>(configs: &[AgentConfig], index: &mut ScanIndex, on_found: F) -> Vec<Skill>
+    where
+        F: Fn(&[Skill]),
+    {
+        let listings = future::join_all(configs.iter().filter(|c| c.enabled).cloned().map(|config| {
+            tokio::task::spawn_blocking(move || {
+                let folders = Self::skill_folders(&config);
+                let files = Self::scan_config_files(&config);
+                (config.agent, folders, files)
+            })
+        })).await;
+
         let mut all_skills = Vec::new();
+        let mut folders = Vec::new();
 
-        for config in configs.iter().filter(|c| c.enabled) {
-            match Self::scan_agent_skills(config) {
-                Ok(skills) => all_skills.extend(skills),
-                Err(e) => {
-                    log::warn!("Failed to scan skills for {:?}: {}", config.agent, e);
+        for listing in listings {
+            match listing {
+                Ok((agent, agent_folders, files)) => {
+                    if !files.is_empty() {
+                        on_found(&files);
+                    }
+                    all_skills.extend(files);
+                    folders.extend(agent_folders.into_iter().map(|folder| (agent.clone(), folder)));
                 }
+                Err(e) => log::warn!("Skill scan task failed: {}", e),
+            }
+        }
+
+        // Folders no longer on disk drop out of the index
+        let mut previous = std::mem::take(&mut index.folders);
+        index.version = SCAN_INDEX_VERSION;
+
+        let tasks = folders.into_iter().map(|(agent, folder)| {
+            let cached = previous.remove(folder.to_string_lossy().as_ref());
+            tokio::task::spawn_blocking(move || Self::scan_folder(&folder, &agent, cached))
+        });
+
+        let mut batches = stream::iter(tasks)
+            .buffer_unordered(SCAN_CONCURRENCY)
+            .ready_chunks(SCAN_CONCURRENCY);
+
+        while let Some(batch) = batches.next().await {
+            let entries: Vec<ScanEntry> = batch.into_iter()
+                .filter_map(|scanned| scanned.ok().flatten())
+                .collect();
+
+            let skills: Vec<Skill> = entries.iter().map(|e| e.skill.clone()).collect();
+            for entry in entries {
+                index.folders.insert(entry.skill.folder_path.clone(), entry);
+            }
+
+            if !skills.is_empty() {
+                on_found(&skills);
+                all_skills.extend(skills);
             }
         }
 
-        Ok(all_skills)
+        // Folders finish in any order; keep the result stable between scans
+        all_skills.sort_by(|a, b| a.folder_path.cmp(&b.folder_path));
+        all_skills
     }
 
     /// Scan skills for a specific agent
     pub fn scan_agent_skills(config: &AgentConfig) -> Result<Vec<Skill>, AppError> {
+        let mut skills: Vec<Skill> = Self::skill_folders(config)
+            .iter()
+            .filter_map(|path| Self::parse_skill_folder(path, &config.agent).ok())
+            .collect();
+
+        skills.extend(Self::scan_config_files(config));
+
+        Ok(skills)
+    }
+
+    /// Reuse the indexed skill for a folder whose files are unchanged,
+    /// otherwise parse it again
+    fn scan_folder(folder: &Path, agent: &AgentType, cached: Option<ScanEntry>) -> Option<ScanEntry> {
+        let (modified, size, hash) = folder_fingerprint(folder)?;
+
+        if let Some(entry) = cached {
+            if entry.hash == hash && entry.skill.agent == *agent {
+                return Some(entry);
+            }
+        }
+
+        let skill = Self::parse_skill_folder(folder, agent).ok()?;
+        Some(ScanEntry { modified, size, hash, skill })
+    }
+
+    /// Skills an agent keeps inside a project, found with the configs'
+    /// project patterns. `SKILL.md` matches are read as skill folders.
+    pub fn scan_project_skills(project_dir: &Path, configs: &[AgentConfig]) -> Vec<Skill> {
         let mut skills = Vec::new();
+        let mut seen = HashSet::new();
 
-        // Scan skills directory - each subdirectory is a skill
-        if let Some(skills_dir) = &config.skills_dir {
-            let skills_path = Path::new(skills_dir);
-            if skills_path.exists() {
-                if let Ok(entries) = fs::read_dir(skills_path) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if path.is_dir() {
-                            // Each directory is a skill folder
-                            if let Ok(skill) = Self::parse_skill_folder(&path, &config.agent) {
-                                skills.push(skill);
-                            }
-                        }
-                        // Skip loose files in skills directory - only folders are skills
+        for config in configs.iter().filter(|c| c.enabled) {
+            for pattern in &config.project_patterns {
+                let Ok(entries) = glob(&project_dir.join(pattern).to_string_lossy()) else {
+                    continue;
+                };
+
+                for entry in entries.flatten() {
+                    if !entry.is_file() || !seen.insert((config.agent.to_string(), entry.clone())) {
+                        continue;
                     }
+
+                    skills.extend(Self::parse_matched_file(&entry, &config.agent));
                 }
             }
         }
 
-        // Also scan for special config files (CLAUDE.md, .cursorrules, etc.)
+        // A conventions file can be matched and named in `read:` as well
+        skills.sort_by(|a, b| a.folder_path.cmp(&b.folder_path));
+        skills.dedup_by(|a, b| a.folder_path == b.folder_path && a.agent == b.agent);
+        skills
+    }
+
+    /// Skills behind a file matched by an agent's patterns. `SKILL.md` is
+    /// read as its folder, and config files expand to the prompts, rules or
+    /// conventions they hold or point to.
+    fn parse_matched_file(path: &Path, agent: &AgentType) -> Vec<Skill> {
+        match agent {
+            AgentType::ContinueDev if ContinueService::is_config_file(path) => {
+                ContinueService::config_entries(path).unwrap_or_else(|e| {
+                    log::warn!("Failed to read {}: {}", path.display(), e);
+                    Vec::new()
+                })
+            }
+            // Conventions are always in context once Aider loads them
+            AgentType::Aider => {
+                let files = if AiderService::is_config_file(path) {
+                    AiderService::read_files(path)
+                } else {
+                    vec![path.to_path_buf()]
+                };
+
+                files.iter()
+                    .filter_map(|file| Self::parse_single_file(file, agent).ok())
+                    .map(|mut skill| {
+                        skill.kind = SkillKind::Memory;
+                        skill
+                    })
+                    .collect()
+            }
+            _ => {
+                let parsed = match path.parent() {
+                    Some(folder) if path.ends_with("SKILL.md") => Self::parse_skill_folder(folder, agent),
+                    _ => Self::parse_single_file(path, agent),
+                };
+                parsed.into_iter().collect()
+            }
+        }
+    }
+
+    /// Config entry behind a `<config>#<section>/<index>` path, when no
+    /// file exists at the path itself
+    fn continue_entry(path: &str) -> Option<ContinueEntry> {
+        ContinueEntry::from_item_path(path).filter(|_| !Path::new(path).exists())
+    }
+
+    /// Kind of a single-file skill from its agent and location
+    fn single_file_kind(path: &Path, agent: &AgentType) -> SkillKind {
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let in_prompts = path.parent()
+            .and_then(|p| p.file_name())
+            .is_some_and(|n| n == "prompts");
+        let in_folder = |folder: &str| path.parent().is_some_and(|p| p.iter().any(|c| c == folder));
+
+        match agent {
+            AgentType::Claude => ClaudeService::kind_for_path(path),
+            AgentType::Cursor | AgentType::Cline => SkillKind::Rule,
+            AgentType::Copilot if file_name.ends_with(".prompt.md") => SkillKind::SlashCommand,
+            AgentType::Copilot if file_name.ends_with(".instructions.md") => SkillKind::Rule,
+            AgentType::Codex if in_prompts => SkillKind::SlashCommand,
+            AgentType::Copilot | AgentType::Codex | AgentType::Gemini if INSTRUCTION_FILES.contains(&file_name.as_str()) => {
+                SkillKind::Memory
+            }
+            AgentType::Windsurf => WindsurfService::kind_for_path(path),
+            AgentType::ContinueDev if in_folder("prompts") => SkillKind::SlashCommand,
+            AgentType::ContinueDev if in_folder("rules") => SkillKind::Rule,
+            _ => SkillKind::Skill,
+        }
+    }
+
+    /// Folders in an agent's skills directory - each subdirectory is a skill
+    fn skill_folders(config: &AgentConfig) -> Vec<PathBuf> {
+        let Some(skills_dir) = &config.skills_dir else {
+            return Vec::new();
+        };
+
+        match fs::read_dir(skills_dir) {
+            // Skip loose files in skills directory - only folders are skills
+            Ok(entries) => entries.flatten()
+                .map(|entry| entry.path())
+                .filter(|path| path.is_dir())
+                .collect(),
+            Err(_) => Vec::new(),
+        }
+    }
+
+    /// Special config files of an agent (CLAUDE.md, .cursorrules, etc.)
+    fn scan_config_files(config: &AgentConfig) -> Vec<Skill> {
+        let mut skills = Vec::new();
+        // Patterns may overlap, e.g. `.cursorrules` and `*.cursorrules`
+        let mut seen = HashSet::new();
+
         let config_path = Path::new(&config.config_dir);
         if config_path.exists() {
             for pattern in &config.file_patterns {
@@ -59,14 +304,18 @@ impl SkillService {
 
                 if let Ok(entries) = glob(&pattern_str) {
                     for entry in entries.flatten() {
-                        if entry.is_file() {
-                            // Skip if this file is inside the skills directory
-                            if let Some(skills_dir) = &config.skills_dir {
-                                if entry.to_string_lossy().contains(skills_dir) {
-                                    continue;
+                        // Agents like Windsurf keep settings next to their rules
+                        if config.agent == AgentType::Windsurf && !WindsurfService::is_windsurf_file(&entry) {
+                            continue;
+                        }
+                        if entry.is_file() && seen.insert(entry.clone()) {
+                            for skill in Self::parse_matched_file(&entry, &config.agent) {
+                                // Skip if this file is inside the skills directory
+                                if let Some(skills_dir) = &config.skills_dir {
+                                    if skill.folder_path.contains(skills_dir) {
+                                        continue;
+                                    }
                                 }
-                            }
-                            if let Ok(skill) = Self::parse_single_file(&entry, &config.agent) {
                                 skills.push(skill);
                             }
                         }
@@ -75,7 +324,7 @@ impl SkillService {
             }
         }
 
-        Ok(skills)
+        skills
     }
 
     /// Parse a skill folder into a Skill struct
@@ -106,6 +355,8 @@ impl SkillService {
                     // Determine if this is the entry file
                     let is_entry = !entry_found && (
                         file_name == "skill.md" ||
+                        file_name == "SKILL.md" ||
+                        file_name == "CONVENTIONS.md" ||
                         file_name == "index.md" ||
                         file_name == "README.md" ||
                         file_name == format!("{}.md", folder_name)
@@ -161,7 +412,14 @@ impl SkillService {
         // Extract description from entry file
         if let Some(entry_path) = &skill.entry_file {
             if let Ok(content) = fs::read_to_string(entry_path) {
-                skill.description = Self::extract_description(&content);
+                // Agent Skills entry files share Claude's frontmatter
+                if *agent == AgentType::Claude || entry_path.ends_with("SKILL.md") {
+                    let (frontmatter, body) = ClaudeService::parse(&content);
+                    skill.description = frontmatter.description.clone().or_else(|| Self::extract_description(body));
+                    skill.claude = Some(frontmatter);
+                } else {
+                    skill.description = Self::extract_description(&content);
+                }
             }
         }
 
@@ -184,6 +442,86 @@ impl SkillService {
         Ok(skill)
     }
 
+    /// Find skill folders below `root` (a directory inside `base`) using the
+    /// entry-file rules of `parse_skill_folder`. The root itself only counts
+    /// as a skill when none of its subdirectories are skills. Returned paths
+    /// are relative to `base`.
+    pub fn discover_skill_folders(base: &Path, root: &Path, agent: &AgentType) -> Vec<DiscoveredSkill> {
+        let mut skills = Vec::new();
+        Self::discover_recursive(root, agent, &mut skills);
+
+        if skills.is_empty() {
+            if let Ok(skill) = Self::parse_skill_folder(root, agent) {
+                if skill.entry_file.is_some() {
+                    skills.push(skill);
+                }
+            }
+        }
+
+        let mut discovered: Vec<DiscoveredSkill> = skills.into_iter()
+            .map(|skill| {
+                let path = Path::new(&skill.folder_path)
+                    .strip_prefix(base)
+                    .map(|p| p.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                DiscoveredSkill { path, skill }
+            })
+            .collect();
+
+        discovered.sort_by(|a, b| a.path.cmp(&b.path));
+        discovered
+    }
+
+    fn discover_recursive(dir: &Path, agent: &AgentType, skills: &mut Vec<Skill>) {
+        let entries = match fs::read_dir(dir) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+            // Symlinks are not followed: they may point out of the repository
+            // or back up the tree
+            let is_dir = entry.file_type().is_ok_and(|t| t.is_dir());
+            if !is_dir || is_hidden {
+                continue;
+            }
+
+            match Self::parse_skill_folder(&path, agent) {
+                // A skill folder - its subdirectories are references/scripts
+                Ok(skill) if skill.entry_file.is_some() => skills.push(skill),
+                _ => Self::discover_recursive(&path, agent, skills),
+            }
+        }
+    }
+
+    /// Resolve a relative directory inside `base`, rejecting anything that escapes it
+    pub fn resolve_within(base: &Path, relative: Option<&str>) -> Result<PathBuf, AppError> {
+        let sub = relative.unwrap_or("").trim_matches('/');
+        let escapes = Path::new(sub).components()
+            .any(|c| matches!(c, Component::ParentDir | Component::RootDir | Component::Prefix(_)));
+        if escapes {
+            return Err(AppError::InvalidPath(format!("Invalid path: {}", sub)));
+        }
+
+        let path = base.join(sub);
+        if !path.is_dir() {
+            return Err(AppError::FileNotFound(format!("{} not found", sub)));
+        }
+
+        // A symlinked folder may still lead outside
+        let inside = match (path.canonicalize(), base.canonicalize()) {
+            (Ok(resolved), Ok(base)) => resolved.starts_with(base),
+            _ => false,
+        };
+        if !inside {
+            return Err(AppError::InvalidPath(format!("Invalid path: {}", sub)));
+        }
+
+        Ok(path)
+    }
+
     /// Scan a subdirectory and add files to the list
     fn scan_subdirectory(dir_path: &Path, files: &mut Vec<SkillFile>) -> Result<(), AppError> {
         if let Ok(entries) = fs::read_dir(dir_path) {
@@ -227,7 +565,9 @@ impl SkillService {
             .unwrap_or("");
 
         let format = SkillFormat::from_extension(extension);
+        // Dotfiles like `.windsurfrules` keep their whole name
         let name = file_name.rsplit('.').last()
+            .filter(|stem| !stem.is_empty())
             .unwrap_or(file_name)
             .to_string();
 
@@ -235,12 +575,31 @@ impl SkillService {
             name,
             path.to_string_lossy().to_string(),
             agent.clone(),
-            format,
+            format.clone(),
         );
+        skill.kind = Self::single_file_kind(path, agent);
 
         // Extract description from content
         if let Ok(content) = fs::read_to_string(path) {
-            skill.description = Self::extract_description(&content);
+            if format == SkillFormat::Mdc {
+                let (rule, body) = RuleService::parse(&content);
+                skill.description = rule.description.clone().or_else(|| Self::extract_description(body));
+                skill.rule = Some(rule);
+            } else if *agent == AgentType::Windsurf {
+                // Workflows share the `description` key with rules
+                let (rule, body) = WindsurfService::parse(&content);
+                skill.description = rule.description.clone().or_else(|| Self::extract_description(body));
+                skill.windsurf = Some(rule).filter(|_| skill.kind == SkillKind::Rule);
+            } else if *agent == AgentType::ContinueDev {
+                let (description, body) = ContinueService::parse_file(&content);
+                skill.description = description.or_else(|| Self::extract_description(body));
+            } else if *agent == AgentType::Claude && format == SkillFormat::Markdown {
+                let (frontmatter, body) = ClaudeService::parse(&content);
+                skill.description = frontmatter.description.clone().or_else(|| Self::extract_description(body));
+                skill.claude = Some(frontmatter);
+            } else {
+                skill.description = Self::extract_description(&content);
+            }
         }
 
         // Get file metadata for timestamps
@@ -264,6 +623,8 @@ impl SkillService {
 
     /// Extract description from content
     fn extract_description(content: &str) -> Option<String> {
+        let content = split_frontmatter(content).map_or(content, |(_, body)| body);
+
         // Look for first paragraph or header
         for line in content.lines() {
             let trimmed = line.trim();
@@ -282,6 +643,10 @@ impl SkillService {
 
     /// Read skill content from file
     pub fn read_content(file_path: &str) -> Result<String, AppError> {
+        if let Some(entry) = Self::continue_entry(file_path) {
+            return ContinueService::read_entry(&entry);
+        }
+
         fs::read_to_string(file_path)
             .map_err(|e| AppError::IoError(e.to_string()))
     }
@@ -364,6 +729,56 @@ impl SkillService {
             .find(|c| &c.agent == agent)
             .ok_or_else(|| AppError::NotFound(format!("Agent config not found: {:?}", agent)))?;
 
+        // Cursor reads rule files, not skill folders
+        if *agent == AgentType::Cursor {
+            let mode = if description.is_some() { RuleMode::AgentRequested } else { RuleMode::Manual };
+            let rule = RuleService::rule_for_mode(mode, description.map(String::from), Vec::new())?;
+            let rules_dir = Path::new(&config.config_dir).join("rules");
+
+            let mut skill = RuleService::create_rule(&rules_dir, name, &rule, Some(content))?;
+            skill.tags = tags.unwrap_or_default();
+            return Ok(skill);
+        }
+
+        // Windsurf has one global rules file, so new skills are global workflows
+        if *agent == AgentType::Windsurf {
+            let workflows_dir = WindsurfService::global_workflows_dir(Path::new(&config.config_dir));
+            let mut skill = WindsurfService::create_workflow(&workflows_dir, name, description, Some(content))?;
+            skill.tags = tags.unwrap_or_default();
+            return Ok(skill);
+        }
+
+        // Continue reads prompt files, not skill folders
+        if *agent == AgentType::ContinueDev {
+            let prompts_dir = Path::new(&config.config_dir).join("prompts");
+            let mut skill = ContinueService::create_prompt(&prompts_dir, name, description, content)?;
+            skill.tags = tags.unwrap_or_default();
+            return Ok(skill);
+        }
+
+        // Cline reads every markdown file in its rules folder
+        if *agent == AgentType::Cline {
+            let path = Path::new(&config.config_dir)
+                .join("Rules")
+                .join(format!("{}.md", name.to_lowercase().replace(' ', "-")));
+            if path.exists() {
+                return Err(AppError::AlreadyExists(path.to_string_lossy().to_string()));
+            }
+
+            let file_content = match description {
+                Some(desc) if !content.starts_with('#') => format!("# {}\n\n{}\n\n{}", name, desc, content),
+                _ => content.to_string(),
+            };
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, file_content)?;
+
+            let mut skill = Self::parse_single_file(&path, agent)?;
+            skill.tags = tags.unwrap_or_default();
+            return Ok(skill);
+        }
+
         // Determine the target directory
         let target_dir = config.skills_dir.as_ref()
             .map(PathBuf::from)
@@ -379,8 +794,8 @@ impl SkillService {
 
         fs::create_dir_all(&skill_folder)?;
 
-        // Create entry file (skill.md)
-        let entry_file = skill_folder.join("skill.md");
+        // Create entry file (SKILL.md for Agent Skills, otherwise skill.md)
+        let entry_file = skill_folder.join(ConvertService::entry_file_name(agent, &SkillFormat::Markdown));
         let mut file_content = content.to_string();
 
         // Add description as header if provided
@@ -389,6 +804,7 @@ impl SkillService {
                 file_content = format!("# {}\n\n{}\n\n{}", name, desc, content);
             }
         }
+        let file_content = ConvertService::with_skill_frontmatter(name, description, &file_content, agent);
 
         fs::write(&entry_file, &file_content)?;
 
@@ -403,6 +819,10 @@ impl SkillService {
 
     /// Update skill file content
     pub fn update_skill(file_path: &str, content: &str) -> Result<Skill, AppError> {
+        if let Some(entry) = Self::continue_entry(file_path) {
+            return ContinueService::update_entry(&entry, content);
+        }
+
         let path = Path::new(file_path);
         if !path.exists() {
             return Err(AppError::FileNotFound(file_path.to_string()));
@@ -414,7 +834,15 @@ impl SkillService {
         let parent = path.parent()
             .ok_or_else(|| AppError::InvalidPath("Cannot get parent directory".to_string()))?;
 
-        let agent = Self::detect_agent_from_path(parent);
+        let agent = Self::detect_agent_from_path(path);
+
+        // Rules, subagents, commands etc. are single files, usually side by
+        // side in one folder
+        let single_file = path.extension().is_some_and(|e| e == "mdc")
+            || Self::single_file_kind(path, &agent) != SkillKind::Skill;
+        if single_file {
+            return Self::parse_single_file(path, &agent);
+        }
 
         if parent.is_dir() && parent.file_name().map(|n| n.to_str().unwrap_or("")).unwrap_or("") != ".claude" {
             Self::parse_skill_folder(parent, &agent)
@@ -425,6 +853,13 @@ impl SkillService {
 
     /// Delete skill (folder or file)
     pub fn delete_skill(path: &str) -> Result<(), AppError> {
+        if let Some(entry) = Self::continue_entry(path) {
+            return Err(AppError::InvalidPath(format!(
+                "{} is part of {}; remove it there",
+                path, entry.config_path
+            )));
+        }
+
         let p = Path::new(path);
         if !p.exists() {
             return Err(AppError::FileNotFound(path.to_string()));
@@ -463,16 +898,20 @@ impl SkillService {
         Self::parse_skill_folder(&new_path, &agent)
     }
 
-    /// Copy directory recursively
-    fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), AppError> {
+    /// Copy a folder's files and subfolders. Symlinks are skipped, so a
+    /// link in a downloaded skill cannot pull in files from elsewhere.
+    pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), AppError> {
         fs::create_dir_all(dst)?;
 
         for entry in fs::read_dir(src)? {
             let entry = entry?;
+            let file_type = entry.file_type()?;
             let src_path = entry.path();
             let dst_path = dst.join(entry.file_name());
 
-            if src_path.is_dir() {
+            if file_type.is_symlink() {
+                log::debug!("Skipping symlink {}", src_path.display());
+            } else if file_type.is_dir() {
                 Self::copy_dir_recursive(&src_path, &dst_path)?;
             } else {
                 fs::copy(&src_path, &dst_path)?;
@@ -494,8 +933,16 @@ impl SkillService {
             AgentType::ContinueDev
         } else if path_str.contains(".aider") {
             AgentType::Aider
-        } else if path_str.contains(".codeium") {
+        } else if path_str.contains(".codeium") || path_str.contains(".windsurf") {
             AgentType::Windsurf
+        } else if path_str.contains(".copilot") || path_str.contains(".github") {
+            AgentType::Copilot
+        } else if path_str.contains(".codex") {
+            AgentType::Codex
+        } else if path_str.contains(".gemini") {
+            AgentType::Gemini
+        } else if path_str.contains("cline") {
+            AgentType::Cline
         } else {
             AgentType::Custom("Unknown".to_string())
         }
@@ -547,3 +994,50 @@ impl SkillService {
         Ok(())
     }
 }
+
+/// Modification time, total size and checksum of the folder itself and
+/// the files `parse_skill_folder` reads: its own files and those directly
+/// inside its subdirectories. Only metadata is read, never file contents.
+fn folder_fingerprint(folder: &Path) -> Option<(i64, u64, String)> {
+    fn stat(path: &Path) -> Option<(u64, u128)> {
+        let metadata = fs::metadata(path).ok()?;
+        let modified = metadata.modified().ok()?
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        Some((metadata.len(), modified))
+    }
+
+    let (_, folder_modified) = stat(folder)?;
+    let mut listing = vec![(String::new(), 0, folder_modified)];
+
+    for entry in fs::read_dir(folder).ok()?.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        if path.is_dir() {
+            let Ok(children) = fs::read_dir(&path) else { continue };
+            for child in children.flatten() {
+                let child_path = child.path();
+                if !child_path.is_file() {
+                    continue;
+                }
+                if let Some((size, modified)) = stat(&child_path) {
+                    listing.push((format!("{}/{}", name, child.file_name().to_string_lossy()), size, modified));
+                }
+            }
+        } else if let Some((size, modified)) = stat(&path) {
+            listing.push((name, size, modified));
+        }
+    }
+
+    listing.sort();
+
+    let size = listing.iter().map(|(_, size, _)| size).sum();
+    let modified = listing.iter().map(|(_, _, modified)| *modified).max().unwrap_or(0);
+    let text: String = listing.iter()
+        .map(|(name, size, modified)| format!("{} {} {}\n", name, size, modified))
+        .collect();
+
+    Some(((modified / 1_000_000_000) as i64, size, DownloadService::checksum(text.as_bytes())))
+}
@@ -0,0 +1,172 @@
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use fs4::fs_std::FileExt;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::models::AppError;
+
+/// Lock file guarding read-modify-write of every state file in the directory
+const LOCK_FILE: &str = ".state.lock";
+
+/// A JSON file of app state, stored as `{"schema": n, "data": ...}`
+pub trait StateFile: Serialize + DeserializeOwned + Default {
+    /// File name inside the state directory
+    const NAME: &'static str;
+    /// Current schema version, starting at 1
+    const SCHEMA: u32;
+
+    /// Upgrade data written with an older schema. `schema` is 0 for files
+    /// written before state files were versioned, with `data` the whole file.
+    fn migrate(schema: u32, data: serde_json::Value) -> Result<serde_json::Value, AppError> {
+        let _ = schema;
+        Ok(data)
+    }
+}
+
+/// Versioned state files shared by the GUI and CLI. Writes are atomic and
+/// updates hold a lock file, so concurrent read-modify-write cycles from
+/// any process do not lose changes.
+#[derive(Clone)]
+pub struct StateService {
+    dir: PathBuf,
+}
+
+impl StateService {
+    pub fn new() -> Self {
+        let dir = dirs::data_local_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("ai-skills-aggregator");
+
+        Self { dir }
+    }
+
+    /// Read a state file, migrating it in memory if it has an older schema.
+    /// Missing files read as the default value.
+    pub async fn read<T: StateFile>(&self) -> Result<T, AppError> {
+        let path = self.dir.join(T::NAME);
+        if !path.exists() {
+            return Ok(T::default());
+        }
+
+        let content = tokio::fs::read_to_string(&path)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        decode::<T>(&content)
+    }
+
+    /// Read, change and write a state file while holding the lock
+    pub async fn update<T, R, F>(&self, change: F) -> Result<R, AppError>
+    where
+        T: StateFile,
+        F: FnOnce(&mut T) -> Result<R, AppError>,
+    {
+        let _lock = self.lock().await?;
+
+        let mut value = self.read::<T>().await?;
+        let result = change(&mut value)?;
+        self.save(&value).await?;
+
+        Ok(result)
+    }
+
+    /// Write a file by renaming a completed temporary file over it, so
+    /// readers never see a partial write
+    pub async fn write_atomic(path: &Path, content: &[u8]) -> Result<(), AppError> {
+        let parent = path.parent().unwrap_or(Path::new("."));
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| AppError::IoError(e.to_string()))?;
+
+        let name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let temp = parent.join(format!(".{}.{}.tmp", name, uuid::Uuid::new_v4().simple()));
+
+        let written = async {
+            let mut file = tokio::fs::File::create(&temp).await?;
+            tokio::io::AsyncWriteExt::write_all(&mut file, content).await?;
+            file.sync_all().await?;
+            tokio::fs::rename(&temp, path).await
+        }.await;
+
+        if let Err(e) = written {
+            let _ = tokio::fs::remove_file(&temp).await;
+            return Err(AppError::IoError(e.to_string()));
+        }
+
+        Ok(())
+    }
+
+    // Internal helpers
+
+    async fn save<T: StateFile>(&self, value: &T) -> Result<(), AppError> {
+        let content = serde_json::to_string_pretty(&Envelope {
+            schema: T::SCHEMA,
+            data: value,
+        })?;
+
+        Self::write_atomic(&self.dir.join(T::NAME), content.as_bytes()).await
+    }
+
+    /// Take the exclusive lock, waiting for other processes to release it.
+    /// The lock is released when the returned file is dropped.
+    async fn lock(&self) -> Result<std::fs::File, AppError> {
+        let dir = self.dir.clone();
+
+        tokio::task::spawn_blocking(move || {
+            std::fs::create_dir_all(&dir)?;
+            let file = OpenOptions::new()
+                .create(true)
+                .truncate(false)
+                .write(true)
+                .open(dir.join(LOCK_FILE))?;
+            FileExt::lock_exclusive(&file)?;
+            Ok(file)
+        })
+        .await
+        .map_err(|e| AppError::IoError(e.to_string()))?
+    }
+}
+
+impl Default for StateService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Serialize)]
+struct Envelope<'a, T> {
+    schema: u32,
+    data: &'a T,
+}
+
+/// Parse a state file, unwrapping the schema envelope and migrating older data
+fn decode<T: StateFile>(content: &str) -> Result<T, AppError> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+
+    let (schema, data) = match value {
+        serde_json::Value::Object(mut map) if map.contains_key("data") => {
+            let schema = map.get("schema").and_then(|s| s.as_u64()).unwrap_or(0) as u32;
+            (schema, map.remove("data").unwrap_or_default())
+        }
+        // Unversioned files from before the state store
+        other => (0, other),
+    };
+
+    if schema > T::SCHEMA {
+        return Err(AppError::ParseError(format!(
+            "{} was written by a newer version (schema {}, supported {})",
+            T::NAME, schema, T::SCHEMA
+        )));
+    }
+
+    let data = if schema < T::SCHEMA {
+        T::migrate(schema, data)?
+    } else {
+        data
+    };
+
+    Ok(serde_json::from_value(data)?)
+}
@@ -1,4 +1,5 @@
-use crate::models::{AgentType, SkillFormat};
+use crate::models::{AgentType, CursorRule, SkillFormat, SkillKind, WindsurfRule, WindsurfTrigger};
+use crate::services::{RuleService, WindsurfService};
 
 pub struct TemplateService;
 
@@ -9,13 +10,100 @@ impl TemplateService {
             (AgentType::Claude, SkillFormat::Python) => Self::claude_py_template(),
             (AgentType::Cursor, SkillFormat::PlainText) => Self::cursor_template(),
             (AgentType::Cursor, SkillFormat::Markdown) => Self::cursor_template(),
+            (AgentType::Cursor, SkillFormat::Mdc) => Self::cursor_mdc_template(),
             (AgentType::ContinueDev, SkillFormat::Json) => Self::continue_template(),
+            (AgentType::ContinueDev, SkillFormat::PlainText) => Self::continue_prompt_template(),
             (AgentType::Aider, SkillFormat::Yaml) => Self::aider_template(),
             (AgentType::Aider, SkillFormat::PlainText) => Self::aider_prompt_template(),
+            (AgentType::Copilot | AgentType::Codex | AgentType::Gemini, SkillFormat::Markdown) => {
+                Self::agent_skill_template()
+            }
+            (AgentType::Cline, SkillFormat::Markdown) => Self::cline_template(),
+            (AgentType::Windsurf, SkillFormat::Markdown) => Self::windsurf_rule_template(),
             _ => Self::generic_md_template(),
         }
     }
 
+    /// Template for a Claude item of the given kind, with its frontmatter
+    pub fn claude_template(kind: SkillKind, name: &str, description: Option<&str>) -> String {
+        let slug = name.trim().to_lowercase().replace(' ', "-");
+        let description = yaml_string(description.unwrap_or(match kind {
+            SkillKind::Subagent => "Use proactively when ... (say when Claude should delegate to this agent)",
+            SkillKind::SlashCommand => "What this command does",
+            _ => "What this does and when Claude should use it",
+        }));
+
+        match kind {
+            SkillKind::Skill => format!(r#"---
+name: {slug}
+description: {description}
+---
+
+# {name}
+
+## Instructions
+
+1. First instruction
+2. Second instruction
+
+## Examples
+
+Example usage here
+"#),
+            SkillKind::Subagent => format!(r#"---
+name: {slug}
+description: {description}
+tools: Read, Grep, Glob
+model: inherit
+---
+
+You are a specialist in ... When invoked:
+
+1. Gather the context you need
+2. Do the task
+3. Report back concisely with what you found or changed
+"#),
+            SkillKind::SlashCommand => format!(r#"---
+description: {description}
+argument-hint: "[args]"
+allowed-tools: Bash(git status:*)
+---
+
+# {name}
+
+Do the following with $ARGUMENTS:
+
+1. First step
+2. Second step
+"#),
+            SkillKind::Memory => r#"# Project Instructions
+
+## Commands
+
+- Build: ...
+- Test: ...
+
+## Conventions
+
+- Code style and patterns to follow
+"#.to_string(),
+            SkillKind::Rule => format!(r#"# {name}
+
+- Rule to follow
+- Another rule
+"#),
+            SkillKind::OutputStyle => format!(r#"---
+name: {name}
+description: {description}
+---
+
+# {name}
+
+Describe how Claude should respond: tone, structure and level of detail.
+"#),
+        }
+    }
+
     fn claude_md_template() -> String {
         r#"# Skill Name
 
@@ -86,6 +174,62 @@ You are an expert assistant following these guidelines:
 "#.to_string()
     }
 
+    fn cursor_mdc_template() -> String {
+        let rule = CursorRule {
+            description: Some("When this rule applies".to_string()),
+            ..CursorRule::default()
+        };
+
+        RuleService::render(&rule, &Self::cursor_rule_body("Rule Name"))
+    }
+
+    /// Body of a new Cursor rule, below its frontmatter
+    pub fn cursor_rule_body(name: &str) -> String {
+        format!(r#"# {}
+
+Guidelines the agent should follow when this rule is attached.
+
+- Follow the project's existing patterns
+- Keep changes focused
+- Reference example files with `@path/to/file`
+"#, name)
+    }
+
+    fn windsurf_rule_template() -> String {
+        let rule = WindsurfRule {
+            trigger: WindsurfTrigger::ModelDecision,
+            description: Some("When this rule applies".to_string()),
+            ..WindsurfRule::default()
+        };
+
+        WindsurfService::render(&rule, &Self::windsurf_rule_body("Rule Name"))
+    }
+
+    /// Body of a new Windsurf rule, below its frontmatter
+    pub fn windsurf_rule_body(name: &str) -> String {
+        format!(r#"# {}
+
+Guidelines Cascade should follow when this rule is active.
+
+- Follow the project's existing patterns
+- Keep changes focused
+"#, name)
+    }
+
+    /// Template for a Windsurf workflow, run in Cascade as `/<name>`
+    pub fn windsurf_workflow_template(name: &str, description: Option<&str>) -> String {
+        format!(r#"---
+description: {}
+---
+
+# {}
+
+1. First step
+2. Second step
+3. Third step
+"#, yaml_string(description.unwrap_or("What this workflow does")), name)
+    }
+
     fn continue_template() -> String {
         r#"{
   "name": "Custom Skill",
@@ -98,6 +242,18 @@ You are an expert assistant following these guidelines:
 "#.to_string()
     }
 
+    fn continue_prompt_template() -> String {
+        r#"name: prompt-name
+description: What this prompt does
+---
+<system>
+You are an expert developer.
+</system>
+
+{{{ input }}}
+"#.to_string()
+    }
+
     fn aider_template() -> String {
         r#"# Aider Configuration
 
@@ -120,6 +276,33 @@ map-tokens: 1024
 "#.to_string()
     }
 
+    fn agent_skill_template() -> String {
+        r#"---
+name: skill-name
+description: What this skill does and when to use it
+---
+
+# Skill Name
+
+## Instructions
+
+1. Step one
+2. Step two
+3. Step three
+"#.to_string()
+    }
+
+    fn cline_template() -> String {
+        r#"# Rule Name
+
+## Guidelines
+
+- Write clean, readable code
+- Follow project conventions
+- Ask for clarification when needed
+"#.to_string()
+    }
+
     fn generic_md_template() -> String {
         r#"# Skill Name
 
@@ -135,3 +318,10 @@ What this skill does.
 "#.to_string()
     }
 }
+
+/// A YAML scalar, quoted when the text needs it
+pub(crate) fn yaml_string(text: &str) -> String {
+    serde_yaml::to_string(text)
+        .map(|yaml| yaml.trim_end().to_string())
+        .unwrap_or_else(|_| format!("{:?}", text))
+}
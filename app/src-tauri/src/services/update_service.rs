@@ -1,12 +1,27 @@
-use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
 use chrono::Utc;
+use futures::stream::{self, StreamExt};
 use serde::{Deserialize, Serialize};
 
-use crate::models::{InstalledSkill, RegistryConfig, AppError};
+use crate::models::{
+    InstalledSkill, InstallSource, GitSource, RegistryConfig, SkillRegistry, SkillVersion, UpdatePolicy, AppError,
+};
+use crate::services::backup_service::BackupService;
+use crate::services::state_service::{StateFile, StateService};
 use crate::services::registry_service::RegistryService;
+use crate::services::version_service::VersionService;
+use crate::services::download_service::DownloadService;
+use crate::services::git_service::GitService;
+use crate::services::diff_service::{DiffService, FileDiff, FileMerge, MergeResolution, MergeStatus};
+use crate::services::SkillService;
 
 pub struct UpdateService {
     registry: RegistryService,
+    state: StateService,
+    download: DownloadService,
+    git: GitService,
+    backup: BackupService,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -28,17 +43,199 @@ pub struct UpdateCheckResult {
     pub error: Option<String>,
 }
 
+/// Release notes of one version between the installed and new version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseNote {
+    pub version: String,
+    pub released_at: Option<String>,
+    pub notes: Option<String>,
+    pub url: Option<String>,
+}
+
+/// What an update changes, for review before applying it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateDetails {
+    pub update: SkillUpdate,
+    /// Newest first
+    pub release_notes: Vec<ReleaseNote>,
+    pub diffs: Vec<FileDiff>,
+}
+
+/// Local edits of an installed skill and how they merge with an update
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdateMerge {
+    pub update: SkillUpdate,
+    pub locally_modified: bool,
+    /// Locally modified files only; everything else takes the new version
+    pub files: Vec<FileMerge>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchUpdateOptions {
+    /// Roll back every update unless all of them apply
+    #[serde(default)]
+    pub all_or_nothing: bool,
+    /// Updates fetched and staged at the same time
+    #[serde(default = "default_max_concurrent")]
+    pub max_concurrent: usize,
+}
+
+impl Default for BatchUpdateOptions {
+    fn default() -> Self {
+        Self {
+            all_or_nothing: false,
+            max_concurrent: default_max_concurrent(),
+        }
+    }
+}
+
+fn default_max_concurrent() -> usize {
+    4
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum BatchOutcome {
+    Applied,
+    /// Not applied because local changes conflict
+    Skipped,
+    Failed,
+    /// Applied or staged, then undone because the batch did not complete
+    RolledBack,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    pub update: SkillUpdate,
+    pub outcome: BatchOutcome,
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchUpdateReport {
+    pub results: Vec<BatchItemResult>,
+    /// True when an all-or-nothing batch was undone
+    pub rolled_back: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipMode {
+    /// Skip only this version
+    #[default]
+    Version,
+    /// Skip this and later versions until the next major release
+    UntilMajor,
+    /// Never offer updates for the skill
+    Ignore,
+}
+
+/// A skip rule. Rules without an agent or registry apply to all of them.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkippedVersion {
     pub skill_id: String,
+    /// Version offered when the skip was made
     pub version: String,
+    #[serde(default)]
+    pub agent: Option<String>,
+    #[serde(default)]
+    pub registry_url: Option<String>,
+    #[serde(default)]
+    pub mode: SkipMode,
     pub skipped_at: i64,
 }
 
+impl SkippedVersion {
+    /// Whether this rule hides `version` of a skill installed for `agent` from `registry_url`
+    pub fn matches(&self, skill_id: &str, agent: &str, registry_url: &str, version: &str) -> bool {
+        if self.skill_id != skill_id
+            || self.agent.as_deref().is_some_and(|a| a != agent)
+            || self.registry_url.as_deref().is_some_and(|r| r != registry_url)
+        {
+            return false;
+        }
+
+        match self.mode {
+            SkipMode::Version => self.version == version,
+            SkipMode::UntilMajor => match (
+                VersionService::parse_version(&self.version),
+                VersionService::parse_version(version),
+            ) {
+                (Some(skipped), Some(offered)) => offered.major <= skipped.major,
+                // Commits and other unversioned releases can only be skipped one at a time
+                _ => self.version == version,
+            },
+            SkipMode::Ignore => true,
+        }
+    }
+
+    /// Whether two rules cover the same skill, scope and mode. Version skips
+    /// are also distinguished by version; the other modes have one rule per scope.
+    fn same_rule(&self, other: &SkippedVersion) -> bool {
+        self.skill_id == other.skill_id
+            && self.agent == other.agent
+            && self.registry_url == other.registry_url
+            && self.mode == other.mode
+            && (self.mode != SkipMode::Version || self.version == other.version)
+    }
+}
+
+/// Skip rules as stored on disk
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+struct SkipList(Vec<SkippedVersion>);
+
+impl StateFile for SkipList {
+    const NAME: &'static str = "skipped-versions.json";
+    const SCHEMA: u32 = 2;
+
+    /// Unversioned files hold either the original flat list of
+    /// `{skill_id, version, skipped_at}`, which applied to every agent and
+    /// registry, or scoped rules under `skips`
+    fn migrate(_schema: u32, data: serde_json::Value) -> Result<serde_json::Value, AppError> {
+        match data {
+            serde_json::Value::Object(mut file) => Ok(file.remove("skips").unwrap_or_default()),
+            list => Ok(list),
+        }
+    }
+}
+
+/// Installed and new content of a skill for one update
+struct UpdateContent {
+    /// Content of the installed version, when it is still available
+    base: Option<BTreeMap<String, Vec<u8>>>,
+    theirs: BTreeMap<String, Vec<u8>>,
+    version: String,
+    commit_sha: Option<String>,
+}
+
+/// New content written next to an install, ready to be swapped in
+struct StagedUpdate {
+    update: SkillUpdate,
+    target: PathBuf,
+    staged: PathBuf,
+    record: InstalledSkill,
+}
+
+/// A swapped-in update and where the previous content was moved
+struct CommittedUpdate {
+    target: PathBuf,
+    previous: Option<PathBuf>,
+}
+
 impl UpdateService {
     pub fn new() -> Self {
+        Self::with_state(StateService::new())
+    }
+
+    /// A service sharing the given state store
+    pub fn with_state(state: StateService) -> Self {
         Self {
-            registry: RegistryService::new(),
+            registry: RegistryService::with_state(state.clone()),
+            state,
+            download: DownloadService::new(),
+            git: GitService::new(),
+            backup: BackupService::new(),
         }
     }
 
@@ -61,7 +258,11 @@ impl UpdateService {
         let mut by_registry: std::collections::HashMap<String, Vec<&InstalledSkill>> =
             std::collections::HashMap::new();
 
-        for skill in &installed {
+        // Pinned skills are held at their installed version
+        let registry_skills = installed.iter()
+            .filter(|s| s.source == InstallSource::Registry && s.update_policy != UpdatePolicy::Pinned);
+
+        for skill in registry_skills {
             by_registry
                 .entry(skill.registry_url.clone())
                 .or_default()
@@ -80,13 +281,20 @@ impl UpdateService {
 
             if let Ok(registry) = self.registry.fetch_registry(&config).await {
                 for installed in skills {
-                    if let Some(remote) = registry.skills.iter().find(|s| s.id == installed.skill_id) {
+                    if let Some(remote) = self.registry.available_update(installed, &registry) {
                         // Check if this version is skipped
                         let is_skipped = skipped.iter().any(|s| {
-                            s.skill_id == installed.skill_id && s.version == remote.version
+                            s.matches(&installed.skill_id, &installed.agent, &registry_url, &remote.version)
                         });
 
-                        if !is_skipped && self.is_newer(&installed.version, &remote.version) {
+                        if !is_skipped {
+                            let releases = RegistryService::releases_between(
+                                &remote,
+                                &installed.version,
+                                &remote.version,
+                            );
+                            let notes = self.release_notes(&registry_url, releases);
+
                             updates.push(SkillUpdate {
                                 skill_id: installed.skill_id.clone(),
                                 skill_name: remote.name.clone(),
@@ -94,8 +302,8 @@ impl UpdateService {
                                 new_version: remote.version.clone(),
                                 agent: installed.agent.clone(),
                                 registry_url: registry_url.clone(),
-                                changelog: None,
-                                is_major: self.is_major_update(&installed.version, &remote.version),
+                                changelog: format_changelog(&notes),
+                                is_major: VersionService::is_major_update(&installed.version, &remote.version),
                             });
                         }
                     }
@@ -103,6 +311,40 @@ impl UpdateService {
             }
         }
 
+        // Git-sourced skills are compared by commit
+        match self.registry.check_git_updates().await {
+            Ok(git_updates) => {
+                for git_update in git_updates {
+                    let Some(record) = installed.iter()
+                        .find(|s| s.skill_id == git_update.skill_id && s.agent == git_update.agent)
+                    else {
+                        continue;
+                    };
+
+                    let is_skipped = skipped.iter().any(|s| {
+                        s.matches(&git_update.skill_id, &git_update.agent, &record.registry_url, &git_update.new_version)
+                    });
+                    if is_skipped || record.update_policy == UpdatePolicy::Pinned {
+                        continue;
+                    }
+
+                    let registry_url = record.registry_url.clone();
+
+                    updates.push(SkillUpdate {
+                        skill_name: git_update.skill_id.clone(),
+                        skill_id: git_update.skill_id,
+                        current_version: git_update.current_version,
+                        new_version: git_update.new_version,
+                        agent: git_update.agent,
+                        registry_url,
+                        changelog: None,
+                        is_major: false,
+                    });
+                }
+            }
+            Err(e) => log::warn!("Failed to check git skills for updates: {}", e),
+        }
+
         UpdateCheckResult {
             available_updates: updates,
             last_checked: Utc::now().timestamp(),
@@ -110,7 +352,18 @@ impl UpdateService {
         }
     }
 
-    pub async fn apply_update(&self, update: &SkillUpdate) -> Result<(), AppError> {
+    /// Release notes and content diff of an update, without applying it
+    pub async fn get_update_details(&self, update: &SkillUpdate) -> Result<UpdateDetails, AppError> {
+        let installed = self.registry.get_installed_skills().await?;
+        let skill = installed
+            .iter()
+            .find(|s| s.skill_id == update.skill_id && s.agent == update.agent)
+            .ok_or_else(|| AppError::FileNotFound(format!("Skill {} not installed", update.skill_id)))?;
+
+        if let InstallSource::Git(source) = &skill.source {
+            return self.git_update_details(update, skill, source).await;
+        }
+
         let config = RegistryConfig {
             url: update.registry_url.clone(),
             name: "".into(),
@@ -126,27 +379,241 @@ impl UpdateService {
             .find(|s| s.id == update.skill_id)
             .ok_or_else(|| AppError::FileNotFound(format!("Skill {} not found", update.skill_id)))?;
 
-        // Install (will backup and replace)
-        self.registry
-            .install_skill(remote_skill, &update.registry_url, &update.agent)
+        let target = remote_skill.at_version(&update.new_version)
+            .ok_or_else(|| AppError::NotFound(format!(
+                "Version {} of {} is not published",
+                update.new_version, update.skill_id
+            )))?;
+
+        let releases = RegistryService::releases_between(remote_skill, &skill.version, &update.new_version);
+        let mut release_notes = self.release_notes(&update.registry_url, releases);
+
+        // Inline linked notes when they are plain text or markdown
+        for note in release_notes.iter_mut().filter(|n| n.notes.is_none()) {
+            let Some(url) = note.url.as_deref() else { continue };
+            if !(url.ends_with(".md") || url.ends_with(".txt")) {
+                continue;
+            }
+
+            match self.download.fetch_text(url).await {
+                Ok(text) => note.notes = Some(text),
+                Err(e) => log::debug!("Failed to fetch release notes {}: {}", url, e),
+            }
+        }
+
+        let new_content = self.registry
+            .fetch_release(&target, &update.registry_url, &update.agent)
             .await?;
+        let current_content = tokio::fs::read(&skill.installed_path).await.ok();
+
+        let file_name = Path::new(&skill.installed_path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(&skill.skill_id);
+
+        let diffs = DiffService::diff_file(file_name, current_content.as_deref(), Some(&new_content))
+            .into_iter()
+            .collect();
+
+        Ok(UpdateDetails {
+            update: update.clone(),
+            release_notes,
+            diffs,
+        })
+    }
+
+    /// Local edits of an installed skill and how they merge with an update
+    pub async fn preview_update_merge(&self, update: &SkillUpdate) -> Result<UpdateMerge, AppError> {
+        let installed = self.registry.get_installed_skills().await?;
+        let skill = installed
+            .iter()
+            .find(|s| s.skill_id == update.skill_id && s.agent == update.agent)
+            .ok_or_else(|| AppError::FileNotFound(format!("Skill {} not installed", update.skill_id)))?;
+
+        let files = if self.is_locally_modified(skill) {
+            let content = self.update_content(update, skill, None).await?;
+            local_changes(skill, &content)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(UpdateMerge {
+            update: update.clone(),
+            locally_modified: !files.is_empty(),
+            files,
+        })
+    }
+
+    /// Apply an update. Locally modified files are merged with the new
+    /// version; `resolutions` (by file path) choose keep-mine, take-theirs or
+    /// the merged text. Fails without changing anything when a conflicting
+    /// file has no resolution.
+    pub async fn apply_update(
+        &self,
+        update: &SkillUpdate,
+        resolutions: &HashMap<String, MergeResolution>,
+    ) -> Result<(), AppError> {
+        let installed = self.registry.get_installed_skills().await?;
+        let current = installed
+            .iter()
+            .find(|s| s.skill_id == update.skill_id && s.agent == update.agent);
+
+        let staged = self.stage_update(update, current, None, resolutions).await?;
+
+        let committed = match self.commit_staged(&staged).await {
+            Ok(committed) => committed,
+            Err(e) => {
+                discard(&staged.staged);
+                return Err(e);
+            }
+        };
+
+        self.registry.record_installations(&[staged.record]).await?;
+        finish(&committed);
 
         Ok(())
     }
 
-    pub async fn apply_all_updates(&self, updates: &[SkillUpdate]) -> Vec<Result<(), String>> {
-        let mut results = Vec::new();
+    /// Apply several updates. New content for every skill is fetched and
+    /// staged concurrently (each registry is fetched once), then swapped in
+    /// one skill at a time. With `all_or_nothing`, any skipped or failed
+    /// update rolls every change back.
+    pub async fn apply_updates(&self, updates: &[SkillUpdate], options: &BatchUpdateOptions) -> BatchUpdateReport {
+        let installed = self.registry.get_installed_skills().await.unwrap_or_default();
+
+        // Fetch each registry once
+        let registry_urls: BTreeSet<&str> = updates.iter()
+            .filter(|u| installed.iter().any(|s| {
+                s.skill_id == u.skill_id && s.agent == u.agent && s.source == InstallSource::Registry
+            }))
+            .map(|u| u.registry_url.as_str())
+            .collect();
+
+        let mut registries = HashMap::new();
+        for url in registry_urls {
+            let config = RegistryConfig {
+                url: url.to_string(),
+                name: "".into(),
+                enabled: true,
+                auth_token: None,
+            };
+
+            match self.registry.fetch_registry(&config).await {
+                Ok(registry) => {
+                    registries.insert(url.to_string(), registry);
+                }
+                Err(e) => log::warn!("Failed to fetch {}: {}", url, e),
+            }
+        }
+
+        // Stage new content concurrently
+        let no_resolutions = HashMap::new();
+        let prepared: Vec<Result<StagedUpdate, AppError>> = stream::iter(updates)
+            .map(|update| {
+                let current = installed.iter()
+                    .find(|s| s.skill_id == update.skill_id && s.agent == update.agent);
+                let registry = registries.get(&update.registry_url);
+                self.stage_update(update, current, registry, &no_resolutions)
+            })
+            .buffered(options.max_concurrent.max(1))
+            .collect()
+            .await;
+
+        let mut results: Vec<BatchItemResult> = Vec::new();
+        let mut staged = Vec::new();
+        for (update, outcome) in updates.iter().zip(prepared) {
+            match outcome {
+                Ok(item) => staged.push(item),
+                Err(e) => results.push(BatchItemResult {
+                    update: update.clone(),
+                    outcome: match e {
+                        AppError::MergeConflict(_) => BatchOutcome::Skipped,
+                        _ => BatchOutcome::Failed,
+                    },
+                    reason: Some(e.to_string()),
+                }),
+            }
+        }
+
+        if options.all_or_nothing && !results.is_empty() {
+            for item in staged {
+                discard(&item.staged);
+                results.push(BatchItemResult {
+                    update: item.update,
+                    outcome: BatchOutcome::RolledBack,
+                    reason: Some("Another update in the batch did not apply".into()),
+                });
+            }
+
+            return batch_report(updates, results, true);
+        }
+
+        // Swap staged content in, one skill at a time
+        let mut committed = Vec::new();
+        let mut rollback_reason = None;
+        for item in &staged {
+            if rollback_reason.is_some() {
+                discard(&item.staged);
+                continue;
+            }
+
+            match self.commit_staged(item).await {
+                Ok(done) => committed.push((item, done)),
+                Err(e) => {
+                    discard(&item.staged);
+                    results.push(BatchItemResult {
+                        update: item.update.clone(),
+                        outcome: BatchOutcome::Failed,
+                        reason: Some(e.to_string()),
+                    });
+                    if options.all_or_nothing {
+                        rollback_reason = Some(format!("Update of {} failed", item.update.skill_id));
+                    }
+                }
+            }
+        }
+
+        if rollback_reason.is_none() {
+            let records: Vec<InstalledSkill> = committed.iter().map(|(item, _)| item.record.clone()).collect();
+            if let Err(e) = self.registry.record_installations(&records).await {
+                rollback_reason = Some(format!("Failed to record installed versions: {}", e));
+            }
+        }
+
+        if let Some(reason) = rollback_reason {
+            // Restore in reverse order so every skill gets its previous content back
+            for (item, done) in committed.iter().rev() {
+                if let Err(e) = restore(done) {
+                    log::error!("Failed to roll back {}: {}", item.update.skill_id, e);
+                }
+            }
+
+            for item in &staged {
+                if !results.iter().any(|r| same_skill(&r.update, &item.update)) {
+                    results.push(BatchItemResult {
+                        update: item.update.clone(),
+                        outcome: BatchOutcome::RolledBack,
+                        reason: Some(reason.clone()),
+                    });
+                }
+            }
+
+            return batch_report(updates, results, true);
+        }
 
-        for update in updates {
-            let result = self.apply_update(update).await.map_err(|e| e.to_string());
-            results.push(result);
+        for (item, done) in &committed {
+            finish(done);
+            results.push(BatchItemResult {
+                update: item.update.clone(),
+                outcome: BatchOutcome::Applied,
+                reason: None,
+            });
         }
 
-        results
+        batch_report(updates, results, false)
     }
 
     pub async fn rollback_skill(&self, skill_id: &str, agent: &str) -> Result<(), AppError> {
-        let backup = crate::services::backup_service::BackupService::new();
         let installed = self.registry.get_installed_skills().await?;
 
         let skill = installed
@@ -154,119 +621,363 @@ impl UpdateService {
             .find(|s| s.skill_id == skill_id && s.agent == agent)
             .ok_or_else(|| AppError::FileNotFound(format!("Skill {} not installed", skill_id)))?;
 
-        // Get most recent backup
-        let filename = std::path::Path::new(&skill.installed_path)
-            .file_name()
-            .and_then(|n| n.to_str())
-            .unwrap_or("");
-
-        let backups = backup.list_backups(filename).await?;
+        // Restore the content this skill's last install or update replaced
+        if let Some(latest) = self.backup.latest_skill_backup(skill_id, agent).await? {
+            let latest = latest.to_string_lossy();
+            if Path::new(latest.as_ref()).is_dir() {
+                self.backup.restore_folder(&latest, &skill.installed_path).await?;
+            } else {
+                self.backup.restore_file(&latest, &skill.installed_path).await?;
+            }
+            return Ok(());
+        }
 
-        if let Some(most_recent) = backups.first() {
-            backup.restore_file(&most_recent.path, &skill.installed_path).await?;
-            Ok(())
-        } else {
-            Err(AppError::FileNotFound("No backup available".into()))
+        // No backup: reinstall the previous published version instead
+        if skill.source != InstallSource::Registry {
+            return Err(AppError::FileNotFound("No backup available".into()));
         }
-    }
 
-    pub async fn skip_version(&self, skill_id: &str, version: &str) -> Result<(), AppError> {
-        let config_path = self.get_skipped_versions_path()?;
+        let config = RegistryConfig {
+            url: skill.registry_url.clone(),
+            name: "".into(),
+            enabled: true,
+            auth_token: None,
+        };
 
-        let mut skipped = self.load_skipped_versions().await;
+        let registry = self.registry.fetch_registry(&config).await?;
 
-        // Check if already skipped
-        if skipped.iter().any(|s| s.skill_id == skill_id && s.version == version) {
-            return Ok(());
-        }
+        let previous = registry
+            .skills
+            .iter()
+            .find(|s| s.id == skill_id)
+            .and_then(|remote| RegistryService::previous_version(remote, &skill.version))
+            .ok_or_else(|| AppError::FileNotFound(
+                format!("No backup or earlier published version of {} available", skill_id)
+            ))?;
 
-        skipped.push(SkippedVersion {
+        self.registry
+            .install_skill(&previous, &skill.registry_url, agent, Some(&previous.version))
+            .await?;
+
+        Ok(())
+    }
+
+    /// Add a skip rule. A rule for the same skill, scope and mode replaces
+    /// the existing one.
+    pub async fn skip_version(
+        &self,
+        skill_id: &str,
+        version: &str,
+        agent: Option<&str>,
+        registry_url: Option<&str>,
+        mode: SkipMode,
+    ) -> Result<(), AppError> {
+        let rule = SkippedVersion {
             skill_id: skill_id.to_string(),
             version: version.to_string(),
+            agent: agent.map(String::from),
+            registry_url: registry_url.map(String::from),
+            mode,
             skipped_at: Utc::now().timestamp(),
-        });
+        };
 
-        let content = serde_json::to_string_pretty(&skipped)
-            .map_err(|e| AppError::ParseError(e.to_string()))?;
+        self.state.update(|SkipList(skipped): &mut SkipList| {
+            skipped.retain(|s| !s.same_rule(&rule));
+            skipped.push(rule);
+            Ok(())
+        }).await
+    }
 
-        if let Some(parent) = config_path.parent() {
-            tokio::fs::create_dir_all(parent)
-                .await
-                .map_err(|e| AppError::IoError(e.to_string()))?;
-        }
+    pub async fn list_skipped_versions(&self) -> Vec<SkippedVersion> {
+        self.load_skipped_versions().await
+    }
 
-        tokio::fs::write(&config_path, content)
-            .await
-            .map_err(|e| AppError::IoError(e.to_string()))?;
+    /// Remove a skip rule as returned by `list_skipped_versions`
+    pub async fn unskip_version(&self, skip: &SkippedVersion) -> Result<(), AppError> {
+        self.state.update(|SkipList(skipped): &mut SkipList| {
+            let count = skipped.len();
+            skipped.retain(|s| !s.same_rule(skip));
 
-        Ok(())
+            if skipped.len() == count {
+                return Err(AppError::NotFound(format!(
+                    "No skip of {} {} found",
+                    skip.skill_id, skip.version
+                )));
+            }
+
+            Ok(())
+        }).await
     }
 
     // Internal helpers
 
-    async fn load_skipped_versions(&self) -> Vec<SkippedVersion> {
-        let config_path = match self.get_skipped_versions_path() {
-            Ok(p) => p,
-            Err(_) => return vec![],
-        };
-
-        if !config_path.exists() {
-            return vec![];
+    /// Whether an installed skill may have been edited since it was
+    /// installed. Skills without an install hash are checked file by file.
+    fn is_locally_modified(&self, skill: &InstalledSkill) -> bool {
+        match &skill.content_hash {
+            Some(hash) => DiffService::content_hash(Path::new(&skill.installed_path)).ok().as_ref() != Some(hash),
+            None => true,
         }
+    }
+
+    /// Content of the installed version (when still available) and of the
+    /// new version, keyed like `DiffService::read_tree`
+    async fn update_content(
+        &self,
+        update: &SkillUpdate,
+        skill: &InstalledSkill,
+        registry: Option<&SkillRegistry>,
+    ) -> Result<UpdateContent, AppError> {
+        if let InstallSource::Git(source) = &skill.source {
+            // The installed commit is checked out into the same working copy
+            let _guard = self.git.lock(source).await;
+
+            let repo = GitSource {
+                subdirectory: None,
+                ..source.clone()
+            };
+
+            let checkout = self.git.checkout(&repo).await?;
+            let folder = SkillService::resolve_within(&checkout.repo_path, source.subdirectory.as_deref())?;
+            let theirs = DiffService::read_tree(&folder)?;
 
-        match tokio::fs::read_to_string(&config_path).await {
-            Ok(content) => serde_json::from_str(&content).unwrap_or_default(),
-            Err(_) => vec![],
+            let base = match &skill.commit_sha {
+                Some(sha) => {
+                    let installed = GitSource {
+                        git_ref: Some(sha.clone()),
+                        ..repo
+                    };
+                    match self.git.checkout(&installed).await {
+                        Ok(old) => SkillService::resolve_within(&old.repo_path, source.subdirectory.as_deref())
+                            .and_then(|folder| DiffService::read_tree(&folder))
+                            .ok(),
+                        Err(e) => {
+                            log::debug!("Installed commit of {} unavailable: {}", skill.skill_id, e);
+                            None
+                        }
+                    }
+                }
+                None => None,
+            };
+
+            return Ok(UpdateContent {
+                base,
+                theirs,
+                version: checkout.commit_sha.chars().take(7).collect(),
+                commit_sha: Some(checkout.commit_sha),
+            });
         }
-    }
 
-    fn get_skipped_versions_path(&self) -> Result<std::path::PathBuf, AppError> {
-        let path = dirs::data_local_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join("ai-skills-aggregator")
-            .join("skipped-versions.json");
-        Ok(path)
+        let fetched;
+        let registry = match registry {
+            Some(registry) => registry,
+            None => {
+                let config = RegistryConfig {
+                    url: update.registry_url.clone(),
+                    name: "".into(),
+                    enabled: true,
+                    auth_token: None,
+                };
+                fetched = self.registry.fetch_registry(&config).await?;
+                &fetched
+            }
+        };
+
+        let remote_skill = registry
+            .skills
+            .iter()
+            .find(|s| s.id == update.skill_id)
+            .ok_or_else(|| AppError::FileNotFound(format!("Skill {} not found", update.skill_id)))?;
+
+        let file_name = Path::new(&skill.installed_path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let target = RegistryService::resolve_version(remote_skill, Some(&update.new_version))?;
+        let new_content = self.registry.fetch_release(&target, &update.registry_url, &update.agent).await?;
+        let theirs = BTreeMap::from([(file_name.clone(), new_content)]);
+
+        let base = match remote_skill.at_version(&skill.version) {
+            Some(installed) => self.registry
+                .fetch_release(&installed, &update.registry_url, &update.agent)
+                .await
+                .ok()
+                .map(|content| BTreeMap::from([(file_name, content)])),
+            None => None,
+        };
+
+        Ok(UpdateContent {
+            base,
+            theirs,
+            version: target.version,
+            commit_sha: None,
+        })
     }
 
-    fn is_newer(&self, current: &str, available: &str) -> bool {
-        self.compare_versions(current, available) == Ordering::Less
+    /// Write the new content, with local changes merged in, next to the
+    /// install so it can be swapped in with a rename
+    async fn stage_update(
+        &self,
+        update: &SkillUpdate,
+        current: Option<&InstalledSkill>,
+        registry: Option<&SkillRegistry>,
+        resolutions: &HashMap<String, MergeResolution>,
+    ) -> Result<StagedUpdate, AppError> {
+        let skill = current.ok_or_else(|| AppError::NotFound(
+            format!("Skill {} not installed for {}", update.skill_id, update.agent)
+        ))?;
+
+        let content = self.update_content(update, skill, registry).await?;
+        let mut tree = content.theirs.clone();
+
+        if self.is_locally_modified(skill) {
+            let mut conflicts = Vec::new();
+            for file in local_changes(skill, &content)? {
+                match (resolutions.get(&file.path), &file.status) {
+                    (None, MergeStatus::Conflict) => conflicts.push(file.path.clone()),
+                    (resolution, _) => {
+                        let resolution = resolution.copied().unwrap_or(MergeResolution::Merge);
+                        match file.resolve(resolution)? {
+                            Some(bytes) => tree.insert(file.path.clone(), bytes),
+                            None => tree.remove(&file.path),
+                        };
+                    }
+                }
+            }
+
+            if !conflicts.is_empty() {
+                return Err(AppError::MergeConflict(format!(
+                    "local changes to {} conflict with {}: {}",
+                    update.skill_id,
+                    update.new_version,
+                    conflicts.join(", ")
+                )));
+            }
+        }
+
+        // Registry installs are a single file; other sources are folders
+        let is_folder = skill.source != InstallSource::Registry;
+        let target = PathBuf::from(&skill.installed_path);
+        let staged = sibling_path(&target, "staged");
+        write_tree(&staged, &tree, is_folder)?;
+
+        // The install hash is of the pristine new content, so local changes
+        // carried over are still detected next time
+        let content_hash = if is_folder {
+            DiffService::tree_hash(&content.theirs)
+        } else {
+            DownloadService::checksum(content.theirs.values().next().map(Vec::as_slice).unwrap_or_default())
+        };
+
+        let record = InstalledSkill {
+            version: content.version,
+            commit_sha: content.commit_sha,
+            content_hash: Some(content_hash),
+            installed_at: Utc::now().timestamp(),
+            ..skill.clone()
+        };
+
+        Ok(StagedUpdate {
+            update: update.clone(),
+            target,
+            staged,
+            record,
+        })
     }
 
-    fn is_major_update(&self, current: &str, available: &str) -> bool {
-        let curr_major = current.split('.').next().and_then(|s| s.parse::<u32>().ok());
-        let avail_major = available.split('.').next().and_then(|s| s.parse::<u32>().ok());
+    /// Back up the current content and swap the staged content in
+    async fn commit_staged(&self, item: &StagedUpdate) -> Result<CommittedUpdate, AppError> {
+        if item.target.exists() {
+            let path = item.target.to_string_lossy().to_string();
+            self.backup.backup_skill(&item.update.skill_id, &item.update.agent, &path).await?;
+        }
 
-        match (curr_major, avail_major) {
-            (Some(c), Some(a)) => a > c,
-            _ => false,
+        let previous = if item.target.exists() {
+            let previous = sibling_path(&item.target, "previous");
+            std::fs::rename(&item.target, &previous)?;
+            Some(previous)
+        } else {
+            None
+        };
+
+        if item.staged.exists() {
+            if let Err(e) = std::fs::rename(&item.staged, &item.target) {
+                if let Some(previous) = &previous {
+                    let _ = std::fs::rename(previous, &item.target);
+                }
+                return Err(e.into());
+            }
         }
+
+        Ok(CommittedUpdate {
+            target: item.target.clone(),
+            previous,
+        })
     }
 
-    fn compare_versions(&self, current: &str, available: &str) -> Ordering {
-        let parse = |v: &str| -> Vec<u32> {
-            v.split('.')
-                .filter_map(|s| {
-                    s.chars()
-                        .take_while(|c| c.is_numeric())
-                        .collect::<String>()
-                        .parse()
-                        .ok()
-                })
-                .collect()
+    async fn git_update_details(
+        &self,
+        update: &SkillUpdate,
+        skill: &InstalledSkill,
+        source: &GitSource,
+    ) -> Result<UpdateDetails, AppError> {
+        let repo = GitSource {
+            subdirectory: None,
+            ..source.clone()
         };
+        let _guard = self.git.lock(&repo).await;
+        let checkout = self.git.checkout(&repo).await?;
+        let folder = SkillService::resolve_within(&checkout.repo_path, source.subdirectory.as_deref())?;
 
-        let curr = parse(current);
-        let avail = parse(available);
+        // Commit subjects stand in for release notes. The old commit may be
+        // gone after a force push, in which case there are none.
+        let release_notes = self.git
+            .log(&checkout, skill.commit_sha.as_deref(), &checkout.commit_sha)
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .map(|line| {
+                let (sha, subject) = line.split_once(' ').unwrap_or((line.as_str(), ""));
+                ReleaseNote {
+                    version: sha.to_string(),
+                    released_at: None,
+                    notes: Some(subject.to_string()),
+                    url: None,
+                }
+            })
+            .collect();
 
-        for i in 0..3 {
-            let c = curr.get(i).copied().unwrap_or(0);
-            let a = avail.get(i).copied().unwrap_or(0);
-            match c.cmp(&a) {
-                Ordering::Equal => continue,
-                other => return other,
+        let diffs = DiffService::diff_folders(Path::new(&skill.installed_path), &folder)?;
+
+        Ok(UpdateDetails {
+            update: update.clone(),
+            release_notes,
+            diffs,
+        })
+    }
+
+    fn release_notes(&self, registry_url: &str, releases: Vec<SkillVersion>) -> Vec<ReleaseNote> {
+        releases.into_iter()
+            .map(|release| ReleaseNote {
+                url: release.changelog_url
+                    .as_deref()
+                    .map(|url| self.registry.resolve_registry_url(registry_url, url)),
+                version: release.version,
+                released_at: release.released_at,
+                notes: release.changelog,
+            })
+            .collect()
+    }
+
+    async fn load_skipped_versions(&self) -> Vec<SkippedVersion> {
+        match self.state.read::<SkipList>().await {
+            Ok(SkipList(skipped)) => skipped,
+            Err(e) => {
+                log::warn!("Failed to read skipped versions: {}", e);
+                vec![]
             }
         }
-        Ordering::Equal
     }
 }
 
@@ -275,3 +986,138 @@ impl Default for UpdateService {
         Self::new()
     }
 }
+
+/// Locally modified files of an installed skill merged with the new content
+fn local_changes(skill: &InstalledSkill, content: &UpdateContent) -> Result<Vec<FileMerge>, AppError> {
+    let ours = DiffService::read_tree(Path::new(&skill.installed_path))?;
+    let theirs = &content.theirs;
+
+    let Some(base) = &content.base else {
+        // Without the installed content or its hash, local edits cannot be told apart
+        if skill.content_hash.is_none() {
+            log::debug!("Cannot detect local changes to {}: no install hash", skill.skill_id);
+            return Ok(Vec::new());
+        }
+
+        return Ok(ours.keys().chain(theirs.keys())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .filter(|path| ours.get(*path) != theirs.get(*path))
+            .map(|path| DiffService::merge_file(
+                path,
+                None,
+                ours.get(path).map(Vec::as_slice),
+                theirs.get(path).map(Vec::as_slice),
+            ))
+            .collect());
+    };
+
+    Ok(ours.keys().chain(base.keys())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .filter(|path| ours.get(*path) != base.get(*path))
+        .map(|path| DiffService::merge_file(
+            path,
+            base.get(path).map(Vec::as_slice),
+            ours.get(path).map(Vec::as_slice),
+            theirs.get(path).map(Vec::as_slice),
+        ))
+        .collect())
+}
+
+/// A hidden path next to `target` for staging or keeping previous content
+fn sibling_path(target: &Path, label: &str) -> PathBuf {
+    let name = target.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let id = uuid::Uuid::new_v4().simple().to_string();
+
+    target.with_file_name(format!(".{}.{}-{}", name, label, &id[..8]))
+}
+
+/// Write skill content to `path`: a folder of files, or the single file
+fn write_tree(path: &Path, tree: &BTreeMap<String, Vec<u8>>, is_folder: bool) -> Result<(), AppError> {
+    if !is_folder {
+        if let Some(content) = tree.values().next() {
+            std::fs::write(path, content)?;
+        }
+        return Ok(());
+    }
+
+    // Keys come from `read_tree`, so they are relative paths inside the skill
+    for (relative, content) in tree {
+        let file = path.join(relative);
+        if let Some(parent) = file.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(file, content)?;
+    }
+
+    Ok(())
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else if path.exists() {
+        std::fs::remove_file(path)
+    } else {
+        Ok(())
+    }
+}
+
+/// Remove staged content that will not be committed
+fn discard(staged: &Path) {
+    if let Err(e) = remove_path(staged) {
+        log::warn!("Failed to remove {}: {}", staged.display(), e);
+    }
+}
+
+/// Put the previous content of a committed update back
+fn restore(done: &CommittedUpdate) -> std::io::Result<()> {
+    remove_path(&done.target)?;
+    match &done.previous {
+        Some(previous) => std::fs::rename(previous, &done.target),
+        None => Ok(()),
+    }
+}
+
+/// Drop the previous content of a committed update (a backup was taken)
+fn finish(done: &CommittedUpdate) {
+    if let Some(previous) = &done.previous {
+        discard(previous);
+    }
+}
+
+/// Report results in the order the updates were requested
+fn batch_report(updates: &[SkillUpdate], mut results: Vec<BatchItemResult>, rolled_back: bool) -> BatchUpdateReport {
+    results.sort_by_key(|r| updates.iter().position(|u| same_skill(u, &r.update)));
+    BatchUpdateReport { results, rolled_back }
+}
+
+fn same_skill(a: &SkillUpdate, b: &SkillUpdate) -> bool {
+    a.skill_id == b.skill_id && a.agent == b.agent
+}
+
+/// Combine release notes into one markdown changelog, newest version first
+fn format_changelog(notes: &[ReleaseNote]) -> Option<String> {
+    let sections: Vec<String> = notes.iter()
+        .filter_map(|note| {
+            let body = note.notes.clone()
+                .or_else(|| note.url.as_ref().map(|url| format!("See {}", url)))?;
+
+            let heading = match &note.released_at {
+                Some(date) => format!("## {} ({})", note.version, date),
+                None => format!("## {}", note.version),
+            };
+
+            Some(format!("{}\n\n{}", heading, body.trim()))
+        })
+        .collect();
+
+    if sections.is_empty() {
+        None
+    } else {
+        Some(sections.join("\n\n"))
+    }
+}
@@ -0,0 +1,120 @@
+use std::cmp::Ordering;
+use semver::{Version, VersionReq};
+
+use crate::models::{UpdateChannel, AppError};
+
+/// Semantic version parsing, ordering and constraint matching shared by
+/// the registry and update services.
+pub struct VersionService;
+
+impl VersionService {
+    /// Parse a version leniently: accepts a leading `v` and missing
+    /// minor/patch components (`1.2` -> `1.2.0`, `v2-beta` -> `2.0.0-beta`)
+    pub fn parse_version(version: &str) -> Option<Version> {
+        let v = version.trim().trim_start_matches(['v', 'V']);
+        if let Ok(parsed) = Version::parse(v) {
+            return Some(parsed);
+        }
+
+        let split = v.find(['-', '+']).unwrap_or(v.len());
+        let (core, suffix) = v.split_at(split);
+        let parts: Vec<&str> = core.split('.').collect();
+
+        let valid = parts.len() <= 3
+            && parts.iter().all(|p| !p.is_empty() && p.chars().all(|c| c.is_ascii_digit()));
+        if !valid {
+            return None;
+        }
+
+        let mut padded = parts.join(".");
+        for _ in parts.len()..3 {
+            padded.push_str(".0");
+        }
+
+        Version::parse(&format!("{}{}", padded, suffix)).ok()
+    }
+
+    /// Parse a version constraint such as `^1.2`, `~1.4`, `>=2 <3` or `1.2.0`.
+    /// A bare version means that version (or series, for `1.2`) exactly.
+    /// Returns None for "any version" (empty, `*` or `latest`).
+    pub fn parse_requirement(requirement: &str) -> Result<Option<VersionReq>, AppError> {
+        let trimmed = requirement.trim();
+        if matches!(trimmed, "" | "*" | "latest") {
+            return Ok(None);
+        }
+
+        // Accept npm-style space separated comparators (">=2 <3", ">= 2")
+        let mut comparators: Vec<String> = Vec::new();
+        let mut pending_op = String::new();
+        for token in trimmed.split(|c: char| c.is_whitespace() || c == ',').filter(|t| !t.is_empty()) {
+            if token.chars().all(|c| "<>=~^".contains(c)) {
+                pending_op.push_str(token);
+                continue;
+            }
+
+            let token = token.trim_start_matches(['v', 'V']);
+            let op = if !pending_op.is_empty() {
+                std::mem::take(&mut pending_op)
+            } else if token.starts_with(|c: char| c.is_ascii_digit()) {
+                "=".to_string()
+            } else {
+                String::new()
+            };
+            comparators.push(format!("{}{}", op, token));
+        }
+
+        VersionReq::parse(&comparators.join(", "))
+            .map(Some)
+            .map_err(|e| AppError::ParseError(format!("Invalid version constraint '{}': {}", requirement, e)))
+    }
+
+    /// Whether `version` satisfies a constraint (None = any version).
+    /// Unparseable versions only satisfy an identical constraint string.
+    pub fn satisfies(requirement: Option<&str>, version: &str) -> bool {
+        let Some(requirement) = requirement else {
+            return true;
+        };
+
+        match (Self::parse_requirement(requirement), Self::parse_version(version)) {
+            (Ok(None), _) => true,
+            (Ok(Some(req)), Some(v)) => req.matches(&v),
+            _ => requirement.trim().trim_start_matches('=') == version.trim(),
+        }
+    }
+
+    /// Order two versions by semver precedence (build metadata is ignored).
+    /// Falls back to plain string comparison when either is not a version.
+    pub fn compare(a: &str, b: &str) -> Ordering {
+        match (Self::parse_version(a), Self::parse_version(b)) {
+            (Some(a), Some(b)) => (a.major, a.minor, a.patch, &a.pre)
+                .cmp(&(b.major, b.minor, b.patch, &b.pre)),
+            _ => a.cmp(b),
+        }
+    }
+
+    pub fn is_prerelease(version: &str) -> bool {
+        Self::parse_version(version).is_some_and(|v| !v.pre.is_empty())
+    }
+
+    pub fn is_major_update(current: &str, available: &str) -> bool {
+        match (Self::parse_version(current), Self::parse_version(available)) {
+            (Some(c), Some(a)) => a.major > c.major,
+            _ => false,
+        }
+    }
+
+    /// Whether `candidate` is a valid update from `current`: strictly newer
+    /// (never a downgrade), allowed by the channel and within the pin.
+    pub fn is_update_allowed(
+        current: &str,
+        candidate: &str,
+        pin: Option<&str>,
+        channel: &UpdateChannel,
+    ) -> bool {
+        if *channel == UpdateChannel::Stable && Self::is_prerelease(candidate) {
+            return false;
+        }
+
+        Self::compare(current, candidate) == Ordering::Less && Self::satisfies(pin, candidate)
+    }
+}
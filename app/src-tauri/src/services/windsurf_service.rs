@@ -0,0 +1,252 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::{AgentType, AppError, Skill, SkillKind, WindsurfRule, WindsurfTrigger};
+use crate::services::rule_service::{fields, other_lines, split_frontmatter, unquote};
+use crate::services::template_service::yaml_string;
+use crate::services::{SkillService, TemplateService};
+
+/// Frontmatter keys managed through `WindsurfRule`
+const RULE_KEYS: [&str; 3] = ["trigger", "description", "globs"];
+
+/// Character limit of `global_rules.md`
+const GLOBAL_RULES_LIMIT: usize = 6_000;
+
+/// Character limit of a workspace rule or workflow file
+const FILE_LIMIT: usize = 12_000;
+
+/// Windsurf global rules, workspace rules and workflows
+pub struct WindsurfService;
+
+impl WindsurfService {
+    /// Workspace rules directory of a project
+    pub fn rules_dir(project_dir: &Path) -> PathBuf {
+        project_dir.join(".windsurf").join("rules")
+    }
+
+    /// Workflows directory of a project
+    pub fn workflows_dir(project_dir: &Path) -> PathBuf {
+        project_dir.join(".windsurf").join("workflows")
+    }
+
+    /// Workflows available in every workspace, under `~/.codeium/windsurf`
+    pub fn global_workflows_dir(windsurf_dir: &Path) -> PathBuf {
+        windsurf_dir.join("global_workflows")
+    }
+
+    /// Rules applied in every workspace, under `~/.codeium/windsurf`
+    pub fn global_rules_path(windsurf_dir: &Path) -> PathBuf {
+        windsurf_dir.join("memories").join("global_rules.md")
+    }
+
+    /// Whether Windsurf reads the file as a rule or workflow. Other files in
+    /// `~/.codeium` (settings, MCP config, caches) are not skills.
+    pub fn is_windsurf_file(path: &Path) -> bool {
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        file_name == ".windsurfrules" || file_name.ends_with(".md")
+    }
+
+    /// Kind of a file from its place in `.windsurf` or `~/.codeium/windsurf`
+    pub fn kind_for_path(path: &Path) -> SkillKind {
+        let file_name = path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if file_name == ".windsurfrules" || file_name == "global_rules.md" {
+            return SkillKind::Memory;
+        }
+
+        let in_workflows = path.parent()
+            .and_then(|p| p.file_name())
+            .is_some_and(|n| n == "workflows" || n == "global_workflows");
+        if in_workflows {
+            SkillKind::SlashCommand
+        } else {
+            SkillKind::Rule
+        }
+    }
+
+    /// Split a workspace rule into its activation settings and body. Files
+    /// without frontmatter are manual rules.
+    pub fn parse(content: &str) -> (WindsurfRule, &str) {
+        let Some((lines, body)) = split_frontmatter(content) else {
+            return (WindsurfRule::default(), content);
+        };
+
+        let mut rule = WindsurfRule::default();
+        for (key, values) in fields(&lines) {
+            match key {
+                "trigger" => {
+                    rule.trigger = values.first()
+                        .and_then(|v| WindsurfTrigger::parse(unquote(v)))
+                        .unwrap_or_default();
+                }
+                "description" => {
+                    rule.description = Some(unquote(&values.join(" ")).to_string())
+                        .filter(|d| !d.is_empty());
+                }
+                "globs" => {
+                    rule.globs = values.iter()
+                        .flat_map(|v| v.trim_start_matches('[').trim_end_matches(']').split(','))
+                        .map(|glob| unquote(glob).to_string())
+                        .filter(|glob| !glob.is_empty())
+                        .collect();
+                }
+                _ => {}
+            }
+        }
+
+        (rule, body)
+    }
+
+    /// A rule file with the rule's frontmatter followed by `body`
+    pub fn render(rule: &WindsurfRule, body: &str) -> String {
+        render_with(rule, &[], body)
+    }
+
+    /// Rule settings for a trigger, checking the trigger has what it needs
+    pub fn rule_for_trigger(
+        trigger: WindsurfTrigger,
+        description: Option<String>,
+        globs: Vec<String>,
+    ) -> Result<WindsurfRule, AppError> {
+        let description = description.map(|d| d.trim().to_string()).filter(|d| !d.is_empty());
+        let globs: Vec<String> = globs.into_iter()
+            .map(|g| g.trim().to_string())
+            .filter(|g| !g.is_empty())
+            .collect();
+
+        match trigger {
+            WindsurfTrigger::Glob if globs.is_empty() => {
+                return Err(AppError::ParseError("Glob rules need at least one glob".into()));
+            }
+            WindsurfTrigger::ModelDecision if description.is_none() => {
+                return Err(AppError::ParseError("Model decision rules need a description".into()));
+            }
+            _ => {}
+        }
+
+        let globs = if trigger == WindsurfTrigger::Glob { globs } else { Vec::new() };
+        Ok(WindsurfRule { trigger, description, globs })
+    }
+
+    /// Change when a workspace rule applies, keeping its body and any other
+    /// frontmatter keys
+    pub fn set_trigger(
+        file_path: &str,
+        trigger: WindsurfTrigger,
+        description: Option<String>,
+        globs: Vec<String>,
+    ) -> Result<Skill, AppError> {
+        let path = Path::new(file_path);
+        if !path.is_file() {
+            return Err(AppError::FileNotFound(file_path.to_string()));
+        }
+
+        let rule = Self::rule_for_trigger(trigger, description, globs)?;
+        let content = fs::read_to_string(path)?;
+
+        let updated = match split_frontmatter(&content) {
+            Some((lines, body)) => render_with(&rule, &other_lines(&lines, &RULE_KEYS), body),
+            None => Self::render(&rule, &content),
+        };
+
+        fs::write(path, updated)?;
+        SkillService::parse_single_file(path, &AgentType::Windsurf)
+    }
+
+    /// Create `<rules_dir>/<name>.md`, using the rule template when no
+    /// content is given
+    pub fn create_rule(
+        rules_dir: &Path,
+        name: &str,
+        rule: &WindsurfRule,
+        content: Option<&str>,
+    ) -> Result<Skill, AppError> {
+        let body = match content {
+            Some(content) => content.to_string(),
+            None => TemplateService::windsurf_rule_body(name),
+        };
+
+        create_file(rules_dir, name, &Self::render(rule, &body))
+    }
+
+    /// Create `<workflows_dir>/<name>.md`, run in Cascade as `/<name>`
+    pub fn create_workflow(
+        workflows_dir: &Path,
+        name: &str,
+        description: Option<&str>,
+        content: Option<&str>,
+    ) -> Result<Skill, AppError> {
+        let content = match (content, description) {
+            (Some(content), Some(description)) if split_frontmatter(content).is_none() => {
+                format!("---\ndescription: {}\n---\n\n{}", yaml_string(description), content)
+            }
+            (Some(content), _) => content.to_string(),
+            (None, _) => TemplateService::windsurf_workflow_template(name, description),
+        };
+
+        create_file(workflows_dir, name, &content)
+    }
+
+    /// Problems that would stop Windsurf from loading a file, or make it
+    /// behave unexpectedly. Empty when the file is valid.
+    pub fn validate(file_name: &str, kind: SkillKind, content: &str) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let limit = if file_name == "global_rules.md" { GLOBAL_RULES_LIMIT } else { FILE_LIMIT };
+        let length = content.chars().count();
+        if length > limit {
+            problems.push(format!(
+                "{} characters is over Windsurf's limit of {}; the rest is ignored",
+                length, limit
+            ));
+        }
+
+        let body = split_frontmatter(content).map_or(content, |(_, body)| body);
+        if body.trim().is_empty() {
+            problems.push("Instructions are empty".to_string());
+        }
+
+        if kind == SkillKind::Rule {
+            let (rule, _) = Self::parse(content);
+            if let Err(e) = Self::rule_for_trigger(rule.trigger, rule.description, rule.globs) {
+                problems.push(e.to_string());
+            }
+        }
+
+        problems
+    }
+}
+
+fn create_file(dir: &Path, name: &str, content: &str) -> Result<Skill, AppError> {
+    let path = dir.join(format!("{}.md", name.trim().to_lowercase().replace(' ', "-")));
+    if path.exists() {
+        return Err(AppError::AlreadyExists(path.to_string_lossy().to_string()));
+    }
+
+    fs::create_dir_all(dir)?;
+    fs::write(&path, content)?;
+
+    SkillService::parse_single_file(&path, &AgentType::Windsurf)
+}
+
+fn render_with(rule: &WindsurfRule, extra: &[&str], body: &str) -> String {
+    let mut out = String::from("---\n");
+    out.push_str(&format!("trigger: {}\n", rule.trigger.as_str()));
+    if let Some(description) = &rule.description {
+        out.push_str(&format!("description: {}\n", description));
+    }
+    if !rule.globs.is_empty() {
+        out.push_str(&format!("globs: {}\n", rule.globs.join(",")));
+    }
+    for line in extra {
+        out.push_str(line);
+        out.push('\n');
+    }
+    out.push_str("---\n");
+    out.push_str(body);
+    out
+}
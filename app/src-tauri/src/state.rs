@@ -0,0 +1,30 @@
+use tokio::sync::{Mutex, RwLock};
+
+use crate::models::{AgentConfig, Skill};
+use crate::services::ScanIndex;
+
+/// Application state shared by the skill commands, registered with
+/// `tauri::Builder::manage`
+pub struct AppState {
+    /// Skills found by the last scan, kept current by the CRUD commands
+    pub skills: RwLock<Vec<Skill>>,
+    pub agent_configs: RwLock<Vec<AgentConfig>>,
+    /// Loaded from disk by the first scan, then kept in memory
+    pub scan_index: Mutex<Option<ScanIndex>>,
+}
+
+impl AppState {
+    pub fn new() -> Self {
+        Self {
+            skills: RwLock::new(Vec::new()),
+            agent_configs: RwLock::new(AgentConfig::defaults()),
+            scan_index: Mutex::new(None),
+        }
+    }
+}
+
+impl Default for AppState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
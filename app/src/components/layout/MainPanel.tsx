@@ -19,6 +19,7 @@ const FORMAT_ICONS: Record<SkillFormat, { label: string; class: string }> = {
   Yaml: { label: 'YML', class: 'file-icon-yaml' },
   Python: { label: 'PY', class: 'file-icon-py' },
   PlainText: { label: 'TXT', class: 'file-icon-txt' },
+  Mdc: { label: 'MDC', class: 'file-icon-md' },
 };
 
 export function MainPanel() {
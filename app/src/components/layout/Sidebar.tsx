@@ -8,6 +8,10 @@ const AGENT_CONFIG: Record<string, { color: string; gradient: string }> = {
   ContinueDev: { color: 'bg-blue-500', gradient: 'from-blue-500 to-blue-600' },
   Aider: { color: 'bg-green-500', gradient: 'from-green-500 to-green-600' },
   Windsurf: { color: 'bg-cyan-500', gradient: 'from-cyan-500 to-cyan-600' },
+  Copilot: { color: 'bg-slate-500', gradient: 'from-slate-500 to-slate-600' },
+  Codex: { color: 'bg-emerald-500', gradient: 'from-emerald-500 to-emerald-600' },
+  Gemini: { color: 'bg-indigo-500', gradient: 'from-indigo-500 to-indigo-600' },
+  Cline: { color: 'bg-pink-500', gradient: 'from-pink-500 to-pink-600' },
 };
 
 function getAgentKey(agent: AgentType): string {
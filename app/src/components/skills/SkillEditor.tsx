@@ -18,6 +18,7 @@ interface SkillEditorProps {
 function getLanguageExtension(format: SkillFormat) {
   switch (format) {
     case 'Markdown':
+    case 'Mdc':
       return markdown();
     case 'Json':
       return json();
@@ -1,13 +1,45 @@
 import { invoke } from '@tauri-apps/api/core';
+import { listen } from '@tauri-apps/api/event';
 import type {
   Skill,
   SkillFile,
   AgentConfig,
+  RuleMode,
+  EffectiveRules,
+  ClaudeContext,
+  WindsurfTrigger,
+  AiderConventions,
+  McpScope,
+  McpServer,
+  McpServerEntry,
+  SkillKind,
   SkillRegistry,
   RemoteSkill,
   InstalledSkill,
   SkillUpdate,
   UpdateCheckResult,
+  UpdateDetails,
+  UpdateMerge,
+  MergeResolution,
+  BatchUpdateOptions,
+  BatchUpdateReport,
+  SkipMode,
+  SkippedVersion,
+  GitDiscovery,
+  DiscoveredSkill,
+  BundleManifest,
+  BundlePreview,
+  BundleImportOptions,
+  Lockfile,
+  SyncReport,
+  DriftItem,
+  ReconcileFix,
+  ReconcileReport,
+  UpdateChannel,
+  UpdatePolicy,
+  SchedulerRun,
+  SchedulerSettings,
+  SchedulerState,
   User,
   PublishResponse,
 } from './types';
@@ -16,7 +48,10 @@ import type {
 export const api = {
   skills: {
     scan: () => invoke<Skill[]>('scan_skills'),
+    onScanProgress: (handler: (skills: Skill[]) => void) =>
+      listen<{ skills: Skill[] }>('skills://scan-progress', (event) => handler(event.payload.skills)),
     getAll: () => invoke<Skill[]>('get_all_skills'),
+    scanProject: (projectDir: string) => invoke<Skill[]>('scan_project_skills', { projectDir }),
     getByAgent: (agent: string) => invoke<Skill[]>('get_skills_by_agent', { agent }),
     getById: (id: string) => invoke<Skill | null>('get_skill_by_id', { id }),
     getFiles: (folderPath: string) => invoke<SkillFile[]>('get_skill_files', { folderPath }),
@@ -33,6 +68,109 @@ export const api = {
     deleteFile: (filePath: string) => invoke<void>('delete_skill_file', { filePath }),
   },
 
+  claude: {
+    create: (
+      kind: SkillKind,
+      name: string,
+      options?: { projectDir?: string; description?: string; content?: string }
+    ) =>
+      invoke<Skill>('create_claude_item', {
+        projectDir: options?.projectDir,
+        kind,
+        name,
+        description: options?.description,
+        content: options?.content,
+      }),
+    template: (kind: SkillKind, name: string, description?: string) =>
+      invoke<string>('get_claude_template', { kind, name, description }),
+    validate: (kind: SkillKind, content: string) =>
+      invoke<string[]>('validate_claude_item', { kind, content }),
+    context: (directory: string) => invoke<ClaudeContext>('get_claude_context', { directory }),
+  },
+
+  rules: {
+    scanProject: (projectDir: string) => invoke<Skill[]>('scan_project_rules', { projectDir }),
+    create: (
+      name: string,
+      mode: RuleMode,
+      options?: { projectDir?: string; description?: string; globs?: string[]; content?: string }
+    ) =>
+      invoke<Skill>('create_cursor_rule', {
+        projectDir: options?.projectDir,
+        name,
+        mode,
+        description: options?.description,
+        globs: options?.globs ?? [],
+        content: options?.content,
+      }),
+    setMode: (filePath: string, mode: RuleMode, description?: string, globs: string[] = []) =>
+      invoke<Skill>('set_cursor_rule_mode', { filePath, mode, description, globs }),
+    effective: (path: string) => invoke<EffectiveRules>('effective_rules', { path }),
+  },
+  windsurf: {
+    createRule: (
+      projectDir: string,
+      name: string,
+      trigger: WindsurfTrigger,
+      options?: { description?: string; globs?: string[]; content?: string }
+    ) =>
+      invoke<Skill>('create_windsurf_rule', {
+        projectDir,
+        name,
+        trigger,
+        description: options?.description,
+        globs: options?.globs ?? [],
+        content: options?.content,
+      }),
+    createWorkflow: (name: string, options?: { projectDir?: string; description?: string; content?: string }) =>
+      invoke<Skill>('create_windsurf_workflow', {
+        projectDir: options?.projectDir,
+        name,
+        description: options?.description,
+        content: options?.content,
+      }),
+    setTrigger: (filePath: string, trigger: WindsurfTrigger, description?: string, globs: string[] = []) =>
+      invoke<Skill>('set_windsurf_rule_trigger', { filePath, trigger, description, globs }),
+    validate: (filePath: string, content: string) =>
+      invoke<string[]>('validate_windsurf_file', { filePath, content }),
+  },
+  aider: {
+    conventions: (projectDir?: string) =>
+      invoke<AiderConventions>('get_aider_conventions', { projectDir }),
+  },
+
+  mcp: {
+    list: (projectDir?: string) => invoke<McpServerEntry[]>('list_mcp_servers', { projectDir }),
+    validate: (agent: string, server: McpServer) =>
+      invoke<string[]>('validate_mcp_server', { agent, server }),
+    save: (agent: string, configPath: string, server: McpServer, previousName?: string) =>
+      invoke<McpServer>('save_mcp_server', { agent, configPath, server, previousName }),
+    remove: (agent: string, configPath: string, name: string) =>
+      invoke<void>('remove_mcp_server', { agent, configPath, name }),
+    copy: (
+      server: McpServer,
+      agents: string[],
+      scope: McpScope,
+      options?: { projectDir?: string; overwrite?: boolean }
+    ) =>
+      invoke<McpServerEntry[]>('copy_mcp_server', {
+        server,
+        agents,
+        scope,
+        projectDir: options?.projectDir,
+        overwrite: options?.overwrite ?? false,
+      }),
+  },
+
+  bundles: {
+    export: (skills: Skill[], destPath: string) =>
+      invoke<BundleManifest>('export_skill_bundle', { skills, destPath }),
+    preview: (bundlePath: string, agent?: string) =>
+      invoke<BundlePreview>('preview_skill_bundle', { bundlePath, agent }),
+    import: (bundlePath: string, options: BundleImportOptions) =>
+      invoke<Skill[]>('import_skill_bundle', { bundlePath, options }),
+  },
+
   agents: {
     getConfigs: () => invoke<AgentConfig[]>('get_agent_configs'),
     updateConfig: (config: AgentConfig) => invoke<void>('update_agent_config', { config }),
@@ -40,13 +178,29 @@ export const api = {
 
   registry: {
     fetch: (url: string) => invoke<SkillRegistry>('fetch_registry', { url }),
-    install: (skill: RemoteSkill, registryUrl: string, agent: string) =>
-      invoke<InstalledSkill>('install_remote_skill', { skill, registryUrl, agent }),
+    install: (skill: RemoteSkill, registryUrl: string, agent: string, version?: string) =>
+      invoke<InstalledSkill>('install_remote_skill', { skill, registryUrl, agent, version }),
     uninstall: (skillId: string, agent: string) =>
       invoke<void>('uninstall_remote_skill', { skillId, agent }),
     getInstalled: () => invoke<InstalledSkill[]>('get_installed_skills'),
     checkUpdates: (registryUrl: string) =>
       invoke<SkillUpdate[]>('check_skill_updates', { registryUrl }),
+    discoverGit: (url: string, agent: string, gitRef?: string, subdirectory?: string) =>
+      invoke<GitDiscovery>('discover_git_skills', { url, gitRef, subdirectory, agent }),
+    installGit: (url: string, paths: string[], agent: string, gitRef?: string) =>
+      invoke<InstalledSkill[]>('install_git_skills', { url, gitRef, paths, agent }),
+    discoverLocal: (path: string, agent: string) =>
+      invoke<DiscoveredSkill[]>('discover_local_skills', { path, agent }),
+    installLocal: (path: string, paths: string[], agent: string) =>
+      invoke<InstalledSkill[]>('install_local_skills', { path, paths, agent }),
+    lockProject: (projectDir: string) =>
+      invoke<Lockfile>('lock_project_skills', { projectDir }),
+    syncProject: (projectDir: string, checkOnly: boolean) =>
+      invoke<SyncReport>('sync_project_skills', { projectDir, checkOnly }),
+    reconcile: (registryUrls: string[]) =>
+      invoke<ReconcileReport>('reconcile_installed_skills', { registryUrls }),
+    fixDrift: (item: DriftItem, fix: ReconcileFix) =>
+      invoke<InstalledSkill | null>('fix_installed_skill', { item, fix }),
   },
 
   auth: {
@@ -66,12 +220,40 @@ export const api = {
 
   updates: {
     check: () => invoke<UpdateCheckResult>('check_for_updates'),
-    apply: (update: SkillUpdate) => invoke<void>('apply_skill_update', { update }),
-    applyAll: (updates: SkillUpdate[]) =>
-      invoke<Array<{ Ok?: null; Err?: string }>>('apply_all_skill_updates', { updates }),
-    skip: (skillId: string, version: string) =>
-      invoke<void>('skip_skill_version', { skillId, version }),
+    details: (update: SkillUpdate) => invoke<UpdateDetails>('get_update_details', { update }),
+    previewMerge: (update: SkillUpdate) => invoke<UpdateMerge>('preview_update_merge', { update }),
+    apply: (update: SkillUpdate, resolutions?: Record<string, MergeResolution>) =>
+      invoke<void>('apply_skill_update', { update, resolutions }),
+    applyAll: (updates: SkillUpdate[], options?: BatchUpdateOptions) =>
+      invoke<BatchUpdateReport>('apply_all_skill_updates', { updates, options }),
+    skip: (
+      skillId: string,
+      version: string,
+      scope?: { agent?: string; registryUrl?: string },
+      mode?: SkipMode
+    ) =>
+      invoke<void>('skip_skill_version', {
+        skillId,
+        version,
+        agent: scope?.agent,
+        registryUrl: scope?.registryUrl,
+        mode,
+      }),
+    listSkipped: () => invoke<SkippedVersion[]>('list_skipped_versions'),
+    unskip: (skip: SkippedVersion) => invoke<void>('unskip_version', { skip }),
     rollback: (skillId: string, agent: string) =>
       invoke<void>('rollback_skill', { skillId, agent }),
+    pin: (skillId: string, agent: string, pin: string | null) =>
+      invoke<InstalledSkill>('pin_skill_version', { skillId, agent, pin }),
+    setChannel: (skillId: string, agent: string, channel: UpdateChannel) =>
+      invoke<InstalledSkill>('set_skill_update_channel', { skillId, agent, channel }),
+    setPolicy: (skillId: string, agent: string, policy: UpdatePolicy) =>
+      invoke<InstalledSkill>('set_skill_update_policy', { skillId, agent, policy }),
+    getScheduler: () => invoke<SchedulerState>('get_update_scheduler'),
+    setScheduler: (settings: SchedulerSettings) =>
+      invoke<SchedulerState>('set_update_scheduler', { settings }),
+    runScheduler: () => invoke<SchedulerRun>('run_update_scheduler'),
+    onScheduledRun: (handler: (run: SchedulerRun) => void) =>
+      listen<SchedulerRun>('updates://scheduled-run', (event) => handler(event.payload)),
   },
 };
@@ -1,8 +1,140 @@
 // Agent types
-export type AgentType = 'Claude' | 'Cursor' | 'ContinueDev' | 'Aider' | 'Windsurf' | { Custom: string };
+export type AgentType = 'Claude' | 'Cursor' | 'ContinueDev' | 'Aider' | 'Windsurf' | 'Copilot' | 'Codex' | 'Gemini' | 'Cline' | { Custom: string };
 
 // Skill format types
-export type SkillFormat = 'Markdown' | 'Json' | 'Yaml' | 'Python' | 'PlainText';
+export type SkillFormat = 'Markdown' | 'Json' | 'Yaml' | 'Python' | 'PlainText' | 'Mdc';
+
+// What a file is to the agent that reads it
+export type SkillKind = 'Skill' | 'Subagent' | 'SlashCommand' | 'Memory' | 'Rule' | 'OutputStyle';
+
+// Frontmatter of Claude skills, subagents, slash commands and output styles
+export interface ClaudeFrontmatter {
+  name: string | null;
+  description: string | null;
+  tools: string[];
+  model: string | null;
+  argument_hint: string | null;
+  allowed_tools: string[];
+}
+
+// Why a CLAUDE.md file is part of Claude's context
+export type MemoryLevel = 'User' | 'Project' | 'Local' | 'Nested' | 'Import';
+
+// A file in Claude's effective context
+export interface MemorySource {
+  skill: Skill;
+  level: MemoryLevel;
+  imported_from: string | null;
+  depth: number;
+}
+
+export type ImportProblem = 'Missing' | 'Cycle' | 'TooDeep';
+
+// An `@` import that was not loaded
+export interface ImportIssue {
+  file: string;
+  import: string;
+  path: string;
+  problem: ImportProblem;
+}
+
+// Everything Claude loads as memory when started in a directory
+export interface ClaudeContext {
+  directory: string;
+  sources: MemorySource[];
+  text: string;
+  issues: ImportIssue[];
+}
+
+// How Cursor attaches a rule to the conversation
+export type RuleMode = 'Always' | 'AutoAttached' | 'AgentRequested' | 'Manual';
+
+// Frontmatter of a Cursor .mdc rule
+export interface CursorRule {
+  description: string | null;
+  globs: string[];
+  always_apply: boolean;
+}
+
+// When Windsurf applies a workspace rule
+export type WindsurfTrigger = 'AlwaysOn' | 'Manual' | 'ModelDecision' | 'Glob';
+
+// Frontmatter of a Windsurf .windsurf/rules/*.md rule
+export interface WindsurfRule {
+  trigger: WindsurfTrigger;
+  description: string | null;
+  globs: string[];
+}
+
+// Why an agent applies a rule to a file
+export type MatchReason = 'Always' | 'Glob' | 'Directory' | 'Import' | 'Description';
+
+// A rule an agent applies to a file, and why
+export interface RuleMatch {
+  skill: Skill;
+  reason: MatchReason;
+  pattern: string | null;
+  explanation: string;
+}
+
+// Rules each agent applies when working on a file
+export interface EffectiveRules {
+  path: string;
+  project_dir: string;
+  rules: RuleMatch[];
+}
+
+// Part of a Continue config file an entry comes from
+export type ContinueSection = 'SystemMessage' | 'Rules' | 'Prompts' | 'CustomCommands' | 'SlashCommands';
+
+// Where a prompt, rule or command lives inside a Continue config file
+export interface ContinueEntry {
+  config_path: string;
+  section: ContinueSection;
+  index: number;
+}
+
+// A file named in the `read:` list of an Aider config
+export interface AiderReadFile {
+  entry: string;
+  path: string;
+  config_path: string;
+  exists: boolean;
+  loaded: boolean;
+}
+
+// Which conventions Aider loads, and which it doesn't
+export interface AiderConventions {
+  configs: string[];
+  effective_config: string | null;
+  files: AiderReadFile[];
+  unreferenced: string[];
+}
+
+export type McpTransport = 'stdio' | 'http' | 'sse';
+
+export type McpScope = 'user' | 'project';
+
+// An MCP server definition, independent of any agent's config format
+export interface McpServer {
+  name: string;
+  transport: McpTransport;
+  command: string | null;
+  args: string[];
+  env: Record<string, string>;
+  url: string | null;
+  headers: Record<string, string>;
+  disabled: boolean;
+}
+
+// An MCP server found in an agent's config file
+export interface McpServerEntry {
+  server: McpServer;
+  agent: AgentType;
+  scope: McpScope;
+  config_path: string;
+  editable: boolean;
+}
 
 // A file within a skill folder
 export interface SkillFile {
@@ -30,6 +162,11 @@ export interface Skill {
   file_count: number;
   created_at: number;
   updated_at: number;
+  kind?: SkillKind;
+  rule?: CursorRule | null;
+  windsurf?: WindsurfRule | null;
+  claude?: ClaudeFrontmatter | null;
+  continue_entry?: ContinueEntry | null;
 }
 
 // Agent configuration
@@ -39,6 +176,7 @@ export interface AgentConfig {
   config_dir: string;
   skills_dir: string | null;
   file_patterns: string[];
+  project_patterns: string[];
   enabled: boolean;
 }
 
@@ -63,6 +201,18 @@ export interface RemoteSkill {
   files: SkillFiles;
   url: string | null;
   checksum: string | null;
+  versions: SkillVersion[];
+  mcp_servers: McpServer[];
+}
+
+export interface SkillVersion {
+  version: string;
+  files: SkillFiles | null;
+  checksum: string | null;
+  released_at: string | null;
+  changelog: string | null;
+  changelog_url: string | null;
+  yanked: boolean;
 }
 
 export interface SkillFiles {
@@ -71,6 +221,10 @@ export interface SkillFiles {
   continue_dev: string | null;
   aider: string | null;
   windsurf: string | null;
+  copilot: string | null;
+  codex: string | null;
+  gemini: string | null;
+  cline: string | null;
 }
 
 export interface InstalledSkill {
@@ -80,6 +234,56 @@ export interface InstalledSkill {
   installed_path: string;
   agent: string;
   installed_at: number;
+  source: InstallSource;
+  commit_sha: string | null;
+  pinned_version: string | null;
+  channel: UpdateChannel;
+  content_hash: string | null;
+  update_policy: UpdatePolicy;
+  mcp_servers: string[];
+  lockfiles: string[];
+}
+
+export type UpdateChannel = 'stable' | 'beta';
+
+export type InstallSource =
+  | { type: 'registry' }
+  | ({ type: 'git' } & GitSource)
+  | { type: 'local'; path: string }
+  | { type: 'archive'; path: string };
+
+export type DriftKind = 'orphan' | 'missing' | 'modified' | 'untracked';
+
+export type ReconcileFix = 'forget' | 'reinstall' | 'adopt';
+
+export interface DriftItem {
+  kind: DriftKind;
+  skill_id: string;
+  agent: string;
+  path: string;
+  registry_url: string | null;
+  fixes: ReconcileFix[];
+}
+
+export interface ReconcileReport {
+  items: DriftItem[];
+  checked: number;
+}
+
+export interface GitSource {
+  url: string;
+  git_ref: string | null;
+  subdirectory: string | null;
+}
+
+export interface DiscoveredSkill {
+  path: string;
+  skill: Skill;
+}
+
+export interface GitDiscovery {
+  commit_sha: string;
+  skills: DiscoveredSkill[];
 }
 
 export interface SkillUpdate {
@@ -99,6 +303,177 @@ export interface UpdateCheckResult {
   error: string | null;
 }
 
+export interface ReleaseNote {
+  version: string;
+  released_at: string | null;
+  notes: string | null;
+  url: string | null;
+}
+
+export type FileChange = 'added' | 'removed' | 'modified';
+
+export interface FileDiff {
+  path: string;
+  change: FileChange;
+  patch: string | null;
+}
+
+export interface UpdateDetails {
+  update: SkillUpdate;
+  release_notes: ReleaseNote[];
+  diffs: FileDiff[];
+}
+
+export type UpdatePolicy = 'notify' | 'auto_patch' | 'auto_minor' | 'pinned';
+
+export interface SchedulerSettings {
+  enabled: boolean;
+  interval_minutes: number;
+}
+
+export interface FailedUpdate {
+  update: SkillUpdate;
+  error: string;
+}
+
+export interface SchedulerRun {
+  started_at: number;
+  new_updates: SkillUpdate[];
+  applied: SkillUpdate[];
+  failed: FailedUpdate[];
+}
+
+export interface SchedulerState {
+  settings: SchedulerSettings;
+  last_checked: number | null;
+  last_result: UpdateCheckResult | null;
+  last_run: SchedulerRun | null;
+}
+
+export type MergeStatus = 'merged' | 'conflict';
+
+export type MergeResolution = 'merge' | 'keep_mine' | 'take_theirs';
+
+export interface FileMerge {
+  path: string;
+  status: MergeStatus;
+  merged: string | null;
+}
+
+export interface UpdateMerge {
+  update: SkillUpdate;
+  locally_modified: boolean;
+  files: FileMerge[];
+}
+
+export interface BatchUpdateOptions {
+  all_or_nothing?: boolean;
+  max_concurrent?: number;
+}
+
+export type SkipMode = 'version' | 'until_major' | 'ignore';
+
+export interface SkippedVersion {
+  skill_id: string;
+  version: string;
+  agent: string | null;
+  registry_url: string | null;
+  mode: SkipMode;
+  skipped_at: number;
+}
+
+export type BatchOutcome = 'applied' | 'skipped' | 'failed' | 'rolled_back';
+
+export interface BatchItemResult {
+  update: SkillUpdate;
+  outcome: BatchOutcome;
+  reason: string | null;
+}
+
+export interface BatchUpdateReport {
+  results: BatchItemResult[];
+  rolled_back: boolean;
+}
+
+// Project manifest / lockfile types
+export interface LockedSkill {
+  id: string;
+  version: string;
+  agent: string;
+  registry: string;
+  source: string;
+  checksum: string;
+}
+
+export interface Lockfile {
+  version: number;
+  skills: LockedSkill[];
+}
+
+export type SyncActionKind = 'install' | 'update' | 'remove';
+
+export interface SyncAction {
+  kind: SyncActionKind;
+  skill_id: string;
+  agent: string;
+  installed_version: string | null;
+  locked_version: string | null;
+  reason: string;
+}
+
+export interface SyncReport {
+  actions: SyncAction[];
+  in_sync: boolean;
+  applied: boolean;
+}
+
+// Skill bundle types
+export interface BundleFile {
+  path: string;
+  sha256: string;
+  size: number;
+  is_entry: boolean;
+}
+
+export interface BundleSkill {
+  name: string;
+  dir: string;
+  agent: AgentType;
+  description: string | null;
+  version: string | null;
+  tags: string[];
+  author: string | null;
+  is_folder: boolean;
+  original_path: string;
+  files: BundleFile[];
+}
+
+export interface BundleManifest {
+  format_version: number;
+  created_at: number;
+  skills: BundleSkill[];
+}
+
+export type ConflictStrategy = 'skip' | 'overwrite' | 'rename';
+
+export interface BundleImportOptions {
+  agent?: string | null;
+  skills?: string[] | null;
+  on_conflict?: ConflictStrategy;
+  convert?: boolean;
+}
+
+export interface BundleConflict {
+  name: string;
+  existing_path: string;
+}
+
+export interface BundlePreview {
+  manifest: BundleManifest;
+  conflicts: BundleConflict[];
+  corrupted_files: string[];
+}
+
 // Auth types
 export interface User {
   id: string;
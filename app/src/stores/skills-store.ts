@@ -28,12 +28,18 @@ export const useSkillsStore = create<SkillsState>((set) => ({
   error: null,
 
   scanSkills: async () => {
-    set({ isLoading: true, error: null });
+    set({ skills: [], isLoading: true, error: null });
+    // Show skills as they are found; the final result replaces the partial list
+    const unlisten = await api.skills.onScanProgress((found) => {
+      set((state) => ({ skills: [...state.skills, ...found] }));
+    });
     try {
       const skills = await api.skills.scan();
       set({ skills, isLoading: false });
     } catch (error) {
       set({ error: String(error), isLoading: false });
+    } finally {
+      unlisten();
     }
   },
 
@@ -1,5 +1,6 @@
 import { create } from 'zustand';
 import { api } from '@/lib/api';
+import type { SkipMode } from '@/lib/types';
 
 export interface SkillUpdate {
   skill_id: string;
@@ -27,7 +28,8 @@ interface UpdatesState {
   checkForUpdates: () => Promise<void>;
   applyUpdate: (update: SkillUpdate) => Promise<void>;
   applyAllUpdates: () => Promise<void>;
-  skipVersion: (skillId: string, version: string) => Promise<void>;
+  /** Skip an update for its agent, or for every agent with `allAgents` */
+  skipVersion: (update: SkillUpdate, mode?: SkipMode, allAgents?: boolean) => Promise<void>;
   rollback: (skillId: string, agent: string) => Promise<void>;
   clearError: () => void;
 }
@@ -70,16 +72,17 @@ export const useUpdatesStore = create<UpdatesState>((set, get) => ({
   applyAllUpdates: async () => {
     const { updates } = get();
     try {
-      const results = await api.updates.applyAll(updates);
-      // Filter out successfully updated skills
-      const failedIndices = results
-        .map((r, i) => (r.Err ? i : -1))
-        .filter((i) => i !== -1);
+      const report = await api.updates.applyAll(updates);
+      // Keep updates that were not applied
+      const applied = report.results.filter((r) => r.outcome === 'applied');
+      const notApplied = report.results.length - applied.length;
 
       set((state) => ({
-        updates: state.updates.filter((_, i) => failedIndices.includes(i)),
-        error: failedIndices.length > 0
-          ? `${failedIndices.length} update(s) failed`
+        updates: state.updates.filter(
+          (u) => !applied.some((r) => r.update.skill_id === u.skill_id && r.update.agent === u.agent)
+        ),
+        error: notApplied > 0
+          ? `${notApplied} update(s) not applied`
           : null,
       }));
     } catch (error) {
@@ -88,11 +91,14 @@ export const useUpdatesStore = create<UpdatesState>((set, get) => ({
     }
   },
 
-  skipVersion: async (skillId, version) => {
+  skipVersion: async (update, mode = 'version', allAgents = false) => {
     try {
-      await api.updates.skip(skillId, version);
+      const scope = allAgents ? {} : { agent: update.agent, registryUrl: update.registry_url };
+      await api.updates.skip(update.skill_id, update.new_version, scope, mode);
       set((state) => ({
-        updates: state.updates.filter((u) => u.skill_id !== skillId),
+        updates: state.updates.filter(
+          (u) => !(u.skill_id === update.skill_id && (allAgents || u.agent === update.agent))
+        ),
       }));
     } catch (error) {
       set({ error: String(error) });